@@ -0,0 +1,50 @@
+use std::{fmt::Display, io};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssetError {
+    FileError(io::ErrorKind),
+    /// The line number and contents of a header line that couldn't be parsed
+    InvalidHeader(usize, String),
+    InvalidSection(usize, String),
+    UnknownColor(String),
+    MissingFrames,
+    /// A mask section was provided before any frames
+    MaskWithoutFrame,
+    /// The frame number of a mask that doesn't match the frame's dimensions
+    MaskSizeMismatch(usize),
+    /// Expected frame count from the header and the number of frames found
+    FrameCountMismatch(usize, usize),
+}
+
+impl Display for AssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FileError(kind) => write!(f, "Couldn't read the file: {}", kind),
+            Self::InvalidHeader(line, contents) => {
+                write!(f, "Line {}: invalid header {:?}", line, contents)
+            }
+            Self::InvalidSection(line, contents) => {
+                write!(f, "Line {}: invalid section {:?}", line, contents)
+            }
+            Self::UnknownColor(color) => write!(f, "Unknown color {:?}", color),
+            Self::MissingFrames => write!(f, "The sprite has no frames"),
+            Self::MaskWithoutFrame => write!(f, "A mask comes before the first frame"),
+            Self::MaskSizeMismatch(frame) => {
+                write!(f, "The mask of frame {} isn't the size of the frame", frame)
+            }
+            Self::FrameCountMismatch(expected, found) => write!(
+                f,
+                "The header expects {} frames but {} were found",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for AssetError {
+    fn from(error: io::Error) -> Self {
+        Self::FileError(error.kind())
+    }
+}
+
+pub type AssetResult<T> = Result<T, AssetError>;
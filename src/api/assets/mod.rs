@@ -0,0 +1,5 @@
+mod asset_error;
+mod sprite_loader;
+
+pub use asset_error::*;
+pub use sprite_loader::*;
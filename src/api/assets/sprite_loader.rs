@@ -0,0 +1,420 @@
+use std::{cell::RefCell, collections::HashMap, env, fs, io, path::PathBuf, time::SystemTime};
+
+use crossterm::style::Color;
+
+use crate::{
+    api::display::{
+        element::{parse_color, DEFAULT_FOREGROUND},
+        map_from_str, Layout, Point,
+    },
+    components::{Animation, AnimationFrame, Sprite},
+    helpers::log_warning,
+};
+
+use super::{AssetError, AssetResult};
+
+pub const ASSETS_DIRECTORY: &str = "assets";
+/// Environment variable that overrides the assets directory, useful when running the game outside of the repository
+pub const ASSETS_DIRECTORY_VARIABLE: &str = "RUSTY_ASTEROIDS_ASSETS";
+const SPRITE_EXTENSION: &str = "sprite";
+
+const DEFAULT_FRAME_DURATION: u128 = 100;
+const SECTION_PREFIX: &str = "---";
+
+thread_local! {
+    // Sprites are cached along with the modified time of their file, so an edited file is picked up the next time the sprite is loaded
+    static SPRITE_CACHE: RefCell<HashMap<PathBuf, (SystemTime, Sprite)>> = RefCell::new(HashMap::new());
+}
+
+pub fn get_assets_directory() -> PathBuf {
+    PathBuf::from(env::var(ASSETS_DIRECTORY_VARIABLE).unwrap_or(ASSETS_DIRECTORY.to_string()))
+}
+
+pub fn get_sprite_path(name: &str) -> PathBuf {
+    get_assets_directory().join(format!("{}.{}", name, SPRITE_EXTENSION))
+}
+
+/// Loads a sprite from the assets directory, the file is only parsed again if it has been modified since it was last loaded
+pub fn load_sprite(name: &str) -> AssetResult<Sprite> {
+    let path = get_sprite_path(name);
+    let modified = fs::metadata(&path)?.modified()?;
+
+    let cached_sprite = SPRITE_CACHE.with(|cache| {
+        cache
+            .borrow()
+            .get(&path)
+            .filter(|(cached_modified, _)| *cached_modified == modified)
+            .map(|(_, sprite)| sprite.clone())
+    });
+
+    if let Some(sprite) = cached_sprite {
+        return Ok(sprite);
+    }
+
+    let sprite = parse_sprite(&fs::read_to_string(&path)?)?;
+
+    SPRITE_CACHE.with(|cache| cache.borrow_mut().insert(path, (modified, sprite.clone())));
+
+    Ok(sprite)
+}
+
+/// Loads a sprite from the assets directory, falling back to the provided built-in sprite if the file is missing or invalid. An
+/// invalid file is logged so the artist editing it can find out why it isn't used.
+pub fn load_sprite_or_default<F: FnOnce() -> Sprite>(name: &str, default: F) -> Sprite {
+    match load_sprite(name) {
+        Ok(sprite) => sprite,
+        Err(AssetError::FileError(io::ErrorKind::NotFound)) => default(),
+        Err(error) => {
+            log_warning(&format!(
+                "Couldn't load the sprite {}, using the built-in sprite: {}",
+                get_sprite_path(name).display(),
+                error
+            ));
+
+            default()
+        }
+    }
+}
+
+fn get_mask_color(value: char) -> AssetResult<Option<Color>> {
+    let color = match value {
+        ' ' | '.' => return Ok(None),
+        'k' => Color::Black,
+        'A' => Color::DarkGrey,
+        'a' => Color::Grey,
+        'r' => Color::Red,
+        'R' => Color::DarkRed,
+        'g' => Color::Green,
+        'G' => Color::DarkGreen,
+        'y' => Color::Yellow,
+        'Y' => Color::DarkYellow,
+        'b' => Color::Blue,
+        'B' => Color::DarkBlue,
+        'm' => Color::Magenta,
+        'M' => Color::DarkMagenta,
+        'c' => Color::Cyan,
+        'C' => Color::DarkCyan,
+        'w' => Color::White,
+        _ => return Err(AssetError::UnknownColor(value.to_string())),
+    };
+
+    Ok(Some(color))
+}
+
+#[derive(Default)]
+struct ParsedFrame<'a> {
+    duration: Option<u128>,
+    rows: Vec<&'a str>,
+    mask: Option<Vec<&'a str>>,
+}
+
+enum Section {
+    Header,
+    Frame,
+    Mask,
+}
+
+/// Blank lines are allowed between sections, so trailing blank rows are removed from frames and masks
+fn trim_trailing_rows(rows: &mut Vec<&str>) {
+    while rows.last().is_some_and(|row| row.trim().is_empty()) {
+        rows.pop();
+    }
+}
+
+fn parse_anchor(value: &str) -> Option<Point<i64>> {
+    let values: Vec<i64> = value
+        .split(|char: char| char == ',' || char.is_whitespace())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.parse().ok())
+        .collect::<Option<Vec<i64>>>()?;
+
+    match values[..] {
+        [width, height] => Some(Point::new(width, height)),
+        _ => None,
+    }
+}
+
+fn apply_mask(layout: &mut Layout, mask: &[&str], frame_number: usize) -> AssetResult<()> {
+    for (row_number, mask_row) in mask.iter().enumerate() {
+        let row = layout
+            .map
+            .get_mut(row_number)
+            .ok_or(AssetError::MaskSizeMismatch(frame_number))?;
+
+        for (column_number, mask_value) in mask_row.chars().enumerate() {
+            let color = get_mask_color(mask_value)?;
+
+            let element = row
+                .get_mut(column_number)
+                .ok_or(AssetError::MaskSizeMismatch(frame_number))?;
+
+            if let (Some(element), Some(color)) = (element, color) {
+                element.foreground = color;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses the contents of a sprite file.
+///
+/// A sprite file starts with a header of `key: value` lines (`color`, `duration`, `anchor` and `frames`), followed by one or more
+/// `--- frame [duration]` sections containing the ascii art. Each frame can be followed by a `--- mask` section, where each
+/// character sets the color of the character at the same position in the frame, e.g. `r` for red or `R` for dark red.
+pub fn parse_sprite(contents: &str) -> AssetResult<Sprite> {
+    let mut color = DEFAULT_FOREGROUND;
+    let mut duration = DEFAULT_FRAME_DURATION;
+    let mut anchor: Point<i64> = Point::default();
+    let mut expected_frames: Option<usize> = None;
+
+    let mut frames: Vec<ParsedFrame> = vec![];
+    let mut section = Section::Header;
+
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+
+        if let Some(section_details) = line.strip_prefix(SECTION_PREFIX) {
+            let invalid_section = || AssetError::InvalidSection(line_number, line.to_string());
+
+            let mut parts = section_details.split_whitespace();
+
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("frame"), frame_duration, None) => {
+                    let frame_duration = match frame_duration {
+                        Some(value) => Some(value.parse().map_err(|_| invalid_section())?),
+                        None => None,
+                    };
+
+                    frames.push(ParsedFrame {
+                        duration: frame_duration,
+                        ..Default::default()
+                    });
+
+                    section = Section::Frame;
+                }
+                (Some("mask"), None, None) => {
+                    let frame = frames.last_mut().ok_or(AssetError::MaskWithoutFrame)?;
+
+                    if frame.mask.is_some() {
+                        return Err(invalid_section());
+                    }
+
+                    frame.mask = Some(vec![]);
+
+                    section = Section::Mask;
+                }
+                _ => return Err(invalid_section()),
+            }
+
+            continue;
+        }
+
+        match section {
+            Section::Header => {
+                let trimmed = line.trim();
+
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    continue;
+                }
+
+                let invalid_header = || AssetError::InvalidHeader(line_number, line.to_string());
+
+                let (key, value) = trimmed.split_once(':').ok_or_else(invalid_header)?;
+                let value = value.trim();
+
+                match key.trim() {
+                    "color" => {
+                        color =
+                            parse_color(value).ok_or(AssetError::UnknownColor(value.to_string()))?
+                    }
+                    "duration" => duration = value.parse().map_err(|_| invalid_header())?,
+                    "anchor" => anchor = parse_anchor(value).ok_or_else(invalid_header)?,
+                    "frames" => {
+                        expected_frames = Some(value.parse().map_err(|_| invalid_header())?)
+                    }
+                    _ => return Err(invalid_header()),
+                }
+            }
+            Section::Frame => frames.last_mut().unwrap().rows.push(line),
+            Section::Mask => frames
+                .last_mut()
+                .and_then(|frame| frame.mask.as_mut())
+                .unwrap()
+                .push(line),
+        }
+    }
+
+    if frames.is_empty() {
+        return Err(AssetError::MissingFrames);
+    }
+
+    if let Some(expected_frames) = expected_frames {
+        if expected_frames != frames.len() {
+            return Err(AssetError::FrameCountMismatch(
+                expected_frames,
+                frames.len(),
+            ));
+        }
+    }
+
+    let mut animation_frames: Vec<AnimationFrame> = Vec::with_capacity(frames.len());
+
+    for (frame_number, mut frame) in frames.into_iter().enumerate() {
+        trim_trailing_rows(&mut frame.rows);
+
+        let mut layout = Layout::from_map(map_from_str(&frame.rows.join("\n"), color), None);
+
+        if let Some(mut mask) = frame.mask {
+            trim_trailing_rows(&mut mask);
+
+            apply_mask(&mut layout, &mask, frame_number)?;
+        }
+
+        animation_frames.push(AnimationFrame::new(
+            layout,
+            frame.duration.unwrap_or(duration),
+        ));
+    }
+
+    Ok(Sprite::new(Animation::new(animation_frames), anchor))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crossterm::style::Color;
+
+    use crate::api::{
+        assets::AssetError,
+        display::{Layout, Point},
+    };
+
+    use super::{load_sprite_or_default, parse_sprite, Sprite, ASSETS_DIRECTORY, SPRITE_EXTENSION};
+
+    const SPRITE: &str = "# A test sprite
+color: green
+duration: 150
+anchor: 1, 2
+frames: 2
+
+--- frame
+ ^
+/_\\
+
+--- mask
+ r
+R.b
+--- frame 50
+ !
+/_\\";
+
+    #[test]
+    fn it_parses_the_header_and_frames() {
+        let mut sprite = parse_sprite(SPRITE).unwrap();
+
+        assert_eq!(sprite.anchor, Point::new(1, 2));
+        assert_eq!(sprite.get_layout().dimensions, Point::new(3, 2));
+
+        // The first frame uses the duration from the header
+        assert!(!sprite.animation.advance(149));
+        assert!(sprite.animation.advance(1));
+
+        assert_eq!(
+            sprite.get_layout(),
+            &Layout::from_ascii(" !\n/_\\", Color::Green)
+        );
+
+        // The second frame has its own duration
+        assert!(sprite.animation.advance(50));
+    }
+
+    #[test]
+    fn it_applies_the_color_mask() {
+        let sprite = parse_sprite(SPRITE).unwrap();
+        let map = &sprite.get_layout().map;
+
+        assert_eq!(map[0][1].unwrap().foreground, Color::Red);
+        assert_eq!(map[1][0].unwrap().foreground, Color::DarkRed);
+        // A dot in the mask keeps the sprite color
+        assert_eq!(map[1][1].unwrap().foreground, Color::Green);
+        assert_eq!(map[1][2].unwrap().foreground, Color::Blue);
+    }
+
+    #[test]
+    fn it_keeps_leading_blank_rows() {
+        let sprite = parse_sprite("--- frame\n\n *").unwrap();
+
+        assert_eq!(sprite.get_layout().dimensions, Point::new(2, 2));
+    }
+
+    #[test]
+    fn it_returns_errors_for_invalid_sprites() {
+        assert_eq!(
+            parse_sprite("speed: 10\n--- frame\n*"),
+            Err(AssetError::InvalidHeader(1, "speed: 10".to_string()))
+        );
+
+        assert_eq!(parse_sprite("color: green"), Err(AssetError::MissingFrames));
+
+        assert_eq!(
+            parse_sprite("frames: 2\n--- frame\n*"),
+            Err(AssetError::FrameCountMismatch(2, 1))
+        );
+
+        assert_eq!(
+            parse_sprite("--- frame\n*\n--- mask\nrr"),
+            Err(AssetError::MaskSizeMismatch(0))
+        );
+
+        assert_eq!(
+            parse_sprite("--- frame\n*\n--- mask\nz"),
+            Err(AssetError::UnknownColor("z".to_string()))
+        );
+
+        assert_eq!(
+            parse_sprite("--- mask\n*"),
+            Err(AssetError::MaskWithoutFrame)
+        );
+
+        assert_eq!(
+            parse_sprite("--- frames\n*"),
+            Err(AssetError::InvalidSection(1, "--- frames".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_parses_the_bundled_sprites() {
+        let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(ASSETS_DIRECTORY);
+
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path
+                .extension()
+                .is_some_and(|extension| extension == SPRITE_EXTENSION)
+            {
+                let contents = fs::read_to_string(&path).unwrap();
+
+                if let Err(error) = parse_sprite(&contents) {
+                    panic!("Invalid sprite {:?}: {:?}", path, error);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_only_uses_the_default_sprite_when_the_file_is_missing() {
+        let default = || Sprite::from_ascii_frames(&["?"], Color::White, 0, Point::default());
+
+        assert_eq!(
+            load_sprite_or_default("missing", default).get_layout(),
+            default().get_layout()
+        );
+        assert_ne!(
+            load_sprite_or_default("heart", default).get_layout(),
+            default().get_layout()
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use std::env;
+
+use crossterm::style::Color;
+
+use super::{
+    element::{DEFAULT_BACKGROUND, DEFAULT_FOREGROUND},
+    Element,
+};
+
+/// The colors a terminal is able to display, every element is mapped down to the nearest color the terminal supports before it is printed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorMode {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    Monochrome,
+}
+
+type Rgb = (u8, u8, u8);
+
+const ANSI_16_COLORS: [(Color, Rgb); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (128, 0, 0)),
+    (Color::DarkGreen, (0, 128, 0)),
+    (Color::DarkYellow, (128, 128, 0)),
+    (Color::DarkBlue, (0, 0, 128)),
+    (Color::DarkMagenta, (128, 0, 128)),
+    (Color::DarkCyan, (0, 128, 128)),
+    (Color::Grey, (192, 192, 192)),
+    (Color::DarkGrey, (128, 128, 128)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (0, 0, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The channel values used by the 6x6x6 color cube of the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+const CUBE_START: u8 = 16;
+const GREYSCALE_START: u8 = 232;
+
+/// Colors with a channel brighter than this are shown as lit in monochrome mode
+const MONOCHROME_THRESHOLD: u8 = 64;
+
+const UPPER_HALF_BLOCK: char = '▀';
+const LOWER_HALF_BLOCK: char = '▄';
+const FULL_BLOCK: char = '█';
+
+fn get_distance(a: Rgb, b: Rgb) -> u32 {
+    let channel_distance = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+
+    channel_distance(a.0, b.0) + channel_distance(a.1, b.1) + channel_distance(a.2, b.2)
+}
+
+fn get_ansi_value_rgb(value: u8) -> Rgb {
+    if value < CUBE_START {
+        return ANSI_16_COLORS[value as usize].1;
+    }
+
+    if value >= GREYSCALE_START {
+        let level = 8 + (value - GREYSCALE_START) * 10;
+
+        return (level, level, level);
+    }
+
+    let index = value - CUBE_START;
+
+    (
+        CUBE_LEVELS[(index / 36) as usize],
+        CUBE_LEVELS[(index / 6 % 6) as usize],
+        CUBE_LEVELS[(index % 6) as usize],
+    )
+}
+
+/// Returns the rgb value of a color, or None for colors that are left to the terminal such as Reset
+pub fn get_rgb(color: Color) -> Option<Rgb> {
+    match color {
+        Color::Rgb { r, g, b } => Some((r, g, b)),
+        Color::AnsiValue(value) => Some(get_ansi_value_rgb(value)),
+        Color::Reset => None,
+        _ => ANSI_16_COLORS
+            .iter()
+            .find(|(ansi_color, _)| *ansi_color == color)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+fn get_nearest_cube_index(channel: u8) -> u8 {
+    (0..CUBE_LEVELS.len())
+        .min_by_key(|index| (CUBE_LEVELS[*index] as i32 - channel as i32).abs())
+        .unwrap() as u8
+}
+
+fn get_nearest_ansi_256(rgb: Rgb) -> u8 {
+    let (r, g, b) = (
+        get_nearest_cube_index(rgb.0),
+        get_nearest_cube_index(rgb.1),
+        get_nearest_cube_index(rgb.2),
+    );
+
+    let cube_value = CUBE_START + 36 * r + 6 * g + b;
+
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let greyscale_value = GREYSCALE_START + ((average.saturating_sub(3)) / 10).min(23) as u8;
+
+    if get_distance(rgb, get_ansi_value_rgb(greyscale_value))
+        < get_distance(rgb, get_ansi_value_rgb(cube_value))
+    {
+        greyscale_value
+    } else {
+        cube_value
+    }
+}
+
+fn get_nearest_ansi_16(rgb: Rgb) -> Color {
+    ANSI_16_COLORS
+        .iter()
+        .min_by_key(|(_, ansi_rgb)| get_distance(rgb, *ansi_rgb))
+        .unwrap()
+        .0
+}
+
+fn get_is_lit(color: Color) -> bool {
+    get_rgb(color).is_some_and(|(r, g, b)| r.max(g).max(b) > MONOCHROME_THRESHOLD)
+}
+
+impl ColorMode {
+    /// Detects the color support of the terminal from the environment
+    pub fn detect() -> Self {
+        ColorMode::detect_from(
+            env::var("COLORTERM").ok().as_deref(),
+            env::var("TERM").ok().as_deref(),
+            env::var_os("NO_COLOR").is_some(),
+        )
+    }
+
+    pub fn detect_from(colorterm: Option<&str>, term: Option<&str>, no_color: bool) -> Self {
+        if no_color {
+            return ColorMode::Monochrome;
+        }
+
+        if matches!(colorterm, Some("truecolor") | Some("24bit")) {
+            return ColorMode::TrueColor;
+        }
+
+        match term {
+            Some("dumb") => ColorMode::Monochrome,
+            Some(term) if term.contains("256color") => ColorMode::Ansi256,
+            _ => ColorMode::Ansi16,
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "truecolor" | "24bit" => Some(ColorMode::TrueColor),
+            "256" => Some(ColorMode::Ansi256),
+            "16" => Some(ColorMode::Ansi16),
+            "mono" | "monochrome" => Some(ColorMode::Monochrome),
+            _ => None,
+        }
+    }
+
+    pub fn map_color(&self, color: Color) -> Color {
+        let rgb = match get_rgb(color) {
+            Some(rgb) => rgb,
+            None => return color,
+        };
+
+        match self {
+            ColorMode::TrueColor => color,
+            ColorMode::Ansi256 => match color {
+                Color::Rgb { .. } => Color::AnsiValue(get_nearest_ansi_256(rgb)),
+                _ => color,
+            },
+            ColorMode::Ansi16 => match color {
+                Color::Rgb { .. } | Color::AnsiValue(..) => get_nearest_ansi_16(rgb),
+                _ => color,
+            },
+            ColorMode::Monochrome => {
+                if get_is_lit(color) {
+                    Color::White
+                } else {
+                    Color::Black
+                }
+            }
+        }
+    }
+
+    /// Maps the colors of the element to the nearest supported colors. In monochrome mode, colored backgrounds and half
+    /// blocks are replaced with block characters so that shapes drawn with colors are still visible.
+    pub fn map_element(&self, element: &Element) -> Element {
+        if *self != ColorMode::Monochrome {
+            return Element::new(
+                element.value,
+                self.map_color(element.background),
+                self.map_color(element.foreground),
+            );
+        }
+
+        let (top_lit, bottom_lit) = match element.value {
+            UPPER_HALF_BLOCK => (
+                get_is_lit(element.foreground),
+                get_is_lit(element.background),
+            ),
+            LOWER_HALF_BLOCK => (
+                get_is_lit(element.background),
+                get_is_lit(element.foreground),
+            ),
+            ' ' => {
+                let background_lit = get_is_lit(element.background);
+
+                (background_lit, background_lit)
+            }
+            // Any other character is drawn in the foreground color so it stays readable
+            value => return Element::new(value, Color::Black, Color::White),
+        };
+
+        let value = match (top_lit, bottom_lit) {
+            (true, true) => FULL_BLOCK,
+            (true, false) => UPPER_HALF_BLOCK,
+            (false, true) => LOWER_HALF_BLOCK,
+            (false, false) => ' ',
+        };
+
+        Element::new(value, Color::Black, Color::White)
+    }
+
+    pub fn get_default_element(&self) -> Element {
+        self.map_element(&Element::new(' ', DEFAULT_BACKGROUND, DEFAULT_FOREGROUND))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::api::display::Element;
+
+    use super::ColorMode;
+
+    #[test]
+    fn it_detects_the_color_mode_from_the_environment() {
+        assert_eq!(
+            ColorMode::detect_from(Some("truecolor"), Some("xterm-256color"), false),
+            ColorMode::TrueColor
+        );
+        assert_eq!(
+            ColorMode::detect_from(None, Some("xterm-256color"), false),
+            ColorMode::Ansi256
+        );
+        assert_eq!(
+            ColorMode::detect_from(None, Some("xterm"), false),
+            ColorMode::Ansi16
+        );
+        assert_eq!(
+            ColorMode::detect_from(None, Some("dumb"), false),
+            ColorMode::Monochrome
+        );
+        assert_eq!(
+            ColorMode::detect_from(Some("truecolor"), None, true),
+            ColorMode::Monochrome
+        );
+    }
+
+    #[test]
+    fn it_maps_rgb_colors_to_the_256_color_palette() {
+        let mode = ColorMode::Ansi256;
+
+        assert_eq!(
+            mode.map_color(Color::Rgb { r: 255, g: 0, b: 0 }),
+            Color::AnsiValue(196)
+        );
+        assert_eq!(
+            mode.map_color(Color::Rgb {
+                r: 100,
+                g: 100,
+                b: 100
+            }),
+            Color::AnsiValue(241)
+        );
+        // Named colors are already supported
+        assert_eq!(mode.map_color(Color::Cyan), Color::Cyan);
+    }
+
+    #[test]
+    fn it_maps_colors_to_the_16_color_palette() {
+        let mode = ColorMode::Ansi16;
+
+        assert_eq!(
+            mode.map_color(Color::Rgb {
+                r: 240,
+                g: 20,
+                b: 10
+            }),
+            Color::Red
+        );
+        assert_eq!(mode.map_color(Color::AnsiValue(22)), Color::DarkGreen);
+        assert_eq!(mode.map_color(Color::Yellow), Color::Yellow);
+    }
+
+    #[test]
+    fn it_keeps_shapes_visible_in_monochrome() {
+        let mode = ColorMode::Monochrome;
+
+        // Borders are drawn as spaces with a colored background
+        assert_eq!(
+            mode.map_element(&Element::new(' ', Color::Cyan, Color::Cyan))
+                .value,
+            '█'
+        );
+
+        // Half blocks are chosen from which halves are lit
+        assert_eq!(
+            mode.map_element(&Element::new('▀', Color::Black, Color::Yellow))
+                .value,
+            '▀'
+        );
+        assert_eq!(
+            mode.map_element(&Element::new('▀', Color::Green, Color::Black))
+                .value,
+            '▄'
+        );
+
+        assert_eq!(
+            mode.map_element(&Element::new('^', Color::Black, Color::DarkBlue)),
+            Element::new('^', Color::Black, Color::White)
+        );
+    }
+}
@@ -1,21 +1,22 @@
 use crossterm::style::Color;
 use crossterm::terminal::size;
-use uuid::Uuid;
 
+use crate::api::assets::load_sprite_or_default;
 use crate::app::GameState;
-use crate::components::{Drawable, DrawableState, Health};
-use crate::systems::EntityController;
-use crate::user_display::{HEART, NUMBER_VECTOR, X};
+use crate::components::{DrawableState, Sprite};
+use crate::user_display::{Theme, HEART, HEART_SPRITE, NUMBER_VECTOR, X};
+use crate::world::{Entity, World};
 
 use super::element::DEFAULT_BACKGROUND;
-use super::{create_map, map_from_str, Element, Map, Point};
 use super::{display_controller_error::DisplayControllerError, Layout};
+use super::{map_from_str, Element, Point};
 
 pub struct DisplayController {
     entity_drawable_offset: Point<i64>,
     pub layout: Layout,
     pub drawable_dimensions: Point<i64>,
-    screen_size: Point<i64>,
+    /// Drawn in the lives box every frame, so it is loaded once rather than read from the assets directory each frame
+    heart: Sprite,
 }
 
 type DisplayControllerResult<T> = Result<T, DisplayControllerError>;
@@ -25,6 +26,19 @@ const BOX_PADDING: u64 = 1;
 
 pub const MINIMUM_SCREEN_WIDTH: u64 = (GAME_DETAILS_BOX_WIDTH + BOX_PADDING) * 2 + 10;
 
+/// The row of the HUD the co-op score is drawn on, below the boss health bar
+const HUD_SCORE_HEIGHT: i64 = 6;
+
+/// The score of each player, e.g. `Player 1: 30   Player 2: 12`
+pub fn get_player_scores_text(player_scores: &[u64]) -> String {
+    player_scores
+        .iter()
+        .enumerate()
+        .map(|(index, score)| format!("Player {}: {}", index + 1, score))
+        .collect::<Vec<String>>()
+        .join("   ")
+}
+
 pub fn get_screen_size() -> Point<i64> {
     let (rows, columns) = size().unwrap();
 
@@ -36,7 +50,7 @@ impl DisplayController {
     ///
     /// # Arguments
     ///
-    /// * `dimensions` - The controllable area, if None then the entire screen is used
+    /// * `dimensions` - The controllable area, the HUD is laid out across its width so it doesn't have to match the terminal
     ///
     /// ```
     pub fn new(
@@ -48,74 +62,165 @@ impl DisplayController {
             drawable_dimensions: dimensions - entity_drawable_offset,
             // The offset is where all drawing will be done, this is the center of the terminal screen
             entity_drawable_offset,
-            screen_size: get_screen_size(),
+            heart: load_sprite_or_default(HEART_SPRITE, || {
+                Sprite::from_ascii_frames(&[HEART], Color::Red, 0, Point::default())
+            }),
         })
     }
 
+    /// Draws the HUD, with a box for the lives of each player. A single player has their score in the box on the right, in co-op
+    /// the second player's lives take that box and the score is drawn between the boxes.
+    ///
+    /// # Arguments
+    ///
+    /// * `lives` - The lives of each player
     pub fn draw_game_state(
         &mut self,
         game_state: &GameState,
-        lives: u32,
+        lives: &[u32],
+        theme: &Theme,
     ) -> DisplayControllerResult<&mut Self> {
-        self.draw_lives(lives)?;
-        self.draw_score(game_state.score)?;
+        let right_box_width = self.layout.dimensions.width - GAME_DETAILS_BOX_WIDTH as i64;
+
+        match lives {
+            [first, second, ..] => {
+                self.draw_lives(*first, BOX_PADDING as i64, "Player 1", theme)?;
+                self.draw_lives(*second, right_box_width, "Player 2", theme)?;
+
+                let score_text = match game_state.player_scores.is_empty() {
+                    true => format!("Score: {}", game_state.score),
+                    false => get_player_scores_text(&game_state.player_scores),
+                };
+
+                self.draw_str(
+                    &score_text,
+                    DEFAULT_BACKGROUND,
+                    theme.score,
+                    Point::new(
+                        self.layout.dimensions.width / 2 - score_text.len() as i64 / 2,
+                        HUD_SCORE_HEIGHT,
+                    ),
+                )?;
+            }
+            _ => {
+                self.draw_lives(
+                    lives.first().copied().unwrap_or_default(),
+                    BOX_PADDING as i64,
+                    "Lives",
+                    theme,
+                )?;
+                self.draw_score(game_state.score, theme)?;
+            }
+        }
 
         Ok(self)
     }
 
-    fn draw_lives(&mut self, lives: u32) -> DisplayControllerResult<()> {
-        let heart_map = map_from_str(HEART, Color::Red);
+    fn draw_lives(
+        &mut self,
+        lives: u32,
+        start_width: i64,
+        label: &str,
+        theme: &Theme,
+    ) -> DisplayControllerResult<()> {
+        let heart = self.heart.clone().with_color(theme.heart);
 
         self.layout.draw_rect(
-            &Point::new(BOX_PADDING as i64, 0),
+            &Point::new(start_width, 0),
             &Point::new(GAME_DETAILS_BOX_WIDTH as i64, 8),
-            Element::new('❤', DEFAULT_BACKGROUND, Color::Red),
+            Element::new('❤', DEFAULT_BACKGROUND, theme.lives),
         )?;
 
-        self.layout
-            .draw_map(&heart_map, Point::new(5, 2), &Default::default())?;
+        self.layout.draw_map(
+            &heart.get_layout().map,
+            Point::new(start_width + 4, 2),
+            &Default::default(),
+        )?;
 
         self.layout.draw_map(
             &map_from_str(X, Color::Black),
-            Point::new(19, 3),
+            Point::new(start_width + 18, 3),
             &Default::default(),
         )?;
 
-        self.draw_u32(lives, Point::new(27, 2), Color::Red)?;
+        self.draw_u32(lives, Point::new(start_width + 26, 2), theme.lives)?;
 
-        self.draw_str("Lives", DEFAULT_BACKGROUND, Color::Red, Point::new(19, 1))?;
+        self.draw_str(
+            label,
+            DEFAULT_BACKGROUND,
+            theme.lives,
+            Point::new(start_width + 18, 1),
+        )?;
 
         Ok(())
     }
 
-    fn draw_score(&mut self, score: u64) -> DisplayControllerResult<()> {
+    fn draw_score(&mut self, score: u64, theme: &Theme) -> DisplayControllerResult<()> {
         let start_position = Point {
             height: 0,
-            width: self.screen_size.width - GAME_DETAILS_BOX_WIDTH as i64,
+            width: self.layout.dimensions.width - GAME_DETAILS_BOX_WIDTH as i64,
         };
 
         self.layout.draw_rect(
             &start_position,
             &Point::new(GAME_DETAILS_BOX_WIDTH as i64, 8),
-            Element::new('⦿', DEFAULT_BACKGROUND, Color::Cyan),
+            Element::new('⦿', DEFAULT_BACKGROUND, theme.score),
         )?;
 
         self.draw_u32(
             score as u32,
             Point::new(start_position.width + 5, 2),
-            Color::Cyan,
+            theme.score,
         )?;
 
         self.draw_str(
             "Score",
             DEFAULT_BACKGROUND,
-            Color::Cyan,
+            theme.score,
             Point::new(start_position.width + 19, 1),
         )?;
 
         Ok(())
     }
 
+    /// Draws the name and health bar of a boss between the lives and the score
+    pub fn draw_boss_health(
+        &mut self,
+        name: &str,
+        health: u32,
+        max_health: u32,
+        theme: &Theme,
+    ) -> DisplayControllerResult<()> {
+        let start_width = (GAME_DETAILS_BOX_WIDTH + BOX_PADDING * 2) as i64;
+        let bar_width = self.layout.dimensions.width - start_width * 2 - 2;
+
+        if bar_width <= 0 {
+            return Ok(());
+        }
+
+        let filled_width = bar_width * health as i64 / max_health.max(1) as i64;
+
+        self.draw_str(
+            name,
+            DEFAULT_BACKGROUND,
+            theme.warning,
+            Point::new(start_width + 1, 2),
+        )?;
+
+        let bar: String = (0..bar_width)
+            .map(|index| if index < filled_width { '█' } else { '░' })
+            .collect();
+
+        self.draw_str(
+            &bar,
+            DEFAULT_BACKGROUND,
+            theme.warning,
+            Point::new(start_width + 1, 4),
+        )?;
+
+        Ok(())
+    }
+
     pub fn draw_u32(
         &mut self,
         numbers: u32,
@@ -171,30 +276,28 @@ impl DisplayController {
         Ok((self, has_drawn_drawable))
     }
 
-    /// Draws the entities within a given entity controller. It also removes items from the entities array if they are outside of the drawable dimensions.
-    /// This is primarily used for the Bullet controller and asteroid controller
-    pub fn draw_entity_controller_items<T: Drawable + Health>(
-        &mut self,
-        entity_controller: &mut EntityController<T>,
-    ) -> &mut Self {
-        // Create an array of delete uuid's and iterate over them after we determine which ones are to be deleted. This is because we can't mutable use the entity_controller within the immutable iterator
-        let mut delete_uuids: Vec<Uuid> = vec![];
-
-        for entity in entity_controller.get_all_drawable_states() {
-            let result = self.draw_drawable(entity);
+    /// Draws every entity in the world that has a position and a sprite, effects are drawn over everything else
+    pub fn draw_world(&mut self, world: &World) -> DisplayControllerResult<&mut Self> {
+        let (effects, entities): (Vec<Entity>, Vec<Entity>) = world
+            .get_entities()
+            .into_iter()
+            .partition(|entity| !world.colliders.contains(*entity));
 
-            let (_, did_draw) = result.unwrap();
-
-            if !did_draw {
-                delete_uuids.push(entity.uuid);
-            }
-        }
+        for entity in entities.into_iter().chain(effects) {
+            let (Some(location), Some(sprite)) =
+                (world.positions.get(entity), world.sprites.get(entity))
+            else {
+                continue;
+            };
 
-        for uuid in delete_uuids {
-            entity_controller.delete_entity(uuid);
+            self.layout.draw_map(
+                &sprite.get_layout().map,
+                *location + self.entity_drawable_offset,
+                &self.entity_drawable_offset,
+            )?;
         }
 
-        self
+        Ok(self)
     }
 }
 
@@ -207,10 +310,6 @@ mod tests {
 
     use super::DisplayController;
 
-    const WIDTH: i64 = 30;
-    const HEIGHT: i64 = 20;
-    const DIMENSIONS: &Point<i64> = &Point::new(WIDTH, HEIGHT);
-
     struct MockDrawble {
         drawable_state: DrawableState,
     }
@@ -229,10 +328,6 @@ mod tests {
     }
 
     impl Drawable for MockDrawble {
-        fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
-            todo!()
-        }
-
         fn get_drawable_state(&self) -> &DrawableState {
             &self.drawable_state
         }
@@ -244,7 +339,7 @@ mod tests {
         let mut display_controller =
             DisplayController::new(get_screen_size(), Default::default()).unwrap();
 
-        let result = display_controller.draw_drawable(&MockDrawble::new().get_drawable_state());
+        let result = display_controller.draw_drawable(MockDrawble::new().get_drawable_state());
 
         assert!(result.err().is_none());
     }
@@ -1,16 +1,29 @@
-use std::fmt::Error;
+use std::fmt::{Display, Error};
 
 use crossterm::ErrorKind;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DisplayControllerError {
     PositionOutOfRange,
     CrossTermWriteError,
     CasteError(Error),
+    /// Why the image couldn't be opened or decoded, e.g. the file is missing or its format isn't supported
+    ImageLoadError(String),
+}
+
+impl Display for DisplayControllerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PositionOutOfRange => write!(f, "Position out of range"),
+            Self::CrossTermWriteError => write!(f, "Couldn't write to the terminal"),
+            Self::CasteError(error) => write!(f, "Couldn't format the output: {}", error),
+            Self::ImageLoadError(cause) => write!(f, "Couldn't load the image: {}", cause),
+        }
+    }
 }
 
 impl DisplayControllerError {
-    pub fn from_crossterm_error(error: ErrorKind) -> Self {
+    pub fn from_crossterm_error(_error: ErrorKind) -> Self {
         Self::CrossTermWriteError
     }
 }
@@ -66,8 +66,43 @@ impl Element {
     }
 }
 
-impl From<u32> for Element {
-    fn from(_: u32) -> Self {
-        todo!();
+/// Parses a color name such as `dark_red`, or a hex value such as `#ff8800`, into a color
+pub fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim().to_lowercase();
+
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+
+        let parse_channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+
+        return Some(Color::Rgb {
+            r: parse_channel(0)?,
+            g: parse_channel(2)?,
+            b: parse_channel(4)?,
+        });
     }
+
+    let color = match value.replace(['-', ' '], "_").as_str() {
+        "black" => Color::Black,
+        "dark_grey" | "dark_gray" => Color::DarkGrey,
+        "red" => Color::Red,
+        "dark_red" => Color::DarkRed,
+        "green" => Color::Green,
+        "dark_green" => Color::DarkGreen,
+        "yellow" => Color::Yellow,
+        "dark_yellow" => Color::DarkYellow,
+        "blue" => Color::Blue,
+        "dark_blue" => Color::DarkBlue,
+        "magenta" => Color::Magenta,
+        "dark_magenta" => Color::DarkMagenta,
+        "cyan" => Color::Cyan,
+        "dark_cyan" => Color::DarkCyan,
+        "white" => Color::White,
+        "grey" | "gray" => Color::Grey,
+        _ => return None,
+    };
+
+    Some(color)
 }
@@ -1,4 +1,5 @@
 use crossterm::style::Color;
+use image::{imageops::FilterType, DynamicImage, Rgba};
 
 use crate::helpers::get_is_position_outside_dimensions_with_offset;
 
@@ -10,10 +11,10 @@ use super::{
 
 pub type TwoDVec<T> = Vec<Vec<T>>;
 
-pub fn collapse_twoDVec<T>(twoDVec: TwoDVec<T>) -> Vec<T> {
+pub fn collapse_two_d_vec<T>(two_d_vec: TwoDVec<T>) -> Vec<T> {
     let mut return_vec: Vec<T> = vec![];
 
-    for row in twoDVec {
+    for row in two_d_vec {
         for item in row {
             return_vec.push(item);
         }
@@ -22,7 +23,7 @@ pub fn collapse_twoDVec<T>(twoDVec: TwoDVec<T>) -> Vec<T> {
     return_vec
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Layout {
     /// A Map is a 2D vector, where the Vec<_> are rows and Vec<Vec<_>> are items in a row
     pub map: Map,
@@ -32,6 +33,11 @@ pub struct Layout {
 
 type LayoutResult<T> = Result<T, DisplayControllerError>;
 
+const UPPER_HALF_BLOCK: char = '▀';
+const LOWER_HALF_BLOCK: char = '▄';
+/// Pixels with an alpha below this are treated as transparent
+const ALPHA_THRESHOLD: u8 = 128;
+
 pub enum Direction {
     Vertical,
     Horizontal,
@@ -70,53 +76,81 @@ impl Layout {
         Layout::from_map(map_from_str(ascii, color), None)
     }
 
-    pub fn from_file(path: &str) -> Self {
-        let img = image::open(path).unwrap();
-        let rgb_img = img.to_rgb8();
-
-        let (width, height) = (img.width(), img.height());
+    /// Loads an image and converts it into a layout. Each cell is drawn with a half block character so that it holds two pixels, the top pixel is the foreground and the bottom pixel is the background.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the image
+    /// * `target_dimensions` - The maximum size of the layout in cells, larger images are downscaled to fit while keeping their aspect ratio
+    pub fn from_file(path: &str, target_dimensions: Option<Point<i64>>) -> LayoutResult<Self> {
+        let image = image::open(path)
+            .map_err(|error| DisplayControllerError::ImageLoadError(error.to_string()))?;
 
-        let raw_rgb = rgb_img.to_vec();
+        Ok(Layout::from_image(&image, target_dimensions))
+    }
 
-        dbg!(width);
-        dbg!(height);
+    pub fn from_image(image: &DynamicImage, target_dimensions: Option<Point<i64>>) -> Self {
+        let image = match target_dimensions {
+            // Each cell holds two pixels vertically
+            Some(target_dimensions)
+                if image.width() as i64 > target_dimensions.width
+                    || image.height() as i64 > target_dimensions.height * 2 =>
+            {
+                image.resize(
+                    target_dimensions.width.max(1) as u32,
+                    (target_dimensions.height * 2).max(1) as u32,
+                    FilterType::Triangle,
+                )
+            }
+            _ => image.clone(),
+        };
 
-        // Use a 1D vector before converting to the 2D vector to simplify the logic
-        let mut converted_items: Vec<Option<Element>> = vec![None; (width * height) as usize];
+        let rgba_image = image.to_rgba8();
 
-        for index in (0..converted_items.len()) {
-            let base_index = index * 3;
-            let (r, g, b) = (
-                raw_rgb[base_index],
-                raw_rgb[base_index + 1],
-                raw_rgb[base_index + 2],
-            );
+        let (width, height) = rgba_image.dimensions();
 
-            converted_items[index] = Some(Element::new(
-                ' ',
-                DEFAULT_BACKGROUND,
-                Color::Rgb { r, g, b },
-            ));
-        }
+        let get_pixel_color = |x: u32, y: u32| -> Option<Color> {
+            if y >= height {
+                return None;
+            }
 
-        let mut map = vec![vec![None; width as usize]; height as usize];
+            let Rgba([r, g, b, a]) = *rgba_image.get_pixel(x, y);
 
-        for index in 0..converted_items.len() as i64 {
-            let height = index / width as i64;
-            let width = index % width as i64;
+            if a < ALPHA_THRESHOLD {
+                return None;
+            }
 
-            map[height as usize][width as usize] = converted_items[index as usize];
+            Some(Color::Rgb { r, g, b })
+        };
+
+        let map: Map = (0..height)
+            .step_by(2)
+            .map(|y| {
+                (0..width)
+                    .map(
+                        |x| match (get_pixel_color(x, y), get_pixel_color(x, y + 1)) {
+                            (Some(top), Some(bottom)) => {
+                                Some(Element::new(UPPER_HALF_BLOCK, bottom, top))
+                            }
+                            (Some(top), None) => {
+                                Some(Element::new(UPPER_HALF_BLOCK, DEFAULT_BACKGROUND, top))
+                            }
+                            // The lower half block is used so the transparent top pixel keeps the default background
+                            (None, Some(bottom)) => {
+                                Some(Element::new(LOWER_HALF_BLOCK, DEFAULT_BACKGROUND, bottom))
+                            }
+                            (None, None) => None,
+                        },
+                    )
+                    .collect()
+            })
+            .collect();
+
+        Layout {
+            dimensions: Point::new(width as i64, map.len() as i64),
+            map,
+            default_element: None,
         }
-
-        // for index in (0..raw_rgb.len()).step_by(3) {
-        //
-
-        //
-
-        //     // map[]
-        // }
-
-        Layout::from_map(map, None)
     }
 
     /// This method allows drawing an additional map on top of the map contained within this layout. This is useful when drawing ascii art.
@@ -136,8 +170,8 @@ impl Layout {
         // Iterate over each row in the map
         for (num_row, drawable_row) in map.iter().enumerate() {
             // Then each column in the row
-            for num_column in 0..drawable_row.len() {
-                if let Some(has_element) = drawable_row[num_column] {
+            for (num_column, element) in drawable_row.iter().enumerate() {
+                if let Some(has_element) = *element {
                     let updated_position = location
                         .add_width(num_column as i64)
                         .add_height(num_row as i64);
@@ -167,6 +201,15 @@ impl Layout {
         self
     }
 
+    /// Sets the foreground color of every element in the layout, used to recolor sprites
+    pub fn set_foreground(&mut self, color: Color) -> &mut Self {
+        for element in self.map.iter_mut().flatten().flatten() {
+            element.foreground = color;
+        }
+
+        self
+    }
+
     pub fn draw_str(
         &mut self,
         str: &str,
@@ -330,6 +373,25 @@ impl Layout {
         Ok(self)
     }
 
+    /// Returns the elements that differ from the previous layout with their position, every element when the dimensions differ
+    pub fn get_changed_elements(&self, previous: &Layout) -> Vec<(Point<i64>, Option<Element>)> {
+        let same_dimensions = self.dimensions == previous.dimensions;
+
+        let mut changed_elements = vec![];
+
+        for (height, row) in self.map.iter().enumerate() {
+            for (width, element) in row.iter().enumerate() {
+                let previous_element = previous.map.get(height).and_then(|row| row.get(width));
+
+                if !same_dimensions || previous_element != Some(element) {
+                    changed_elements.push((Point::new(width as i64, height as i64), *element));
+                }
+            }
+        }
+
+        changed_elements
+    }
+
     pub fn draw_item(
         &mut self,
         element: Element,
@@ -340,7 +402,7 @@ impl Layout {
             return Err(DisplayControllerError::PositionOutOfRange);
         }
 
-        let existing_element = self.get_element_mut(&position)?;
+        let existing_element = self.get_element_mut(position)?;
 
         *existing_element = Some(element);
 
@@ -350,17 +412,20 @@ impl Layout {
 
 #[cfg(test)]
 mod tests {
-    use crate::api::display::{Element, Layout, Point};
+    use crossterm::style::Color;
+    use image::{DynamicImage, Rgba, RgbaImage};
 
-    use super::Direction;
+    use crate::api::display::{
+        element::DEFAULT_BACKGROUND, DisplayControllerError, Element, Layout, Point,
+    };
+
+    use super::{Direction, LOWER_HALF_BLOCK, UPPER_HALF_BLOCK};
 
     const WIDTH: i64 = 30;
     const HEIGHT: i64 = 20;
 
     const DIMENSIONS: &Point<i64> = &Point::new(WIDTH, HEIGHT);
 
-    const DEFAULT_ELEMENT: Element = Element::default();
-
     #[test]
     fn it_creates_correct_dimensions() {
         let map = Layout::new(DIMENSIONS, None);
@@ -440,4 +505,102 @@ mod tests {
 
         assert_eq!(right_column, vec![&Some(LINE_ELEMENT); HEIGHT as usize]);
     }
+
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const BLUE: Rgba<u8> = Rgba([0, 0, 255, 255]);
+    const TRANSPARENT: Rgba<u8> = Rgba([0, 0, 0, 0]);
+
+    #[test]
+    fn it_converts_two_pixels_into_each_cell() {
+        // A 3x3 image, so the last row only has a top pixel
+        let mut image = RgbaImage::from_pixel(3, 3, TRANSPARENT);
+
+        image.put_pixel(0, 0, RED);
+        image.put_pixel(0, 1, BLUE);
+        image.put_pixel(1, 0, RED);
+        image.put_pixel(2, 1, BLUE);
+        image.put_pixel(0, 2, RED);
+
+        let layout = Layout::from_image(&DynamicImage::ImageRgba8(image), None);
+
+        assert_eq!(layout.dimensions, Point::new(3, 2));
+
+        let red = Color::Rgb { r: 255, g: 0, b: 0 };
+        let blue = Color::Rgb { r: 0, g: 0, b: 255 };
+
+        assert_eq!(
+            layout.map[0],
+            vec![
+                Some(Element::new(UPPER_HALF_BLOCK, blue, red)),
+                Some(Element::new(UPPER_HALF_BLOCK, DEFAULT_BACKGROUND, red)),
+                Some(Element::new(LOWER_HALF_BLOCK, DEFAULT_BACKGROUND, blue)),
+            ]
+        );
+
+        // Fully transparent cells are left empty
+        assert_eq!(
+            layout.map[1],
+            vec![
+                Some(Element::new(UPPER_HALF_BLOCK, DEFAULT_BACKGROUND, red)),
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn it_downscales_images_to_the_target_dimensions() {
+        let image = RgbaImage::from_pixel(100, 50, RED);
+
+        let layout = Layout::from_image(&DynamicImage::ImageRgba8(image), Some(Point::new(20, 20)));
+
+        // The aspect ratio is kept, 100x50 pixels fit into 20x10 pixels which is 5 rows
+        assert_eq!(layout.dimensions, Point::new(20, 5));
+    }
+
+    #[test]
+    fn it_loads_images_from_a_file() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/spaceship.png");
+
+        let layout = Layout::from_file(path, Some(Point::new(30, 20))).unwrap();
+
+        assert!(layout.dimensions.width <= 30);
+        assert!(layout.dimensions.height <= 20);
+        assert!(layout.map.iter().flatten().any(|element| element.is_some()));
+
+        let missing = Layout::from_file("missing.png", None).unwrap_err();
+        let unsupported =
+            Layout::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/readme.md"), None).unwrap_err();
+
+        // The cause of the error is kept, so a missing file can be told apart from a format that isn't supported
+        assert!(matches!(missing, DisplayControllerError::ImageLoadError(_)));
+        assert!(matches!(
+            unsupported,
+            DisplayControllerError::ImageLoadError(_)
+        ));
+        assert_ne!(missing, unsupported);
+    }
+
+    #[test]
+    fn it_returns_the_changed_elements() {
+        let previous = Layout::new(&Point::new(3, 2), None);
+        let mut layout = previous.clone();
+
+        let element = Element::new('x', DEFAULT_BACKGROUND, Color::Red);
+        layout.draw_item(element, &Point::new(2, 1)).unwrap();
+
+        assert_eq!(
+            layout.get_changed_elements(&previous),
+            vec![(Point::new(2, 1), Some(element))]
+        );
+        assert!(layout.get_changed_elements(&layout).is_empty());
+
+        // A layout of a different size changes everything
+        assert_eq!(
+            layout
+                .get_changed_elements(&Layout::new(&Point::new(2, 2), None))
+                .len(),
+            6
+        );
+    }
 }
@@ -13,8 +13,7 @@ pub fn map_from_str(str: &str, color: Color) -> Map {
 
     let width = rows.into_iter().max_by_key(|row| row.len()).unwrap().len();
 
-    let mut map: Vec<Vec<Option<Element>>> =
-        vec![vec![None; width as usize]; str.split("\n").count()];
+    let mut map: Vec<Vec<Option<Element>>> = vec![vec![None; width]; str.split("\n").count()];
 
     for (index, row) in str.split("\n").enumerate() {
         map[index] = parse_str_to_element_array(row, None, Some(color));
@@ -1,3 +1,4 @@
+mod color_mode;
 mod display_controller;
 mod display_controller_error;
 pub mod element;
@@ -6,6 +7,7 @@ mod map;
 mod output;
 mod point;
 
+pub use color_mode::ColorMode;
 pub use display_controller::*;
 pub use display_controller_error::DisplayControllerError;
 pub use element::Element;
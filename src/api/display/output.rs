@@ -2,23 +2,40 @@ use crossterm::{
     cursor::{Hide, MoveTo, Show},
     execute, queue,
     style::{Print, SetBackgroundColor, SetForegroundColor},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ErrorKind as CrosstermError,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
 use std::io;
 use std::io::Write;
 
-use crate::api::display::element::{DEFAULT_BACKGROUND, DEFAULT_FOREGROUND};
+use super::{ColorMode, DisplayControllerError, Element, Layout, Point};
 
-use super::{DisplayControllerError, Element, Layout, Point};
+/// Receives every layout that is printed, e.g. to show the game somewhere other than the terminal
+pub trait FrameSink {
+    fn present(&mut self, layout: &Layout);
+}
 
 pub struct Output {
     target: io::Stdout,
+    color_mode: ColorMode,
+    frame_sink: Option<Box<dyn FrameSink>>,
 }
 
 impl Output {
-    pub fn new(target: io::Stdout) -> Self {
-        Output { target }
+    pub fn new(target: io::Stdout, color_mode: ColorMode) -> Self {
+        Output {
+            target,
+            color_mode,
+            frame_sink: None,
+        }
+    }
+
+    pub fn set_frame_sink(&mut self, frame_sink: impl FrameSink + 'static) -> &mut Self {
+        self.frame_sink = Some(Box::new(frame_sink));
+
+        self
     }
 
     pub fn start(&mut self) -> Result<(), DisplayControllerError> {
@@ -37,6 +54,20 @@ impl Output {
         Ok(())
     }
 
+    /// Clears the whole terminal, used before printing a layout smaller than the previous one
+    pub fn clear(&mut self) -> Result<(), DisplayControllerError> {
+        let default_element = self.color_mode.get_default_element();
+
+        queue!(
+            self.target,
+            SetBackgroundColor(default_element.background),
+            Clear(ClearType::All)
+        )
+        .map_err(DisplayControllerError::from_crossterm_error)?;
+
+        Ok(())
+    }
+
     /// Flushing the target publishes all queued writes
     fn flush(&mut self) -> &mut Self {
         self.target.flush().unwrap();
@@ -45,10 +76,12 @@ impl Output {
     }
 
     pub fn reset_cursor(&mut self) -> Result<(), DisplayControllerError> {
+        let default_element = self.color_mode.get_default_element();
+
         queue!(
             self.target,
-            SetForegroundColor(DEFAULT_FOREGROUND),
-            SetBackgroundColor(DEFAULT_BACKGROUND),
+            SetForegroundColor(default_element.foreground),
+            SetBackgroundColor(default_element.background),
             MoveTo(0, 0)
         )
         .unwrap();
@@ -72,6 +105,9 @@ impl Output {
             .map_err(DisplayControllerError::from_crossterm_error)?;
         };
 
+        // Map the element down to the colors the terminal supports
+        let element = self.color_mode.map_element(element);
+
         queue!(
             self.target,
             SetForegroundColor(element.foreground),
@@ -86,7 +122,11 @@ impl Output {
     pub fn print_display(&mut self, map: &Layout) -> Result<(), DisplayControllerError> {
         self.reset_cursor()?;
 
-        for row in map.map.iter() {
+        // Each row starts at the left of the terminal, so a layout narrower than the terminal isn't wrapped onto the rows above
+        for (height, row) in map.map.iter().enumerate() {
+            queue!(self.target, MoveTo(0, height as u16))
+                .map_err(DisplayControllerError::from_crossterm_error)?;
+
             for element in row.iter() {
                 match element {
                     Some(element) => {
@@ -99,6 +139,12 @@ impl Output {
             }
         }
 
+        self.flush();
+
+        if let Some(frame_sink) = &mut self.frame_sink {
+            frame_sink.present(map);
+        }
+
         Ok(())
     }
 }
@@ -79,13 +79,6 @@ where
 
         self
     }
-
-    pub fn default() -> Self {
-        Self {
-            width: Default::default(),
-            height: Default::default(),
-        }
-    }
 }
 
 impl<T> Add for Point<T>
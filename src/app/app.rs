@@ -1,38 +1,82 @@
-use std::panic;
+use std::collections::{HashMap, HashSet};
 
-use crossterm::{
-    event::{poll, read, Event, KeyCode},
-    style::Color,
-};
+use crossterm::event::Event;
+use uuid::Uuid;
 
 use crate::{
-    api::display::{get_screen_size, DisplayController, Output, Point},
-    components::{Drawable, DrawableState, Health},
-    entities::{Borders, Controller, Player},
-    systems::{get_collision_summary, run_collision_detection, AsteroidController},
+    api::display::{element::DEFAULT_BACKGROUND, DisplayController, Output, Point},
+    components::{CollisionLayer, Drawable, DrawableState},
+    entities::{
+        Action, Autopilot, AutopilotView, Borders, Boss, Controller, Player, PICKUP_HEALING,
+        WASD_KEY_BINDINGS,
+    },
+    helpers::log_warning,
+    levels::Level,
+    network::{BossSnapshot, CellSnapshot, Snapshot},
+    systems::{
+        get_bounce, get_collision_summary, run_animation_system, run_bounds_system,
+        run_collision_detection, run_lifetime_system, run_movement_system,
+        run_spawn_command_system, CollisionEvent, CollisionEventKind, InteractionRule,
+        InteractionTable, LevelDirector, Spawner,
+    },
+    user_display::Theme,
+    world::{Entity, World},
 };
 
 use super::{
     app_errors::{AppError, AppResult},
+    environment::{CellKind, EntityObservation, Observation, PlayerObservation},
     game_state::GameState,
+    GameMode, Settings, MAX_LIVES,
 };
 
+/// Where the entities of a game come from
+enum SpawnSource {
+    /// Picked at random from the game mode's spawn table
+    Spawner(Box<Spawner>),
+    /// The timeline of an authored level
+    Level(Box<LevelDirector>),
+}
+
 pub struct App {
     display_controller: DisplayController,
     borders: Borders,
-    player: Player,
-    asteroid_controller: AsteroidController,
+    world: World,
+    /// The first player uses the arrow keys, the second player uses WASD
+    players: Vec<Player>,
+    spawn_source: SpawnSource,
+    /// Random spawning is paused while a boss is alive
+    boss: Option<Boss>,
     dimensions: Point<i64>,
+    theme: Theme,
+    interaction_table: InteractionTable,
+    game_mode: GameMode,
+    /// The enemies alive at the end of the last frame, so new enemies are only counted once
+    enemies: HashSet<Entity>,
+    /// Text drawn over the middle of the play field, e.g. to tell the player a demo is playing
+    overlay: Option<String>,
 }
 
 pub struct InitialGameState {
     pub player_health: u32,
+    /// Seeds the random spawning, None picks a different seed every game
+    pub seed: Option<u64>,
 }
 
-const HUD_HEIGHT: i64 = 10;
+pub(super) const HUD_HEIGHT: i64 = 10;
+
+/// Half of the distance between the ships at the start of a co-op game
+const PLAYER_SPACING: i64 = 10;
 
 impl App {
-    pub fn new(dimensions: Point<i64>, init_game_state: InitialGameState) -> AppResult<App> {
+    /// Creates a new game, a level replaces the random spawning of the game mode with the level's timeline
+    pub fn new(
+        dimensions: Point<i64>,
+        init_game_state: InitialGameState,
+        settings: &Settings,
+        level: Option<Level>,
+    ) -> AppResult<App> {
+        let theme = &settings.theme;
         let game_screen_size = dimensions.sub_height(HUD_HEIGHT);
 
         let game_display_controller = DisplayController::new(dimensions, Point::new(0, HUD_HEIGHT));
@@ -43,133 +87,561 @@ impl App {
 
         let game_display_controller = game_display_controller.unwrap();
 
-        Ok(App {
-            display_controller: game_display_controller,
-            borders: Borders::new(&game_screen_size, Color::Cyan)?,
-            player: Player::new(
+        let mut interaction_table = InteractionTable::default();
+
+        if settings.mode.asteroid_bounce {
+            interaction_table.set_rule(
+                CollisionLayer::Enemy,
+                CollisionLayer::Enemy,
+                InteractionRule::no_damage(),
+            );
+        }
+
+        let mut world = World::new();
+
+        let player_count = settings.players.players.max(1);
+        let mut players = Vec::with_capacity(player_count);
+
+        for index in 0..player_count {
+            // The ships start side by side around the middle of the screen
+            let offset = (index as i64 * 2 + 1 - player_count as i64) * PLAYER_SPACING;
+
+            let mut player = Player::new(
+                &mut world,
                 Some(Point {
                     height: game_screen_size.height - 15,
-                    width: game_screen_size.width / 2 - 10,
+                    width: game_screen_size.width / 2 - 10 + offset,
                 }),
                 init_game_state.player_health,
-            ),
-            asteroid_controller: AsteroidController::new(100, game_screen_size),
+                theme,
+            );
+
+            if settings.mode.free_flight {
+                player = player.with_free_flight(&mut world);
+            }
+
+            if index == 1 {
+                player = player
+                    .with_key_bindings(WASD_KEY_BINDINGS)
+                    .with_color(&mut world, theme.second_player);
+            }
+
+            players.push(player);
+        }
+
+        let spawn_source = match level {
+            Some(level) => {
+                SpawnSource::Level(Box::new(LevelDirector::new(level, game_screen_size, theme)))
+            }
+            None => {
+                let mut spawner = Spawner::new(settings.mode.spawn_table, game_screen_size, theme)
+                    .with_difficulty(settings.difficulty);
+
+                if let Some(seed) = init_game_state.seed {
+                    spawner = spawner.with_seed(seed);
+                }
+
+                SpawnSource::Spawner(Box::new(spawner))
+            }
+        };
+
+        Ok(App {
+            display_controller: game_display_controller,
+            borders: Borders::new(&game_screen_size, theme.border)?,
+            world,
+            players,
+            spawn_source,
+            boss: None,
             dimensions,
+            theme: *theme,
+            interaction_table,
+            game_mode: settings.mode,
+            enemies: HashSet::new(),
+            overlay: None,
         })
     }
 
+    pub fn with_overlay(mut self, text: &str) -> Self {
+        self.overlay = Some(text.to_string());
+
+        self
+    }
+
     /// Reset method to be called at the start of each loop
     fn reset(&mut self) {
         self.display_controller.layout.reset();
     }
 
-    /// Process the keyboard events, also returns true if the user closes the game with escape
-    fn handle_keyboard(&mut self, keyboard_input: Option<&Event>) -> AppResult<()> {
-        if let Some(event) = keyboard_input {
-            self.player.handle_event(&event);
+    /// Passes the keyboard events to every player, each player only responds to their own key bindings
+    fn handle_keyboard(&mut self, keyboard_events: &[Event]) -> AppResult<()> {
+        for event in keyboard_events {
+            for player in self.players.iter_mut() {
+                player.handle_event(event, &mut self.world);
+            }
         }
 
         Ok(())
     }
 
+    /// The key presses of an autopilot flying a player's ship, pressed with the player's key bindings so they can be passed
+    /// to the game like keyboard events. A destroyed ship presses nothing.
+    pub fn get_autopilot_events(
+        &self,
+        player_index: usize,
+        autopilot: &mut dyn Autopilot,
+        frame_duration: u128,
+    ) -> Vec<Event> {
+        let Some(player) = self.players.get(player_index) else {
+            return vec![];
+        };
+
+        if !self.world.has_entity(player.entity) {
+            return vec![];
+        }
+
+        let actions = autopilot.get_actions(&AutopilotView {
+            world: &self.world,
+            ship: player.entity,
+            flight: player.flight,
+            dimensions: self.display_controller.drawable_dimensions,
+            frame_duration,
+        });
+
+        self.get_action_events(player_index, &actions)
+    }
+
+    /// The key presses of the actions with a player's key bindings, nothing is pressed for a player that isn't in the game
+    pub fn get_action_events(&self, player_index: usize, actions: &[Action]) -> Vec<Event> {
+        let Some(player) = self.players.get(player_index) else {
+            return vec![];
+        };
+
+        let bindings = player.get_key_bindings();
+
+        actions
+            .iter()
+            .map(|action| bindings.get_event(*action))
+            .collect()
+    }
+
+    /// The index of the player that controls the entity, or that owns it, such as the player that fired a bullet
+    fn get_player_index(&self, entity: Entity) -> Option<usize> {
+        let owner = self.world.owners.get(entity).copied().unwrap_or(entity);
+
+        self.players
+            .iter()
+            .position(|player| player.entity == owner)
+    }
+
     pub fn run_next_game_frame(
         &mut self,
         output: &mut Output,
         game_state: &mut GameState,
         game_loop_duration: u128,
+    ) -> AppResult<()> {
+        self.update(game_state, game_loop_duration)?;
+
+        self.draw_all_entities(game_state, output)?;
+
+        Ok(())
+    }
+
+    /// Runs the systems for a frame without drawing it, used to run games without a terminal
+    pub fn update(
+        &mut self,
+        game_state: &mut GameState,
+        game_loop_duration: u128,
     ) -> AppResult<()> {
         self.reset();
 
-        self.handle_keyboard(game_state.keyboard_event.as_ref())?;
+        self.handle_keyboard(&game_state.keyboard_events)?;
 
-        self.asteroid_controller
-            .handle_game_loop(game_loop_duration);
+        self.handle_spawning(game_state, game_loop_duration);
+
+        for player in self.players.iter_mut() {
+            player.handle_game_loop(&mut self.world, game_loop_duration);
+        }
 
-        self.update_positions(game_loop_duration);
+        // Bosses aim at the first ship that is still flying
+        let player_center = self.players.iter().find_map(|player| {
+            self.world
+                .get_drawable_state(player.entity)
+                .map(|state| state.location + state.layout.dimensions / 2_i64.into())
+        });
+
+        if let Some(boss) = &mut self.boss {
+            boss.handle_game_loop(&mut self.world, game_loop_duration, player_center);
+        }
+
+        run_movement_system(
+            &mut self.world,
+            &self.display_controller.drawable_dimensions,
+            game_loop_duration,
+            self.game_mode.screen_wrap,
+        );
+
+        // Animations are advanced before collision detection so collisions use the shape of the frame that is drawn
+        run_animation_system(&mut self.world, game_loop_duration);
+
+        run_lifetime_system(&mut self.world, game_loop_duration);
+
+        // Entities that wrap around the screen never leave it
+        if !self.game_mode.screen_wrap {
+            run_bounds_system(
+                &mut self.world,
+                &self.display_controller.drawable_dimensions,
+                self.game_mode.cull_margin,
+            );
+        }
 
         self.handle_collisions(game_state)?;
 
-        self.draw_all_entities(game_state, output)?;
+        self.check_boss_defeated(game_state);
+
+        self.check_level_complete(game_state);
+
+        run_spawn_command_system(&mut self.world, &self.theme);
+
+        self.count_spawned_enemies(game_state);
 
         Ok(())
     }
 
+    /// Counts the enemies that appeared this frame, whether they came from the spawner, the level or a split asteroid
+    fn count_spawned_enemies(&mut self, game_state: &mut GameState) {
+        let enemies: HashSet<Entity> = self
+            .world
+            .get_entities()
+            .into_iter()
+            .filter(|entity| self.world.colliders.get(*entity) == Some(&CollisionLayer::Enemy))
+            .collect();
+
+        game_state.stats.enemies_spawned += enemies.difference(&self.enemies).count() as u32;
+
+        self.enemies = enemies;
+    }
+
     fn handle_collisions(&mut self, game_state: &mut GameState) -> AppResult<&mut Self> {
-        let collision_results = get_collision_summary(run_collision_detection(
-            self.get_all_drawable_states(),
+        let drawable_states = self.world.get_collidable_drawable_states();
+
+        let collision_events = run_collision_detection(
+            drawable_states.iter().collect::<Vec<&DrawableState>>(),
             &self.dimensions,
-        ));
-
-        for (uuid, collision) in collision_results {
-            // Asteroid collision
-            if self
-                .asteroid_controller
-                .entity_controller
-                .has_entity(collision.uuid)
-            {
-                let destroyed = self
-                    .asteroid_controller
-                    .entity_controller
-                    .apply_entity_damage(uuid, collision.damage);
+            &self.interaction_table,
+        );
+
+        let destroyed_uuids = self.apply_collision_damage(&collision_events, game_state);
+
+        self.handle_bounces(&collision_events);
+
+        // A target can be hit by multiple drawables in the same frame, but it is only destroyed once
+        let mut handled_destroyed_uuids: HashSet<Uuid> = HashSet::new();
 
-                if destroyed {
-                    game_state.score += collision.points;
+        for event in &collision_events {
+            let target_destroyed = destroyed_uuids.contains(&event.target.uuid)
+                && handled_destroyed_uuids.insert(event.target.uuid);
+
+            // The player that collected the pickup is the source of the event
+            if event.kind == CollisionEventKind::PlayerCollectedPickup && target_destroyed {
+                self.world
+                    .apply_healing(event.source.uuid, PICKUP_HEALING, MAX_LIVES);
+            }
+
+            let scorer = self.get_player_index(event.source.uuid);
+
+            game_state.handle_collision_event(event, target_destroyed, scorer);
+        }
+
+        Ok(self)
+    }
+
+    /// Spawns from the spawn table or the level, and brings in a boss once its trigger or time is reached
+    fn handle_spawning(&mut self, game_state: &GameState, game_loop_duration: u128) -> &mut Self {
+        let boss_kind = match &mut self.spawn_source {
+            SpawnSource::Spawner(spawner) => {
+                if self.boss.is_some() {
+                    return self;
                 }
-            } else if self.player.bullet_entity_controller.has_entity(uuid) {
-                // Bullet collision
-                self.player
-                    .bullet_entity_controller
-                    .apply_entity_damage(uuid, collision.damage);
-            } else if self.player.drawable.uuid == uuid {
-                // Player collision
-                self.player.apply_damage(collision.damage);
-
-                if self.player.get_health() == 0 {
-                    game_state.handle_game_over();
+
+                spawner.handle_game_loop(&mut self.world, game_loop_duration);
+                spawner.take_triggered_boss(game_state.score)
+            }
+            SpawnSource::Level(director) => {
+                director.handle_game_loop(&mut self.world, game_loop_duration);
+
+                match self.boss {
+                    Some(_) => None,
+                    None => director.take_boss_appearance(),
                 }
-            } else {
-                // Use a lazy panic here because this shouldn't happen
-                panic!("UUID missing from all arrays");
             }
+        };
+
+        if let Some(kind) = boss_kind {
+            self.boss = Some(Boss::spawn(
+                &mut self.world,
+                kind,
+                self.display_controller.drawable_dimensions,
+                &self.theme,
+            ));
         }
 
-        Ok(self)
+        self
     }
 
-    fn update_positions(&mut self, game_loop_duration: u128) -> &mut Self {
-        self.player.update_position(
-            Some(&self.display_controller.drawable_dimensions),
-            game_loop_duration,
-        );
+    /// Destroys what is left of a defeated boss and scores its reward
+    fn check_boss_defeated(&mut self, game_state: &mut GameState) -> &mut Self {
+        if !self
+            .boss
+            .as_ref()
+            .is_some_and(|boss| boss.is_defeated(&self.world))
+        {
+            return self;
+        }
 
-        self.player
-            .bullet_entity_controller
-            .update_entity_positions(game_loop_duration);
+        if let Some(boss) = self.boss.take() {
+            let points = boss.destroy(&mut self.world);
 
-        self.asteroid_controller
-            .entity_controller
-            .update_entity_positions(game_loop_duration);
+            game_state.handle_boss_defeated(points);
+        }
 
         self
     }
 
-    fn get_all_drawable_states(&self) -> Vec<&DrawableState> {
-        let mut drawable_items: Vec<&DrawableState> = vec![self.player.get_drawable_state()];
+    /// Ends the game once the win condition of the level is met, losing the last life in the same frame is still a game over
+    fn check_level_complete(&mut self, game_state: &mut GameState) -> &mut Self {
+        if let SpawnSource::Level(director) = &self.spawn_source {
+            if !game_state.game_over
+                && director.is_complete(
+                    &self.world,
+                    game_state.score,
+                    game_state.stats.enemies_destroyed,
+                )
+            {
+                game_state.handle_level_complete();
+            }
+        }
 
-        drawable_items.append(
-            &mut self
-                .asteroid_controller
-                .entity_controller
-                .get_all_drawable_states(),
-        );
-        drawable_items.append(
-            &mut self
-                .player
-                .bullet_entity_controller
-                .get_all_drawable_states(),
-        );
+        self
+    }
+
+    /// Bounces asteroids off each other, the interaction table only emits these events when the game mode enables bouncing
+    fn handle_bounces(&mut self, collision_events: &[CollisionEvent]) -> &mut Self {
+        let spawn_order: HashMap<Uuid, usize> = self
+            .world
+            .get_entities()
+            .into_iter()
+            .enumerate()
+            .map(|(index, entity)| (entity, index))
+            .collect();
+
+        for event in collision_events {
+            // Both asteroids emit an event for the same collision, only the one from the asteroid spawned first is used so the
+            // asteroids are always bounced in the same order
+            if event.kind != CollisionEventKind::EnemyHitEnemy
+                || spawn_order.get(&event.source.uuid) > spawn_order.get(&event.target.uuid)
+            {
+                continue;
+            }
 
-        drawable_items
+            let (Some(source), Some(target)) = (
+                self.world.get_drawable_state(event.source.uuid),
+                self.world.get_drawable_state(event.target.uuid),
+            ) else {
+                continue;
+            };
+
+            let bounce = get_bounce(&source, &target);
+
+            self.world
+                .velocities
+                .insert(source.uuid, bounce.first_velocity);
+            self.world
+                .positions
+                .insert(source.uuid, bounce.first_location);
+            self.world
+                .velocities
+                .insert(target.uuid, bounce.second_velocity);
+            self.world
+                .positions
+                .insert(target.uuid, bounce.second_location);
+        }
+
+        self
+    }
+
+    /// Applies the damage from the collision events to each target
+    ///
+    /// # Returns
+    ///
+    /// * The uuids of the targets that were destroyed
+    fn apply_collision_damage(
+        &mut self,
+        collision_events: &[CollisionEvent],
+        game_state: &mut GameState,
+    ) -> HashSet<Uuid> {
+        let mut destroyed_uuids: HashSet<Uuid> = HashSet::new();
+        let mut collision_summary = get_collision_summary(collision_events);
+
+        // The targets are damaged in the order of the events rather than the summary's, so they are destroyed in the same
+        // order every game
+        for event in collision_events {
+            let uuid = event.target.uuid;
+            let Some(collision) = collision_summary.remove(&uuid) else {
+                continue;
+            };
+
+            if !self.world.has_entity(uuid) {
+                log_warning(&format!("Collision with an unknown drawable {}", uuid));
+
+                continue;
+            }
+
+            if self.world.apply_damage(uuid, collision.damage) {
+                destroyed_uuids.insert(uuid);
+            }
+
+            // The game is over once every player is out of lives
+            if self.get_player_index(uuid).is_some()
+                && self
+                    .players
+                    .iter()
+                    .all(|player| player.get_health(&self.world) == 0)
+            {
+                game_state.handle_game_over();
+            }
+        }
+
+        destroyed_uuids
+    }
+
+    /// Returns what is on screen for a network client to draw, entities are in the same order as draw_world so effects
+    /// are drawn over everything else
+    pub fn get_snapshot(&self, game_state: &GameState) -> Snapshot {
+        let (effects, entities): (Vec<Entity>, Vec<Entity>) = self
+            .world
+            .get_entities()
+            .into_iter()
+            .partition(|entity| !self.world.colliders.contains(*entity));
+
+        let mut cells = vec![];
+
+        for entity in entities.into_iter().chain(effects) {
+            let (Some(location), Some(sprite)) = (
+                self.world.positions.get(entity),
+                self.world.sprites.get(entity),
+            ) else {
+                continue;
+            };
+
+            for (height, row) in sprite.get_layout().map.iter().enumerate() {
+                for (width, element) in row.iter().enumerate() {
+                    if let Some(element) = element {
+                        cells.push(CellSnapshot {
+                            location: *location + Point::new(width as i64, height as i64),
+                            value: element.value,
+                            color: element.foreground,
+                        });
+                    }
+                }
+            }
+        }
+
+        Snapshot {
+            frame: 0,
+            score: game_state.score,
+            player_scores: game_state.player_scores.clone(),
+            lives: self
+                .players
+                .iter()
+                .map(|player| player.get_health(&self.world))
+                .collect(),
+            boss: self.boss.as_ref().map(|boss| BossSnapshot {
+                name: boss.get_name().to_string(),
+                health: boss.get_health(&self.world),
+                max_health: boss.get_max_health(),
+            }),
+            cells,
+        }
+    }
+
+    /// Returns what is in the play field for an agent learning to play, nothing in it depends on the uuids of the entities so
+    /// the same game always gives the same observations
+    pub fn get_observation(&self, game_state: &GameState) -> Observation {
+        let dimensions = self.display_controller.drawable_dimensions;
+        let ships: Vec<Entity> = self.players.iter().map(|player| player.entity).collect();
+
+        // Effects are placed first so they don't hide what can be collided with
+        let (effects, entities): (Vec<Entity>, Vec<Entity>) = self
+            .world
+            .get_entities()
+            .into_iter()
+            .partition(|entity| !self.world.colliders.contains(*entity));
+
+        let mut grid =
+            vec![vec![CellKind::Empty; dimensions.width as usize]; dimensions.height as usize];
+        let mut observed_entities = vec![];
+
+        for entity in effects.into_iter().chain(entities) {
+            let (Some(location), Some(sprite)) = (
+                self.world.positions.get(entity),
+                self.world.sprites.get(entity),
+            ) else {
+                continue;
+            };
+
+            let layout = sprite.get_layout();
+            let kind = CellKind::from_layer(self.world.colliders.get(entity).copied());
+
+            for (height, row) in layout.map.iter().enumerate() {
+                for (width, element) in row.iter().enumerate() {
+                    let cell = *location + Point::new(width as i64, height as i64);
+
+                    if element.is_none()
+                        || !(0..dimensions.width).contains(&cell.width)
+                        || !(0..dimensions.height).contains(&cell.height)
+                    {
+                        continue;
+                    }
+
+                    grid[cell.height as usize][cell.width as usize] = kind;
+                }
+            }
+
+            if !ships.contains(&entity) {
+                observed_entities.push(EntityObservation {
+                    kind,
+                    location: *location,
+                    size: layout.dimensions,
+                    velocity: self
+                        .world
+                        .velocities
+                        .get(entity)
+                        .copied()
+                        .unwrap_or_default(),
+                    health: self.world.healths.get(entity).copied(),
+                });
+            }
+        }
+
+        Observation {
+            grid,
+            players: self
+                .players
+                .iter()
+                .map(|player| PlayerObservation {
+                    location: self.world.positions.get(player.entity).copied(),
+                    velocity: self
+                        .world
+                        .velocities
+                        .get(player.entity)
+                        .copied()
+                        .unwrap_or_default(),
+                    lives: player.get_health(&self.world),
+                })
+                .collect(),
+            entities: observed_entities,
+            score: game_state.score,
+        }
     }
 
     /// Method to handle drawing all the entities that will be rendered
@@ -178,24 +650,93 @@ impl App {
         game_state: &mut GameState,
         output: &mut Output,
     ) -> AppResult<&mut Self> {
-        self.display_controller
-            .draw_drawable(&self.player.get_drawable_state())?;
+        self.display_controller.draw_world(&self.world)?;
 
-        // Draw all the entities in the bullet and asteroid controller
         self.display_controller
-            .draw_entity_controller_items(&mut self.player.bullet_entity_controller);
+            .draw_drawable(self.borders.get_drawable_state())?;
 
-        self.display_controller
-            .draw_entity_controller_items(&mut self.asteroid_controller.entity_controller);
+        let lives: Vec<u32> = self
+            .players
+            .iter()
+            .map(|player| player.get_health(&self.world))
+            .collect();
 
         self.display_controller
-            .draw_drawable(&self.borders.get_drawable_state())?;
+            .draw_game_state(game_state, &lives, &self.theme)?;
+
+        if let Some(boss) = &self.boss {
+            self.display_controller.draw_boss_health(
+                boss.get_name(),
+                boss.get_health(&self.world),
+                boss.get_max_health(),
+                &self.theme,
+            )?;
+        }
 
-        self.display_controller
-            .draw_game_state(game_state, self.player.get_health())?;
+        if let Some(overlay) = &self.overlay {
+            self.display_controller.draw_str(
+                overlay,
+                DEFAULT_BACKGROUND,
+                self.theme.warning,
+                Point::new(
+                    self.dimensions.width / 2 - overlay.len() as i64 / 2,
+                    HUD_HEIGHT + (self.dimensions.height - HUD_HEIGHT) / 2,
+                ),
+            )?;
+        }
 
         output.print_display(&self.display_controller.layout)?;
 
         Ok(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        app::{GameState, Settings, BOUNCE_MODE},
+        entities::spawn_asteroid,
+    };
+
+    use super::{App, InitialGameState};
+
+    #[test]
+    fn it_bounces_enemies_apart_in_the_order_they_were_spawned() {
+        let settings = Settings {
+            mode: BOUNCE_MODE,
+            ..Settings::default()
+        };
+        let mut app = App::new(
+            Point::new(120, 40),
+            InitialGameState {
+                player_health: 1,
+                seed: Some(0),
+            },
+            &settings,
+            None,
+        )
+        .unwrap();
+
+        // Asteroids on top of each other have no direction to bounce in, so the one spawned first is always pushed up and left
+        let pairs: Vec<_> = (0..8)
+            .map(|index| {
+                let location = Point::new(5 + index * 12, 5);
+
+                (
+                    spawn_asteroid(&mut app.world, location, Point::default(), &settings.theme),
+                    spawn_asteroid(&mut app.world, location, Point::default(), &settings.theme),
+                )
+            })
+            .collect();
+
+        app.handle_collisions(&mut GameState::new()).unwrap();
+
+        for (first, second) in pairs {
+            let first = app.world.positions.get(first).unwrap();
+            let second = app.world.positions.get(second).unwrap();
+
+            assert!((first.height, first.width) < (second.height, second.width));
+        }
+    }
+}
@@ -1,11 +1,37 @@
+use std::fmt::Display;
+
 use crossterm::ErrorKind;
 
-use crate::api::display::DisplayControllerError;
+use crate::{api::display::DisplayControllerError, network::NetworkError};
+
+use super::ReplayError;
 
 #[derive(Debug)]
 pub enum AppError {
     DisplayControllerError(DisplayControllerError),
     ScreenWidthTooSmall(u64, u64),
+    NetworkError(NetworkError),
+    ReplayError(ReplayError),
+    /// An environment was stepped before a game was started
+    EnvironmentNotReset,
+}
+
+impl Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DisplayControllerError(error) => write!(f, "Display error: {}", error),
+            Self::ScreenWidthTooSmall(width, minimum_width) => write!(
+                f,
+                "Screen width of {} is too small, the game requires at least {} columns",
+                width, minimum_width
+            ),
+            Self::NetworkError(error) => write!(f, "{}", error),
+            Self::ReplayError(error) => write!(f, "{}", error),
+            Self::EnvironmentNotReset => {
+                write!(f, "The environment has to be reset before it is stepped")
+            }
+        }
+    }
 }
 
 impl From<DisplayControllerError> for AppError {
@@ -14,6 +40,18 @@ impl From<DisplayControllerError> for AppError {
     }
 }
 
+impl From<NetworkError> for AppError {
+    fn from(error: NetworkError) -> Self {
+        Self::NetworkError(error)
+    }
+}
+
+impl From<ReplayError> for AppError {
+    fn from(error: ReplayError) -> Self {
+        Self::ReplayError(error)
+    }
+}
+
 impl From<ErrorKind> for AppError {
     fn from(_: ErrorKind) -> Self {
         Self::DisplayControllerError(DisplayControllerError::CrossTermWriteError)
@@ -6,55 +6,65 @@ use crossterm::{
 };
 
 use crate::{
+    api::assets::load_sprite_or_default,
     api::display::{
-        element::DEFAULT_BACKGROUND, map_from_str, DisplayController, Output, Point,
-        MINIMUM_SCREEN_WIDTH,
+        element::DEFAULT_BACKGROUND, get_player_scores_text, ColorMode, DisplayController, Output,
+        Point, MINIMUM_SCREEN_WIDTH,
     },
-    components::Drawable,
-    entities::Borders,
-    helpers::{get_keyboard_event, get_now},
-    user_display::{DifficultyDisplay, GAME_OVER_TEXT},
+    components::{Drawable, Sprite},
+    entities::{Autopilot, Borders, DODGING_AUTOPILOT, WASD_KEY_BINDINGS},
+    helpers::{get_keyboard_event, get_keyboard_events, get_now},
+    levels::{load_levels, Level, LevelFile},
+    network::{NetworkHost, SpectatorServer},
+    user_display::{Theme, GAME_OVER_SPRITE, GAME_OVER_TEXT},
 };
 
 use super::{
     app::InitialGameState,
     app_errors::{AppError, AppResult},
-    App, GameState,
+    App, GameOptions, GameState, Replay, ReplayError, Settings, CO_OP_SHARED_MODE, DEFAULT_LIVES,
 };
 
 pub struct AppManager {
     dimensions: Point<i64>,
     output: Output,
-    app: Option<App>,
     game_state: GameState,
+    settings: Settings,
+    /// The level played by the next game, None plays the endless game mode
+    level: Option<Level>,
+    /// Runs the game for a client on another machine, which plays the second ship
+    network_host: Option<NetworkHost>,
+    options: GameOptions,
+    /// The recorded game being played back, which replaces the keyboard
+    replay: Option<Replay>,
 }
 
-struct DifficultyOption<'name> {
-    name: &'name str,
-    level: u32,
-}
+/// The time waited for keyboard events on the menus
+const GAME_LOOP_DELAY: u64 = 75;
 
-const DIFFICULTIES: &'static [DifficultyOption] = &[
-    DifficultyOption {
-        name: "Easy",
-        level: 1,
-    },
-    DifficultyOption {
-        name: "Medium",
-        level: 2,
-    },
-    DifficultyOption {
-        name: "Hard",
-        level: 3,
-    },
-];
+/// Drawn over the demo played by the attract mode
+const ATTRACT_OVERLAY_TEXT: &str = "PRESS ENTER";
 
-const GAME_LOOP_DELAY: u64 = 75;
+/// Shown in the level select for the game mode's random spawning
+const ENDLESS_LEVEL_NAME: &str = "Endless";
+
+const TITLE_TEXT: &str = "RUSTY ASTEROIDS";
 
-const INIT_GAME_STATE: InitialGameState = InitialGameState { player_health: 3 };
+/// The screens with the menu, both play a demo when they are left idle
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MenuScreen {
+    /// Shown when the game starts
+    Title,
+    /// Shown after each game, with its score
+    GameOver,
+}
 
 impl AppManager {
-    pub fn new(dimensions: Point<i64>) -> AppResult<AppManager> {
+    pub fn new(
+        dimensions: Point<i64>,
+        color_mode: ColorMode,
+        settings: Settings,
+    ) -> AppResult<AppManager> {
         if dimensions.width < MINIMUM_SCREEN_WIDTH as i64 {
             return Err(AppError::ScreenWidthTooSmall(
                 dimensions.width as u64,
@@ -62,30 +72,79 @@ impl AppManager {
             ));
         }
 
-        let output = Output::new(stdout());
+        let output = Output::new(stdout(), color_mode);
 
         Ok(AppManager {
             dimensions,
             output,
-            app: None,
             game_state: GameState::new(),
+            settings,
+            level: None,
+            network_host: None,
+            options: GameOptions::default(),
+            replay: None,
         })
     }
 
+    /// Sets the options of the games. A replay sets the settings, level and dimensions it was recorded with, so it is an error
+    /// if the replay can't be read or doesn't fit the dimensions.
+    pub fn with_options(mut self, options: GameOptions) -> AppResult<Self> {
+        if let Some(path) = &options.replay {
+            let replay = Replay::load(path)?;
+
+            if replay.dimensions.width > self.dimensions.width
+                || replay.dimensions.height > self.dimensions.height
+            {
+                return Err(ReplayError::ScreenTooSmall(replay.dimensions).into());
+            }
+
+            self.level = replay.load_level()?;
+            self.dimensions = replay.dimensions;
+            self.settings = replay.get_settings(&self.settings);
+            self.replay = Some(replay);
+        }
+
+        self.options = options;
+
+        Ok(self)
+    }
+
+    /// Hosts the game for a network client, the second ship is played by the client so co-op is always used
+    pub fn with_network_host(mut self, network_host: NetworkHost) -> Self {
+        if self.settings.players.players < 2 {
+            self.settings.players = CO_OP_SHARED_MODE;
+        }
+
+        self.network_host = Some(network_host);
+
+        self
+    }
+
+    /// Serves every frame printed to spectators, including the menus
+    pub fn with_spectator_server(mut self, spectator_server: SpectatorServer) -> Self {
+        self.output.set_frame_sink(spectator_server);
+
+        self
+    }
+
     pub fn run(&mut self) -> AppResult<()> {
         self.output.start()?;
 
-        let mut is_running = true;
+        // A replay and a hosted game start straight away, the network client is already waiting for the game
+        let mut is_running = self.replay.is_some()
+            || self.network_host.is_some()
+            || self.handle_menu(MenuScreen::Title)?;
 
         while is_running {
             self.start_and_run_game()?;
 
-            if self.game_state.game_over {
-                let new_game = self.handle_game_over()?;
+            if self.game_state.game_over || self.game_state.level_complete {
+                let new_game = self.handle_menu(MenuScreen::GameOver)?;
                 if !new_game {
                     is_running = false;
                 } else {
                     self.game_state.game_over = false;
+                    self.game_state.level_complete = false;
                     self.game_state.score = 0;
                 }
             } else {
@@ -99,37 +158,121 @@ impl AppManager {
         Ok(())
     }
 
-    fn handle_keyboard(&mut self) -> AppResult<()> {
-        let event = get_keyboard_event(GAME_LOOP_DELAY)?;
-
-        if let Some(event) = event {
-            if event == Event::Key(KeyCode::Esc.into()) {
-                self.game_state.stop_game();
+    fn handle_keyboard(&mut self, delay: u64) -> AppResult<()> {
+        let events = get_keyboard_events(delay)?;
 
-                return Ok(());
-            }
+        if events.contains(&Event::Key(KeyCode::Esc.into())) {
+            self.game_state.stop_game();
+            self.game_state.keyboard_events.clear();
 
-            self.game_state.keyboard_event = Some(event);
-        } else {
-            self.game_state.keyboard_event = None;
+            return Ok(());
         }
 
+        self.game_state.keyboard_events = events;
+
         Ok(())
     }
 
     fn start_and_run_game(&mut self) -> AppResult<()> {
-        self.game_state.start_game();
-
-        let mut app = App::new(self.dimensions, INIT_GAME_STATE).unwrap();
+        self.game_state.start_game(&self.settings.players);
+
+        // The seed is always known so the game can be recorded
+        let seed = match &self.replay {
+            Some(replay) => replay.seed,
+            None => self.options.seed.unwrap_or_else(rand::random),
+        };
+
+        let lives = match &self.replay {
+            Some(replay) => replay.lives,
+            None => self.options.lives,
+        };
+
+        let mut app = App::new(
+            self.dimensions,
+            InitialGameState {
+                player_health: lives,
+                seed: Some(seed),
+            },
+            &self.settings,
+            self.level.clone(),
+        )
+        .unwrap();
+
+        let mut recording = self.options.record.as_ref().map(|_| {
+            Replay::new(
+                seed,
+                &self.settings,
+                lives,
+                self.dimensions,
+                self.level.as_ref().map(|level| level.name.clone()),
+            )
+        });
+
+        let mut replay_frames = self
+            .replay
+            .as_ref()
+            .map(|replay| replay.frames.clone().into_iter());
+
+        // Actions pressed by the client on the game over screen aren't carried into the new game
+        if let Some(network_host) = &mut self.network_host {
+            network_host.poll_actions()?;
+        }
 
         while self.game_state.is_running() {
             let game_loop_start = get_now();
 
-            self.handle_keyboard()?;
+            let game_loop_duration = match &mut replay_frames {
+                Some(frames) => {
+                    let Some(frame) = frames.next() else {
+                        self.game_state.stop_game();
+
+                        break;
+                    };
+
+                    // The keyboard is only read to stop the replay
+                    self.handle_keyboard(frame.duration as u64)?;
+
+                    if self.game_state.is_running() {
+                        self.game_state.keyboard_events = Replay::get_events(&frame);
+                    }
+
+                    frame.duration
+                }
+                None => {
+                    self.handle_keyboard(self.options.frame_delay)?;
+
+                    get_now() - game_loop_start
+                }
+            };
+
+            if let Some(network_host) = &mut self.network_host {
+                // The client plays the second ship, so its actions are pressed as the second player's keys
+                let events = network_host
+                    .poll_actions()?
+                    .into_iter()
+                    .map(|action| WASD_KEY_BINDINGS.get_event(action));
+
+                self.game_state.keyboard_events.extend(events);
+            }
 
-            let game_loop_duration = get_now() - game_loop_start;
+            if let Some(recording) = &mut recording {
+                recording.record_frame(game_loop_duration, &self.game_state.keyboard_events);
+            }
 
             app.run_next_game_frame(&mut self.output, &mut self.game_state, game_loop_duration)?;
+
+            if let Some(network_host) = &mut self.network_host {
+                network_host.send_snapshot(app.get_snapshot(&self.game_state));
+            }
+        }
+
+        if let Some(network_host) = &mut self.network_host {
+            network_host.send_game_over(self.game_state.score, &self.game_state.player_scores);
+        }
+
+        // Each game overwrites the recording, so the file holds the last game played
+        if let (Some(recording), Some(path)) = (recording, &self.options.record) {
+            recording.save(path).map_err(ReplayError::from)?;
         }
 
         Ok(())
@@ -141,21 +284,78 @@ impl AppManager {
         Ok(())
     }
 
-    fn handle_game_over(&mut self) -> AppResult<bool> {
+    /// Switches to the next theme and saves it to the settings file
+    fn change_theme(&mut self) {
+        self.settings.theme = self.settings.theme.next();
+
+        // Failing to persist the theme shouldn't end the game, the theme is still used until the game is closed
+        let _ = self.settings.save();
+    }
+
+    /// Switches to the next game mode and saves it to the settings file, the mode is used from the next game
+    fn change_mode(&mut self) {
+        self.settings.mode = self.settings.mode.next();
+
+        let _ = self.settings.save();
+    }
+
+    /// Switches between single player and co-op, which is saved to the settings file and used from the next game
+    fn change_players(&mut self) {
+        self.settings.players = self.settings.players.next();
+
+        // The network client always has a ship
+        if self.network_host.is_some() && self.settings.players.players < 2 {
+            self.settings.players = self.settings.players.next();
+        }
+
+        let _ = self.settings.save();
+    }
+
+    /// Shows the menu until ENTER starts a new game or ESC closes the game
+    ///
+    /// # Returns
+    ///
+    /// * True if a new game should be started
+    fn handle_menu(&mut self, screen: MenuScreen) -> AppResult<bool> {
         let mut display_controller = DisplayController::new(self.dimensions, Default::default())?;
 
         let mut while_running = true;
         let mut new_game = false;
 
-        let border = Borders::new(&self.dimensions, Color::Cyan)?;
-
         let draw_start_height = self.dimensions.height / 2 - 10;
 
+        let load_game_over_text = |theme: &Theme| {
+            load_sprite_or_default(GAME_OVER_SPRITE, || {
+                Sprite::from_ascii_frames(&[GAME_OVER_TEXT], Color::Green, 0, Point::default())
+            })
+            .with_color(theme.game_over)
+        };
+
+        let mut border = Borders::new(&self.dimensions, self.settings.theme.border)?;
+        let mut game_over_text = load_game_over_text(&self.settings.theme);
+        let mut last_key_press = get_now();
+
         while while_running {
             display_controller.layout.reset();
 
             let event = get_keyboard_event(GAME_LOOP_DELAY)?;
 
+            // The network client is shown the menu while the host waits, so a host never plays a demo
+            let attract_delay = self
+                .options
+                .attract_delay
+                .filter(|_| self.network_host.is_none());
+
+            if event.is_some() {
+                last_key_press = get_now();
+            } else if let Some(attract_delay) = attract_delay {
+                if get_now() - last_key_press > attract_delay as u128 * 1000 {
+                    self.run_attract_mode()?;
+
+                    last_key_press = get_now();
+                }
+            }
+
             if let Some(event) = event {
                 if event == Event::Key(KeyCode::Esc.into()) {
                     while_running = false;
@@ -164,124 +364,462 @@ impl AppManager {
                     new_game = true;
                     while_running = false;
                 }
+                if event == Event::Key(KeyCode::Char('t').into()) {
+                    self.change_theme();
+
+                    border = Borders::new(&self.dimensions, self.settings.theme.border)?;
+                    game_over_text = load_game_over_text(&self.settings.theme);
+                }
+                if event == Event::Key(KeyCode::Char('m').into()) {
+                    self.change_mode();
+                }
+                if event == Event::Key(KeyCode::Char('l').into()) {
+                    self.select_level()?;
+                }
+                if event == Event::Key(KeyCode::Char('p').into()) {
+                    self.change_players();
+                }
             }
 
-            display_controller.draw_drawable(&border.get_drawable_state())?;
+            let theme = self.settings.theme;
+
+            display_controller.draw_drawable(border.get_drawable_state())?;
+
+            if screen == MenuScreen::Title {
+                display_controller.draw_str(
+                    TITLE_TEXT,
+                    DEFAULT_BACKGROUND,
+                    theme.game_over.unwrap_or(theme.warning),
+                    Point {
+                        height: draw_start_height + 4,
+                        width: self.dimensions.width / 2 - TITLE_TEXT.len() as i64 / 2,
+                    },
+                )?;
+            } else if self.game_state.level_complete {
+                let level_complete_text = format!(
+                    "Level complete: {}",
+                    self.level
+                        .as_ref()
+                        .map(|level| level.name.as_str())
+                        .unwrap_or_default()
+                );
+
+                display_controller.draw_str(
+                    &level_complete_text,
+                    DEFAULT_BACKGROUND,
+                    theme.game_over.unwrap_or(theme.warning),
+                    Point {
+                        height: draw_start_height + 4,
+                        width: self.dimensions.width / 2 - level_complete_text.len() as i64 / 2,
+                    },
+                )?;
+            } else {
+                display_controller.layout.draw_map(
+                    &game_over_text.get_layout().map,
+                    Point {
+                        height: draw_start_height,
+                        width: self.dimensions.width / 2 - 47,
+                    },
+                    &Default::default(),
+                )?;
+            }
 
-            display_controller.layout.draw_map(
-                &map_from_str(GAME_OVER_TEXT, Color::Green),
+            // The title screen has no game to show the score of
+            if screen == MenuScreen::GameOver {
+                display_controller.draw_str(
+                    "Score:",
+                    DEFAULT_BACKGROUND,
+                    theme.warning,
+                    Point {
+                        height: draw_start_height + 10,
+                        width: self.dimensions.width / 2 - 5,
+                    },
+                )?;
+
+                let score_items = self.game_state.score.to_string().len();
+
+                display_controller.draw_u32(
+                    self.game_state.score as u32,
+                    Point {
+                        height: draw_start_height + 12,
+                        width: self.dimensions.width / 2 - (2 + score_items as i64 * 3),
+                    },
+                    theme.score,
+                )?;
+
+                let stats = self.game_state.stats;
+                let stats_text = format!(
+                "Enemies destroyed: {}   Bullet hits: {}   Hits taken: {}   Bosses defeated: {}",
+                stats.enemies_destroyed, stats.bullet_hits, stats.hits_taken, stats.bosses_defeated
+            );
+
+                display_controller.draw_str(
+                    &stats_text,
+                    DEFAULT_BACKGROUND,
+                    theme.text,
+                    Point {
+                        height: draw_start_height + 20,
+                        width: self.dimensions.width / 2 - stats_text.len() as i64 / 2,
+                    },
+                )?;
+
+                if !self.game_state.player_scores.is_empty() {
+                    let player_scores_text = get_player_scores_text(&self.game_state.player_scores);
+
+                    display_controller.draw_str(
+                        &player_scores_text,
+                        DEFAULT_BACKGROUND,
+                        theme.score,
+                        Point {
+                            height: draw_start_height + 18,
+                            width: self.dimensions.width / 2 - player_scores_text.len() as i64 / 2,
+                        },
+                    )?;
+                }
+            }
+
+            display_controller.draw_str(
+                "Press ENTER for new game",
+                DEFAULT_BACKGROUND,
+                theme.text,
                 Point {
-                    height: draw_start_height,
-                    width: self.dimensions.width / 2 - 47,
+                    height: self.dimensions.height - 4,
+                    width: self.dimensions.width / 2 - 13,
                 },
-                &Default::default(),
             )?;
 
             display_controller.draw_str(
-                "Score:",
+                "or ESC to close",
                 DEFAULT_BACKGROUND,
-                Color::Red,
+                theme.warning,
                 Point {
-                    height: draw_start_height + 10,
-                    width: self.dimensions.width / 2 - 5,
+                    height: self.dimensions.height - 3,
+                    width: self.dimensions.width / 2 - 9,
                 },
             )?;
 
-            let score_items = self.game_state.score.to_string().len();
+            let theme_text = format!("Press T to change the theme: {}", theme.name);
 
-            display_controller.draw_u32(
-                self.game_state.score as u32,
+            display_controller.draw_str(
+                &theme_text,
+                DEFAULT_BACKGROUND,
+                theme.text,
                 Point {
-                    height: draw_start_height + 12,
-                    width: self.dimensions.width / 2 - (2 + score_items as i64 * 3),
+                    height: self.dimensions.height - 6,
+                    width: self.dimensions.width / 2 - theme_text.len() as i64 / 2,
                 },
-                Color::Cyan,
             )?;
 
+            let mode_text = format!("Press M to change the mode: {}", self.settings.mode.name);
+
             display_controller.draw_str(
-                "Press ENTER for new game",
+                &mode_text,
                 DEFAULT_BACKGROUND,
-                Color::Green,
+                theme.text,
                 Point {
-                    height: self.dimensions.height - 4,
-                    width: self.dimensions.width / 2 - 13,
+                    height: self.dimensions.height - 7,
+                    width: self.dimensions.width / 2 - mode_text.len() as i64 / 2,
                 },
             )?;
 
+            let level_text = format!("Press L to select a level: {}", self.get_level_name());
+
             display_controller.draw_str(
-                "or ESC to close",
+                &level_text,
                 DEFAULT_BACKGROUND,
-                Color::Red,
+                theme.text,
                 Point {
-                    height: self.dimensions.height - 3,
-                    width: self.dimensions.width / 2 - 9,
+                    height: self.dimensions.height - 8,
+                    width: self.dimensions.width / 2 - level_text.len() as i64 / 2,
                 },
             )?;
 
+            let players_text = format!(
+                "Press P to change the players: {}",
+                self.settings.players.name
+            );
+
+            display_controller.draw_str(
+                &players_text,
+                DEFAULT_BACKGROUND,
+                theme.text,
+                Point {
+                    height: self.dimensions.height - 9,
+                    width: self.dimensions.width / 2 - players_text.len() as i64 / 2,
+                },
+            )?;
+
+            if let Some(network_host) = &mut self.network_host {
+                // Clients can join between games, what they press before the game starts is dropped
+                network_host.poll_actions()?;
+
+                let network_text = format!(
+                    "Hosting on {}: {}",
+                    network_host.get_address()?,
+                    match network_host.has_client() {
+                        true => "the second player has joined",
+                        false => "waiting for the second player",
+                    }
+                );
+
+                display_controller.draw_str(
+                    &network_text,
+                    DEFAULT_BACKGROUND,
+                    theme.text,
+                    Point {
+                        height: self.dimensions.height - 11,
+                        width: self.dimensions.width / 2 - network_text.len() as i64 / 2,
+                    },
+                )?;
+            }
+
             self.output.print_display(&display_controller.layout)?;
         }
 
         Ok(new_game)
     }
 
-    fn run_difficulty_selection(&mut self) -> AppResult<DifficultyOption> {
-        let difficulty_selected: Option<DifficultyDisplay> = None;
-        let mut display_controller = DisplayController::new(self.dimensions, Default::default())?;
+    /// The demo replay in the assets directory with the level it was recorded on, None if there isn't one or it doesn't fit the
+    /// screen
+    fn load_demo_replay(&self) -> Option<(Replay, Option<Level>)> {
+        let replay = Replay::load_demo().ok().filter(|replay| {
+            replay.dimensions.width <= self.dimensions.width
+                && replay.dimensions.height <= self.dimensions.height
+        })?;
 
-        let start_position: Point<i64> = Default::default();
-        let difficulty = DifficultyDisplay::new("Low", 1, Default::default()).unwrap();
+        let level = replay.load_level().ok()?;
 
-        // let difficulty_displays: Vec<DifficultyDisplay> = DIFFICULTIES
-        //     .into_iter()
-        //     .map(|option| {
-        //         DifficultyDisplay::new(option.name, option.level, Default::default()).unwrap()
-        //     })
-        //     .collect();
+        Some((replay, level))
+    }
 
-        let mut difficulty_selection_running = true;
+    /// Plays a demo behind a "PRESS ENTER" overlay until a key is pressed or the demo ends, then returns to the menu. The
+    /// demo replay from the assets directory is played if there is one, otherwise the ships are flown by the autopilot.
+    /// The demo keeps its own game state, so the game over screen still shows the last game played.
+    fn run_attract_mode(&mut self) -> AppResult<()> {
+        let demo_replay = self.load_demo_replay();
+
+        let (settings, level, dimensions, seed, lives) = match &demo_replay {
+            Some((replay, level)) => (
+                replay.get_settings(&self.settings),
+                level.clone(),
+                replay.dimensions,
+                replay.seed,
+                replay.lives,
+            ),
+            None => (
+                self.settings,
+                self.level.clone(),
+                self.dimensions,
+                rand::random(),
+                DEFAULT_LIVES,
+            ),
+        };
+
+        let mut app = App::new(
+            dimensions,
+            InitialGameState {
+                player_health: lives,
+                seed: Some(seed),
+            },
+            &settings,
+            level,
+        )?
+        .with_overlay(ATTRACT_OVERLAY_TEXT);
+
+        let mut game_state = GameState::new();
+        game_state.start_game(&settings.players);
+
+        let mut autopilots: Vec<Box<dyn Autopilot>> = (0..settings.players.players.max(1) as u64)
+            .map(|player| (DODGING_AUTOPILOT.create)(seed.wrapping_add(player)))
+            .collect();
+
+        let mut replay_frames = demo_replay.map(|(replay, _)| replay.frames.into_iter());
+
+        // A demo recorded on a smaller screen would leave the menu around it
+        if dimensions != self.dimensions {
+            self.output.clear()?;
+        }
 
-        while difficulty_selection_running {
-            display_controller.layout.reset();
+        while game_state.is_running() {
+            let game_loop_start = get_now();
 
-            let event = get_keyboard_event(GAME_LOOP_DELAY)?;
+            // The keys pressed on the keyboard only stop the demo, the demo is played by the replay or the autopilot
+            let (events, demo_events, game_loop_duration) = match &mut replay_frames {
+                Some(frames) => {
+                    let Some(frame) = frames.next() else {
+                        break;
+                    };
+
+                    (
+                        get_keyboard_events(frame.duration as u64)?,
+                        Replay::get_events(&frame),
+                        frame.duration,
+                    )
+                }
+                None => {
+                    let events = get_keyboard_events(self.options.frame_delay)?;
+
+                    let autopilot_events = autopilots
+                        .iter_mut()
+                        .enumerate()
+                        .flat_map(|(player, autopilot)| {
+                            app.get_autopilot_events(
+                                player,
+                                autopilot.as_mut(),
+                                self.options.frame_delay as u128,
+                            )
+                        })
+                        .collect();
+
+                    (events, autopilot_events, get_now() - game_loop_start)
+                }
+            };
 
-            if let Some(event) = event {
+            if events.iter().any(|event| matches!(event, Event::Key(_))) {
+                break;
+            }
+
+            game_state.keyboard_events = demo_events;
+
+            app.run_next_game_frame(&mut self.output, &mut game_state, game_loop_duration)?;
+        }
+
+        if dimensions != self.dimensions {
+            self.output.clear()?;
+        }
+
+        Ok(())
+    }
+
+    fn get_level_name(&self) -> &str {
+        self.level
+            .as_ref()
+            .map(|level| level.name.as_str())
+            .unwrap_or(ENDLESS_LEVEL_NAME)
+    }
+
+    /// Shows the levels in the levels directory, the selected level is played from the next game. Invalid levels are listed
+    /// with their error so they can be fixed, but can't be selected.
+    fn select_level(&mut self) -> AppResult<()> {
+        let mut display_controller = DisplayController::new(self.dimensions, Default::default())?;
+        let border = Borders::new(&self.dimensions, self.settings.theme.border)?;
+
+        let level_files = load_levels();
+
+        // The first item is the endless game mode
+        let mut selected = self
+            .level
+            .as_ref()
+            .and_then(|level| {
+                level_files.iter().position(|file| {
+                    file.level
+                        .as_ref()
+                        .is_ok_and(|file_level| file_level == level)
+                })
+            })
+            .map(|index| index + 1)
+            .unwrap_or(0);
+
+        let get_item_text = |file: &LevelFile| match &file.level {
+            Ok(level) => level.name.clone(),
+            Err(_) => format!("{} (invalid)", file.file_name),
+        };
+
+        let draw_start_height = self.dimensions.height / 2 - 10;
+        let draw_start_width = self.dimensions.width / 2 - 20;
+
+        loop {
+            display_controller.layout.reset();
+
+            if let Some(event) = get_keyboard_event(GAME_LOOP_DELAY)? {
                 if event == Event::Key(KeyCode::Esc.into()) {
-                    difficulty_selection_running = false;
+                    return Ok(());
+                }
+                if event == Event::Key(KeyCode::Up.into()) {
+                    selected = selected.saturating_sub(1);
+                }
+                if event == Event::Key(KeyCode::Down.into()) {
+                    selected = (selected + 1).min(level_files.len());
+                }
+                if event == Event::Key(KeyCode::Enter.into()) {
+                    match selected {
+                        0 => {
+                            self.level = None;
+
+                            return Ok(());
+                        }
+                        index => {
+                            if let Ok(level) = &level_files[index - 1].level {
+                                self.level = Some(level.clone());
+
+                                return Ok(());
+                            }
+                        }
+                    }
                 }
             }
 
-            let updated_position = start_position + (10 as i64).into();
-
-            display_controller.layout.draw_map(
-                &difficulty.layout.map,
-                start_position,
-                &Default::default(),
-            )?;
-            // for (idx, difficulty) in difficulty_displays.iter().enumerate() {
-            //     let updated_position = start_position + (10 as i64).into();
+            let theme = self.settings.theme;
 
-            //     display_controller.layout.draw_map(
-            //         &difficulty.layout.map,
-            //         start_position,
-            //         &updated_position,
-            //     )?;
-            // }
+            display_controller.draw_drawable(border.get_drawable_state())?;
 
-            self.output.print_display(&display_controller.layout)?;
+            display_controller.draw_str(
+                "Select a level",
+                DEFAULT_BACKGROUND,
+                theme.warning,
+                Point {
+                    height: draw_start_height,
+                    width: draw_start_width,
+                },
+            )?;
 
-            // let game_loop_start = get_now();
+            let items = std::iter::once(ENDLESS_LEVEL_NAME.to_string())
+                .chain(level_files.iter().map(get_item_text));
+
+            for (index, item) in items.enumerate() {
+                let (prefix, color) = match index == selected {
+                    true => ("> ", theme.warning),
+                    false => ("  ", theme.text),
+                };
+
+                display_controller.draw_str(
+                    &format!("{}{}", prefix, item),
+                    DEFAULT_BACKGROUND,
+                    color,
+                    Point {
+                        height: draw_start_height + 2 + index as i64,
+                        width: draw_start_width,
+                    },
+                )?;
+            }
 
-            // self.handle_keyboard()?;
+            if let Some(Err(error)) = selected
+                .checked_sub(1)
+                .and_then(|index| level_files.get(index))
+                .map(|file| &file.level)
+            {
+                display_controller.draw_str(
+                    &error.to_string(),
+                    DEFAULT_BACKGROUND,
+                    theme.warning,
+                    Point {
+                        height: draw_start_height + 4 + level_files.len() as i64,
+                        width: draw_start_width,
+                    },
+                )?;
+            }
 
-            // let game_loop_duration = get_now() - game_loop_start;
+            display_controller.draw_str(
+                "Use the arrow keys to choose, ENTER to select or ESC to go back",
+                DEFAULT_BACKGROUND,
+                theme.text,
+                Point {
+                    height: self.dimensions.height - 4,
+                    width: self.dimensions.width / 2 - 32,
+                },
+            )?;
 
-            // app.run_next_game_frame(&mut self.output, &mut self.game_state, game_loop_duration)?;
+            self.output.print_display(&display_controller.layout)?;
         }
-
-        Ok(DifficultyOption {
-            name: "Easy",
-            level: 1,
-        })
     }
 }
@@ -0,0 +1,161 @@
+use std::io::stdout;
+
+use crossterm::event::{Event, KeyCode};
+
+use crate::{
+    api::display::{
+        element::DEFAULT_BACKGROUND, get_player_scores_text, ColorMode, DisplayController, Output,
+        Point, MINIMUM_SCREEN_WIDTH,
+    },
+    components::{Drawable, DrawableState, DrawableType},
+    entities::{Borders, ARROW_KEY_BINDINGS},
+    helpers::get_keyboard_events,
+    network::{HostMessage, NetworkClient, Snapshot},
+};
+
+use super::{
+    app::HUD_HEIGHT,
+    app_errors::{AppError, AppResult},
+    GameState, Settings,
+};
+
+/// Shorter than the game loop of the host so snapshots are drawn soon after they arrive
+const CLIENT_LOOP_DELAY: u64 = 25;
+
+/// Plays the second ship of a game hosted on another machine. The client doesn't run the game, it sends the actions of the
+/// arrow keys to the host and draws the snapshots the host sends back.
+pub struct ClientManager {
+    dimensions: Point<i64>,
+    output: Output,
+    settings: Settings,
+    client: NetworkClient,
+}
+
+impl ClientManager {
+    pub fn new(
+        dimensions: Point<i64>,
+        color_mode: ColorMode,
+        settings: Settings,
+        client: NetworkClient,
+    ) -> AppResult<ClientManager> {
+        if dimensions.width < MINIMUM_SCREEN_WIDTH as i64 {
+            return Err(AppError::ScreenWidthTooSmall(
+                dimensions.width as u64,
+                MINIMUM_SCREEN_WIDTH,
+            ));
+        }
+
+        Ok(ClientManager {
+            dimensions,
+            output: Output::new(stdout(), color_mode),
+            settings,
+            client,
+        })
+    }
+
+    /// Runs until ESC is pressed or the host closes the connection
+    pub fn run(&mut self) -> AppResult<()> {
+        self.output.start()?;
+
+        let result = self.run_client();
+
+        self.client.leave();
+        self.output.close()?;
+
+        result
+    }
+
+    fn run_client(&mut self) -> AppResult<()> {
+        let mut display_controller =
+            DisplayController::new(self.dimensions, Point::new(0, HUD_HEIGHT))?;
+        let game_screen_size = self.dimensions.sub_height(HUD_HEIGHT);
+        let theme = self.settings.theme;
+        let borders = Borders::new(&game_screen_size, theme.border)?;
+
+        let mut snapshot: Option<Snapshot> = None;
+        let mut game_over: Option<(u64, Vec<u64>)> = None;
+
+        loop {
+            let events = get_keyboard_events(CLIENT_LOOP_DELAY)?;
+
+            if events.contains(&Event::Key(KeyCode::Esc.into())) {
+                return Ok(());
+            }
+
+            for action in events
+                .iter()
+                .filter_map(|event| ARROW_KEY_BINDINGS.get_action(event))
+            {
+                self.client.send_action(action)?;
+            }
+
+            for message in self.client.receive()? {
+                match message {
+                    HostMessage::Snapshot(next_snapshot) => {
+                        snapshot = Some(next_snapshot);
+                        game_over = None;
+                    }
+                    HostMessage::GameOver(score, player_scores) => {
+                        game_over = Some((score, player_scores))
+                    }
+                    HostMessage::Welcome(_) | HostMessage::Full => {}
+                }
+            }
+
+            display_controller.layout.reset();
+
+            display_controller.draw_drawable(borders.get_drawable_state())?;
+
+            let status_text = match (&snapshot, &game_over) {
+                (None, _) => Some("Waiting for the host to start a game".to_string()),
+                (_, Some((score, player_scores))) => Some(match player_scores.is_empty() {
+                    true => format!("Game over, score: {}", score),
+                    false => format!(
+                        "Game over, score: {}   {}",
+                        score,
+                        get_player_scores_text(player_scores)
+                    ),
+                }),
+                _ => None,
+            };
+
+            if let Some(snapshot) = &snapshot {
+                display_controller.draw_drawable(&DrawableState::new(
+                    snapshot.get_layout(&game_screen_size),
+                    Point::default(),
+                    DrawableType::Effect,
+                    None,
+                ))?;
+
+                let mut game_state = GameState::new();
+                game_state.score = snapshot.score;
+                game_state.player_scores = snapshot.player_scores.clone();
+
+                display_controller.draw_game_state(&game_state, &snapshot.lives, &theme)?;
+
+                if let Some(boss) = &snapshot.boss {
+                    display_controller.draw_boss_health(
+                        &boss.name,
+                        boss.health,
+                        boss.max_health,
+                        &theme,
+                    )?;
+                }
+            }
+
+            if let Some(status_text) = status_text {
+                display_controller.draw_str(
+                    &status_text,
+                    DEFAULT_BACKGROUND,
+                    theme.warning,
+                    Point {
+                        height: self.dimensions.height / 2,
+                        width: self.dimensions.width / 2 - status_text.len() as i64 / 2,
+                    },
+                )?;
+            }
+
+            self.output.print_display(&display_controller.layout)?;
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{
+    api::display::{Point, TwoDVec},
+    components::CollisionLayer,
+    entities::Action,
+    levels::Level,
+};
+
+use super::{app::InitialGameState, App, AppError, AppResult, GameOptions, GameState, Settings};
+
+/// What fills a cell of the play field in an observation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellKind {
+    #[default]
+    Empty,
+    Ship,
+    /// Asteroids and UFOs
+    Enemy,
+    Bullet,
+    Pickup,
+    Boss,
+    Border,
+    /// Explosions and other entities that can't be collided with
+    Effect,
+}
+
+impl CellKind {
+    pub fn from_layer(layer: Option<CollisionLayer>) -> Self {
+        match layer {
+            Some(CollisionLayer::Player) => Self::Ship,
+            Some(CollisionLayer::Enemy) => Self::Enemy,
+            Some(CollisionLayer::Ammunition) => Self::Bullet,
+            Some(CollisionLayer::Pickup) => Self::Pickup,
+            Some(CollisionLayer::Boss) => Self::Boss,
+            Some(CollisionLayer::Border) => Self::Border,
+            Some(CollisionLayer::Effect) | None => Self::Effect,
+        }
+    }
+}
+
+/// A player's ship in an observation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerObservation {
+    /// None once the ship is destroyed
+    pub location: Option<Point<i64>>,
+    /// In cells per second
+    pub velocity: Point<i64>,
+    pub lives: u32,
+}
+
+/// Everything in the play field other than the ships, in the order the entities were spawned
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityObservation {
+    pub kind: CellKind,
+    pub location: Point<i64>,
+    pub size: Point<i64>,
+    /// In cells per second
+    pub velocity: Point<i64>,
+    /// None for entities that can't be damaged, such as bullets
+    pub health: Option<u32>,
+}
+
+/// What an agent sees of the game after a tick
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    /// The play field without the HUD, rows of cells from the top
+    pub grid: TwoDVec<CellKind>,
+    pub players: Vec<PlayerObservation>,
+    pub entities: Vec<EntityObservation>,
+    pub score: u64,
+}
+
+impl Observation {
+    /// The lives left over every ship
+    pub fn get_lives(&self) -> u32 {
+        self.players.iter().map(|player| player.lives).sum()
+    }
+}
+
+/// The outcome of a tick
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepResult {
+    pub observation: Observation,
+    /// The points scored in the tick
+    pub reward: u64,
+    /// The lives lost in the tick, over every ship
+    pub damage: u32,
+    /// The game is over or the level is complete, stepping again does nothing until the environment is reset
+    pub done: bool,
+}
+
+/// A game in progress
+struct Episode {
+    app: App,
+    game_state: GameState,
+    observation: Observation,
+}
+
+/// Plays games a tick at a time without a terminal, for agents that learn to play. Each tick takes the frame delay of the options,
+/// so a game is the same every time it is played with the same seed and actions.
+pub struct Environment {
+    dimensions: Point<i64>,
+    settings: Settings,
+    options: GameOptions,
+    level: Option<Level>,
+    episode: Option<Episode>,
+}
+
+impl Environment {
+    pub fn new(dimensions: Point<i64>, settings: Settings) -> Self {
+        Self {
+            dimensions,
+            settings,
+            options: GameOptions::default(),
+            level: None,
+            episode: None,
+        }
+    }
+
+    /// Sets the lives and the length of a tick, the options for recording and replays aren't used
+    pub fn with_options(mut self, options: GameOptions) -> Self {
+        self.options = options;
+
+        self
+    }
+
+    /// Plays the level instead of the game mode's random spawning
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.level = Some(level);
+
+        self
+    }
+
+    /// Starts a new game, the random spawning is seeded so resetting with the same seed plays the same game
+    pub fn reset(&mut self, seed: u64) -> AppResult<Observation> {
+        let mut game_state = GameState::new();
+        game_state.start_game(&self.settings.players);
+
+        let app = App::new(
+            self.dimensions,
+            InitialGameState {
+                player_health: self.options.lives,
+                seed: Some(seed),
+            },
+            &self.settings,
+            self.level.clone(),
+        )?;
+
+        let observation = app.get_observation(&game_state);
+
+        self.episode = Some(Episode {
+            app,
+            game_state,
+            observation: observation.clone(),
+        });
+
+        Ok(observation)
+    }
+
+    /// Advances the game a tick with the actions pressed by the first player
+    pub fn step(&mut self, actions: &[Action]) -> AppResult<StepResult> {
+        self.step_players(&[actions.to_vec()])
+    }
+
+    /// Advances the game a tick with the actions pressed by each player, in the order of the players
+    pub fn step_players(&mut self, actions: &[Vec<Action>]) -> AppResult<StepResult> {
+        let tick = self.options.frame_delay as u128;
+        let Some(episode) = &mut self.episode else {
+            return Err(AppError::EnvironmentNotReset);
+        };
+
+        if is_done(&episode.game_state) {
+            return Ok(StepResult {
+                observation: episode.observation.clone(),
+                reward: 0,
+                damage: 0,
+                done: true,
+            });
+        }
+
+        episode.game_state.keyboard_events = actions
+            .iter()
+            .enumerate()
+            .flat_map(|(player, actions)| episode.app.get_action_events(player, actions))
+            .collect();
+
+        episode.app.update(&mut episode.game_state, tick)?;
+
+        let observation = episode.app.get_observation(&episode.game_state);
+        let previous = std::mem::replace(&mut episode.observation, observation.clone());
+
+        Ok(StepResult {
+            reward: observation.score - previous.score,
+            damage: previous.get_lives().saturating_sub(observation.get_lives()),
+            done: is_done(&episode.game_state),
+            observation,
+        })
+    }
+}
+
+fn is_done(game_state: &GameState) -> bool {
+    game_state.game_over || game_state.level_complete
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        app::{AppError, Settings, BOUNCE_MODE, CLASSIC_MODE, DEFAULT_LIVES},
+        entities::Action,
+    };
+
+    use super::{CellKind, Environment, StepResult};
+
+    const ACTIONS: [&[Action]; 4] = [
+        &[Action::Left, Action::Fire],
+        &[],
+        &[Action::Right, Action::Special],
+        &[Action::Up],
+    ];
+
+    fn play(environment: &mut Environment, seed: u64, ticks: usize) -> Vec<StepResult> {
+        environment.reset(seed).unwrap();
+
+        (0..ticks)
+            .map(|tick| environment.step(ACTIONS[tick % ACTIONS.len()]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn it_steps_the_same_game_for_the_same_seed() {
+        let mut environment = Environment::new(Point::new(120, 40), Settings::default());
+
+        let first_game = play(&mut environment, 5, 400);
+
+        assert_eq!(first_game[0].observation.grid.len(), 30);
+        assert_eq!(first_game[0].observation.grid[0].len(), 120);
+        assert!(first_game.iter().any(|result| result
+            .observation
+            .grid
+            .iter()
+            .flatten()
+            .any(|cell| *cell == CellKind::Enemy)));
+        assert_eq!(
+            play(
+                &mut Environment::new(Point::new(120, 40), Settings::default()),
+                5,
+                400
+            ),
+            first_game
+        );
+        assert_ne!(play(&mut environment, 6, 400), first_game);
+    }
+
+    #[test]
+    fn it_rewards_points_and_reports_damage_until_the_game_is_over() {
+        let mut environment = Environment::new(Point::new(120, 40), Settings::default());
+
+        assert!(matches!(
+            environment.step(&[]),
+            Err(AppError::EnvironmentNotReset)
+        ));
+
+        let results = play(&mut environment, 2, 5_000);
+        let last_tick = results.iter().position(|result| result.done).unwrap();
+
+        assert_eq!(
+            results[..=last_tick]
+                .iter()
+                .map(|result| result.damage)
+                .sum::<u32>(),
+            DEFAULT_LIVES
+        );
+        assert_eq!(
+            results.iter().map(|result| result.reward).sum::<u64>(),
+            results[last_tick].observation.score
+        );
+        assert!(results[last_tick..]
+            .iter()
+            .all(|result| result.done && result.observation == results[last_tick].observation));
+    }
+
+    #[test]
+    fn it_destroys_and_bounces_the_same_way_for_the_same_seed() {
+        for mode in [CLASSIC_MODE, BOUNCE_MODE] {
+            let settings = Settings {
+                mode,
+                ..Settings::default()
+            };
+
+            for seed in 0..5 {
+                let game = play(
+                    &mut Environment::new(Point::new(120, 40), settings),
+                    seed,
+                    3_000,
+                );
+
+                assert!(
+                    game.iter().any(|result| result.reward > 0),
+                    "Nothing was destroyed in the {} mode with the seed {seed}",
+                    mode.name
+                );
+                assert_eq!(
+                    play(
+                        &mut Environment::new(Point::new(120, 40), settings),
+                        seed,
+                        3_000
+                    ),
+                    game,
+                    "The {} mode played differently with the seed {seed}",
+                    mode.name
+                );
+            }
+        }
+    }
+}
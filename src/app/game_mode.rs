@@ -0,0 +1,75 @@
+use crate::systems::{SpawnTable, CLASSIC_SPAWN_TABLE, STANDARD_SPAWN_TABLE};
+
+/// The rules that change between game modes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameMode {
+    /// The name used to select the game mode in the settings file
+    pub name: &'static str,
+    /// Asteroids bounce off each other instead of passing through
+    pub asteroid_bounce: bool,
+    /// Everything wraps around the edges of the play field instead of being blocked or despawned
+    pub screen_wrap: bool,
+    /// The ship rotates and thrusts with inertia instead of moving in fixed directions
+    pub free_flight: bool,
+    /// What is spawned and how often
+    pub spawn_table: &'static SpawnTable,
+    /// How far entities can leave the play field before they are despawned, nothing is despawned when the screen wraps
+    pub cull_margin: i64,
+}
+
+// Asteroids spawn just above the play field, so they need room to move onto it
+const DEFAULT_CULL_MARGIN: i64 = 5;
+
+pub const STANDARD_MODE: GameMode = GameMode {
+    name: "standard",
+    asteroid_bounce: false,
+    screen_wrap: false,
+    free_flight: false,
+    spawn_table: &STANDARD_SPAWN_TABLE,
+    cull_margin: DEFAULT_CULL_MARGIN,
+};
+
+pub const BOUNCE_MODE: GameMode = GameMode {
+    name: "bounce",
+    asteroid_bounce: true,
+    screen_wrap: false,
+    free_flight: false,
+    spawn_table: &STANDARD_SPAWN_TABLE,
+    cull_margin: DEFAULT_CULL_MARGIN,
+};
+
+/// The arcade rules, the ship flies freely and everything wraps around the screen
+pub const CLASSIC_MODE: GameMode = GameMode {
+    name: "classic",
+    asteroid_bounce: false,
+    screen_wrap: true,
+    free_flight: true,
+    spawn_table: &CLASSIC_SPAWN_TABLE,
+    cull_margin: DEFAULT_CULL_MARGIN,
+};
+
+pub const GAME_MODES: [GameMode; 3] = [STANDARD_MODE, BOUNCE_MODE, CLASSIC_MODE];
+
+impl Default for GameMode {
+    fn default() -> Self {
+        STANDARD_MODE
+    }
+}
+
+impl GameMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+
+        GAME_MODES.iter().find(|mode| mode.name == name).copied()
+    }
+
+    /// Returns the game mode after this one, looping back to the first game mode
+    pub fn next(&self) -> Self {
+        let index = GAME_MODES
+            .iter()
+            .position(|mode| mode == self)
+            .unwrap_or_default();
+
+        GAME_MODES[(index + 1) % GAME_MODES.len()]
+    }
+}
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+/// The starting lives of each ship, lives are drawn as a single digit
+pub const DEFAULT_LIVES: u32 = 3;
+pub const MAX_LIVES: u32 = 9;
+
+/// The time waited for keyboard events each frame, in milliseconds
+pub const DEFAULT_FRAME_DELAY: u64 = 75;
+
+/// How long the title or game over screen waits for a key press before playing a demo, in seconds
+pub const DEFAULT_ATTRACT_DELAY: u64 = 30;
+
+/// Options for the games of a session that aren't saved to the settings file, usually set from the command line
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameOptions {
+    pub lives: u32,
+    pub frame_delay: u64,
+    /// Seeds the random spawning of every game, None picks a different seed every game
+    pub seed: Option<u64>,
+    /// Saves the input of each game to the file, so it can be played back
+    pub record: Option<PathBuf>,
+    /// Plays back a recorded game instead of reading the keyboard
+    pub replay: Option<PathBuf>,
+    /// Seconds without a key press before the title or game over screen plays a demo, None never plays a demo
+    pub attract_delay: Option<u64>,
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        Self {
+            lives: DEFAULT_LIVES,
+            frame_delay: DEFAULT_FRAME_DELAY,
+            seed: None,
+            record: None,
+            replay: None,
+            attract_delay: Some(DEFAULT_ATTRACT_DELAY),
+        }
+    }
+}
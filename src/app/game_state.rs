@@ -1,10 +1,27 @@
 use crossterm::event::Event;
 
+use crate::systems::CollisionEvent;
+
+use super::{GameStats, PlayerMode};
+
 pub struct GameState {
     running: bool,
-    pub keyboard_event: Option<Event>,
+    /// The keyboard events since the last frame
+    pub keyboard_events: Vec<Event>,
+    /// The combined score of every player
     pub score: u64,
+    /// The score of each player, empty when the players share the score
+    pub player_scores: Vec<u64>,
     pub game_over: bool,
+    /// The player met the win condition of the level being played
+    pub level_complete: bool,
+    pub stats: GameStats,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameState {
@@ -12,8 +29,11 @@ impl GameState {
         Self {
             running: false,
             game_over: false,
-            keyboard_event: None,
+            level_complete: false,
+            keyboard_events: vec![],
             score: 0,
+            player_scores: vec![],
+            stats: GameStats::default(),
         }
     }
 
@@ -27,11 +47,47 @@ impl GameState {
         self
     }
 
-    pub fn start_game(&mut self) -> &mut Self {
+    /// Resets the state for a new game
+    ///
+    /// # Arguments
+    ///
+    /// * `player_mode` - Sets whether the score of each player is kept
+    pub fn start_game(&mut self, player_mode: &PlayerMode) -> &mut Self {
         self.game_over = false;
+        self.level_complete = false;
         self.score = 0;
+        self.player_scores = match player_mode.separate_scores {
+            true => vec![0; player_mode.players],
+            false => vec![],
+        };
+        self.stats = GameStats::default();
         self.running = true;
-        self.keyboard_event = None;
+        self.keyboard_events.clear();
+
+        self
+    }
+
+    /// Scores the points of destroyed targets and records the event in the stats
+    ///
+    /// # Arguments
+    ///
+    /// * `scorer` - The index of the player that scored the points, such as the player that fired the bullet
+    pub fn handle_collision_event(
+        &mut self,
+        event: &CollisionEvent,
+        target_destroyed: bool,
+        scorer: Option<usize>,
+    ) -> &mut Self {
+        if target_destroyed {
+            self.score += event.points;
+
+            if let Some(player_score) = scorer.and_then(|scorer| self.player_scores.get_mut(scorer))
+            {
+                *player_score += event.points;
+            }
+        }
+
+        self.stats.record_event(event, target_destroyed);
 
         self
     }
@@ -42,4 +98,25 @@ impl GameState {
 
         self
     }
+
+    pub fn handle_level_complete(&mut self) -> &mut Self {
+        self.level_complete = true;
+        self.stop_game();
+
+        self
+    }
+
+    /// Scores the reward for defeating a boss, the reward is split between the players when they keep separate scores
+    pub fn handle_boss_defeated(&mut self, points: u64) -> &mut Self {
+        self.score += points;
+
+        let players = self.player_scores.len() as u64;
+
+        for player_score in self.player_scores.iter_mut() {
+            *player_score += points / players;
+        }
+        self.stats.bosses_defeated += 1;
+
+        self
+    }
 }
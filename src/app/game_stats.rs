@@ -0,0 +1,94 @@
+use crate::systems::{CollisionEvent, CollisionEventKind};
+
+/// Statistics for a single game, collected from the collision events
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct GameStats {
+    /// Every enemy that entered the game, including the pieces of split asteroids
+    pub enemies_spawned: u32,
+    /// Asteroids, flying saucers and boss shots, boss parts are counted by the bosses defeated
+    pub enemies_destroyed: u32,
+    pub bullet_hits: u32,
+    pub hits_taken: u32,
+    pub bosses_defeated: u32,
+}
+
+impl GameStats {
+    /// Records a collision event
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The collision event
+    /// * `target_destroyed` - Whether the event destroyed its target, this should only be true for one event per target
+    pub fn record_event(&mut self, event: &CollisionEvent, target_destroyed: bool) -> &mut Self {
+        match event.kind {
+            CollisionEventKind::BulletHitEnemy | CollisionEventKind::BulletHitBoss => {
+                self.bullet_hits += 1
+            }
+            CollisionEventKind::EnemyHitPlayer => self.hits_taken += 1,
+            _ => {}
+        }
+
+        if target_destroyed
+            && matches!(
+                event.kind,
+                CollisionEventKind::BulletHitEnemy | CollisionEventKind::PlayerHitEnemy
+            )
+        {
+            self.enemies_destroyed += 1;
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        api::display::Point,
+        components::DrawableType,
+        systems::{CollisionEvent, CollisionEventKind, CollisionParticipant},
+    };
+
+    use super::GameStats;
+
+    fn get_event_mock(kind: CollisionEventKind) -> CollisionEvent {
+        let participant = CollisionParticipant {
+            uuid: Uuid::new_v4(),
+            drawable_type: DrawableType::Player,
+        };
+
+        CollisionEvent {
+            kind,
+            source: participant,
+            target: participant,
+            position: Point::default(),
+            damage: 1,
+            points: 0,
+        }
+    }
+
+    #[test]
+    fn it_records_collision_events() {
+        let mut stats = GameStats::default();
+
+        stats
+            .record_event(&get_event_mock(CollisionEventKind::BulletHitEnemy), false)
+            .record_event(&get_event_mock(CollisionEventKind::BulletHitEnemy), true)
+            .record_event(&get_event_mock(CollisionEventKind::EnemyHitBullet), false)
+            .record_event(&get_event_mock(CollisionEventKind::EnemyHitPlayer), false)
+            .record_event(&get_event_mock(CollisionEventKind::PlayerHitEnemy), true);
+
+        assert_eq!(
+            stats,
+            GameStats {
+                enemies_spawned: 0,
+                enemies_destroyed: 2,
+                bullet_hits: 2,
+                hits_taken: 1,
+                bosses_defeated: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use std::{fs, path::Path};
+
+use crossterm::event::Event;
+
+use crate::{
+    api::display::Point,
+    entities::{Action, Autopilot, AutopilotKind, PLAYER_KEY_BINDINGS},
+    levels::Level,
+};
+
+use super::{
+    app::InitialGameState, App, AppResult, GameOptions, GameState, GameStats, Replay, ReplayError,
+    ReplayResult, Settings,
+};
+
+/// Stops a game that never ends, e.g. a ship that is never hit, after about two hours of game time
+pub const DEFAULT_MAX_FRAMES: u64 = 100_000;
+
+/// The actions pressed by the first player each frame, the script starts over when it runs out
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputScript {
+    pub frames: Vec<Vec<Action>>,
+}
+
+impl InputScript {
+    pub fn load(path: &Path) -> ReplayResult<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parses a script with a line of action names for each frame, e.g. `left fire`. An empty line is a frame without input
+    /// and lines starting with `#` are skipped.
+    pub fn parse(contents: &str) -> ReplayResult<Self> {
+        let mut frames = vec![];
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+
+            if line.starts_with('#') {
+                continue;
+            }
+
+            let actions = line
+                .split_whitespace()
+                .map(Action::from_name)
+                .collect::<Option<Vec<Action>>>()
+                .ok_or_else(|| ReplayError::InvalidLine(index + 1, line.to_string()))?;
+
+            frames.push(actions);
+        }
+
+        Ok(Self { frames })
+    }
+}
+
+/// What presses the keys of a headless game, a replay takes the place of the input
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum HeadlessInput {
+    /// The ships are never moved
+    #[default]
+    Idle,
+    Script(InputScript),
+    /// Every ship is flown by an autopilot
+    Autopilot(AutopilotKind),
+}
+
+/// The outcome of a game run without a terminal
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessResult {
+    pub seed: u64,
+    pub score: u64,
+    pub player_scores: Vec<u64>,
+    pub frames: u64,
+    /// The game time that passed, in milliseconds
+    pub game_time: u128,
+    /// False if the game was stopped by the frame limit or the end of the replay
+    pub game_over: bool,
+    pub stats: GameStats,
+}
+
+/// Runs games as fast as possible without a terminal, each frame is treated as if it took the frame delay
+pub struct HeadlessRunner {
+    dimensions: Point<i64>,
+    settings: Settings,
+    options: GameOptions,
+    input: HeadlessInput,
+    replay: Option<Replay>,
+    level: Option<Level>,
+    max_frames: u64,
+}
+
+impl HeadlessRunner {
+    pub fn new(dimensions: Point<i64>, settings: Settings) -> Self {
+        Self {
+            dimensions,
+            settings,
+            options: GameOptions::default(),
+            input: HeadlessInput::Idle,
+            replay: None,
+            level: None,
+            max_frames: DEFAULT_MAX_FRAMES,
+        }
+    }
+
+    /// Sets the options of the games, a replay sets the settings, level and dimensions it was recorded with
+    pub fn with_options(mut self, options: GameOptions) -> AppResult<Self> {
+        if let Some(path) = &options.replay {
+            let replay = Replay::load(path)?;
+
+            self.level = replay.load_level()?;
+            self.dimensions = replay.dimensions;
+            self.settings = replay.get_settings(&self.settings);
+            self.replay = Some(replay);
+        }
+
+        self.options = options;
+
+        Ok(self)
+    }
+
+    pub fn with_input(mut self, input: HeadlessInput) -> Self {
+        self.input = input;
+
+        self
+    }
+
+    pub fn with_max_frames(mut self, max_frames: u64) -> Self {
+        self.max_frames = max_frames;
+
+        self
+    }
+
+    /// Runs the games one after another. Each game is seeded with the next number after the seed of the previous game, so a
+    /// batch of games can be run again with the same seed.
+    pub fn run(&self, games: usize) -> AppResult<Vec<HeadlessResult>> {
+        let first_seed = match &self.replay {
+            Some(replay) => replay.seed,
+            None => self.options.seed.unwrap_or_else(rand::random),
+        };
+
+        (0..games as u64)
+            .map(|game| match &self.replay {
+                Some(replay) => self.run_game(replay.seed),
+                None => self.run_game(first_seed.wrapping_add(game)),
+            })
+            .collect()
+    }
+
+    fn run_game(&self, seed: u64) -> AppResult<HeadlessResult> {
+        let mut game_state = GameState::new();
+        game_state.start_game(&self.settings.players);
+
+        let lives = match &self.replay {
+            Some(replay) => replay.lives,
+            None => self.options.lives,
+        };
+
+        let mut app = App::new(
+            self.dimensions,
+            InitialGameState {
+                player_health: lives,
+                seed: Some(seed),
+            },
+            &self.settings,
+            self.level.clone(),
+        )?;
+
+        let mut recording = self.options.record.as_ref().map(|_| {
+            Replay::new(
+                seed,
+                &self.settings,
+                lives,
+                self.dimensions,
+                self.level.as_ref().map(|level| level.name.clone()),
+            )
+        });
+
+        let mut replay_frames = self.replay.as_ref().map(|replay| replay.frames.iter());
+        let mut autopilots: Vec<Box<dyn Autopilot>> = match &self.input {
+            HeadlessInput::Autopilot(kind) => (0..self.settings.players.players.max(1) as u64)
+                .map(|player| (kind.create)(seed.wrapping_add(player)))
+                .collect(),
+            _ => vec![],
+        };
+        let mut frames = 0;
+        let mut game_time = 0;
+
+        while game_state.is_running() && frames < self.max_frames {
+            let duration = match &mut replay_frames {
+                Some(replay_frames) => {
+                    let Some(frame) = replay_frames.next() else {
+                        break;
+                    };
+
+                    game_state.keyboard_events = Replay::get_events(frame);
+
+                    frame.duration
+                }
+                None => {
+                    game_state.keyboard_events = self.get_events(&app, frames, &mut autopilots);
+
+                    self.options.frame_delay as u128
+                }
+            };
+
+            if let Some(recording) = &mut recording {
+                recording.record_frame(duration, &game_state.keyboard_events);
+            }
+
+            app.update(&mut game_state, duration)?;
+
+            frames += 1;
+            game_time += duration;
+        }
+
+        if let (Some(recording), Some(path)) = (recording, &self.options.record) {
+            recording.save(path).map_err(ReplayError::from)?;
+        }
+
+        Ok(HeadlessResult {
+            seed,
+            score: game_state.score,
+            player_scores: game_state.player_scores,
+            frames,
+            game_time,
+            game_over: game_state.game_over,
+            stats: game_state.stats,
+        })
+    }
+
+    /// The keys pressed by the input in a frame
+    fn get_events(
+        &self,
+        app: &App,
+        frame: u64,
+        autopilots: &mut [Box<dyn Autopilot>],
+    ) -> Vec<Event> {
+        match &self.input {
+            HeadlessInput::Idle => vec![],
+            HeadlessInput::Script(script) if script.frames.is_empty() => vec![],
+            HeadlessInput::Script(script) => script.frames[frame as usize % script.frames.len()]
+                .iter()
+                .map(|action| PLAYER_KEY_BINDINGS[0].get_event(*action))
+                .collect(),
+            HeadlessInput::Autopilot(_) => autopilots
+                .iter_mut()
+                .enumerate()
+                .flat_map(|(player, autopilot)| {
+                    app.get_autopilot_events(
+                        player,
+                        autopilot.as_mut(),
+                        self.options.frame_delay as u128,
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        app::{GameOptions, Settings},
+        entities::{Action, DODGING_AUTOPILOT, RANDOM_AUTOPILOT},
+    };
+
+    use super::{HeadlessInput, HeadlessRunner, InputScript};
+
+    #[test]
+    fn it_runs_the_same_games_for_the_same_seed() {
+        let runner = HeadlessRunner::new(Point::new(120, 40), Settings::default())
+            .with_options(GameOptions {
+                seed: Some(7),
+                ..Default::default()
+            })
+            .unwrap()
+            .with_input(HeadlessInput::Autopilot(RANDOM_AUTOPILOT))
+            .with_max_frames(2_000);
+
+        let results = runner.run(3).unwrap();
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| result.seed)
+                .collect::<Vec<u64>>(),
+            vec![7, 8, 9]
+        );
+        assert!(results
+            .iter()
+            .all(|result| result.game_over || result.frames == 2_000));
+        assert!(results
+            .iter()
+            .all(|result| result.stats.enemies_spawned > 0));
+        assert_eq!(runner.run(3).unwrap(), results);
+    }
+
+    #[test]
+    fn it_survives_longer_with_the_dodging_autopilot() {
+        let get_survival_time = |input| {
+            HeadlessRunner::new(Point::new(120, 40), Settings::default())
+                .with_options(GameOptions {
+                    seed: Some(1),
+                    ..Default::default()
+                })
+                .unwrap()
+                .with_input(input)
+                .with_max_frames(1_000)
+                .run(5)
+                .unwrap()
+                .iter()
+                .map(|result| result.game_time)
+                .sum::<u128>()
+        };
+
+        assert!(
+            get_survival_time(HeadlessInput::Autopilot(DODGING_AUTOPILOT))
+                > get_survival_time(HeadlessInput::Idle) * 2
+        );
+    }
+
+    #[test]
+    fn it_parses_input_scripts() {
+        assert_eq!(
+            InputScript::parse("# Strafe\nleft fire\n\nright fire").unwrap(),
+            InputScript {
+                frames: vec![
+                    vec![Action::Left, Action::Fire],
+                    vec![],
+                    vec![Action::Right, Action::Fire]
+                ]
+            }
+        );
+        assert!(InputScript::parse("jump").is_err());
+    }
+}
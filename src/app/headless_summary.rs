@@ -0,0 +1,179 @@
+use super::HeadlessResult;
+
+/// How the summary of headless games is printed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum StatsFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl StatsFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// The spread of a statistic over the games, percentiles use the nearest value
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Distribution {
+    pub min: u128,
+    pub p25: u128,
+    pub median: u128,
+    pub p75: u128,
+    pub p90: u128,
+    pub max: u128,
+    pub mean: f64,
+}
+
+impl Distribution {
+    pub fn new(mut values: Vec<u128>) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+
+        values.sort_unstable();
+
+        let percentile = |percent: usize| values[(values.len() - 1) * percent / 100];
+
+        Self {
+            min: values[0],
+            p25: percentile(25),
+            median: percentile(50),
+            p75: percentile(75),
+            p90: percentile(90),
+            max: values[values.len() - 1],
+            mean: values.iter().sum::<u128>() as f64 / values.len() as f64,
+        }
+    }
+}
+
+/// The statistics of a batch of headless games, used to tune the difficulty
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessSummary {
+    pub games: usize,
+    /// The name and distribution of each statistic, `game_over` is 1 for a game that ended with every ship destroyed rather
+    /// than the frame limit so its mean is the share of games lost
+    pub stats: Vec<(&'static str, Distribution)>,
+}
+
+impl HeadlessSummary {
+    pub fn new(results: &[HeadlessResult]) -> Self {
+        let get_distribution = |get_value: fn(&HeadlessResult) -> u128| {
+            Distribution::new(results.iter().map(get_value).collect())
+        };
+
+        Self {
+            games: results.len(),
+            stats: vec![
+                (
+                    "game_over",
+                    get_distribution(|result| result.game_over as u128),
+                ),
+                ("score", get_distribution(|result| result.score as u128)),
+                (
+                    "survival_time_ms",
+                    get_distribution(|result| result.game_time),
+                ),
+                ("frames", get_distribution(|result| result.frames as u128)),
+                (
+                    "enemies_spawned",
+                    get_distribution(|result| result.stats.enemies_spawned as u128),
+                ),
+                (
+                    "enemies_destroyed",
+                    get_distribution(|result| result.stats.enemies_destroyed as u128),
+                ),
+                (
+                    "hits_taken",
+                    get_distribution(|result| result.stats.hits_taken as u128),
+                ),
+                (
+                    "bosses_defeated",
+                    get_distribution(|result| result.stats.bosses_defeated as u128),
+                ),
+            ],
+        }
+    }
+
+    pub fn format(&self, format: StatsFormat) -> String {
+        match format {
+            StatsFormat::Json => self.to_json(),
+            StatsFormat::Csv => self.to_csv(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let stats = self
+            .stats
+            .iter()
+            .map(|(name, distribution)| {
+                format!(
+                    "    \"{}\": {{\"min\": {}, \"p25\": {}, \"median\": {}, \"p75\": {}, \"p90\": {}, \"max\": {}, \"mean\": {:.2}}}",
+                    name,
+                    distribution.min,
+                    distribution.p25,
+                    distribution.median,
+                    distribution.p75,
+                    distribution.p90,
+                    distribution.max,
+                    distribution.mean
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",\n");
+
+        format!(
+            "{{\n  \"games\": {},\n  \"stats\": {{\n{}\n  }}\n}}",
+            self.games, stats
+        )
+    }
+
+    /// A row for each statistic
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec!["stat,games,min,p25,median,p75,p90,max,mean".to_string()];
+
+        lines.extend(self.stats.iter().map(|(name, distribution)| {
+            format!(
+                "{},{},{},{},{},{},{},{},{:.2}",
+                name,
+                self.games,
+                distribution.min,
+                distribution.p25,
+                distribution.median,
+                distribution.p75,
+                distribution.p90,
+                distribution.max,
+                distribution.mean
+            )
+        }));
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Distribution;
+
+    #[test]
+    fn it_gets_the_distribution_of_values() {
+        assert_eq!(
+            Distribution::new(vec![40, 10, 30, 20, 50]),
+            Distribution {
+                min: 10,
+                p25: 20,
+                median: 30,
+                p75: 40,
+                p90: 40,
+                max: 50,
+                mean: 30.0,
+            }
+        );
+        assert_eq!(Distribution::new(vec![]), Distribution::default());
+    }
+}
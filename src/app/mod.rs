@@ -1,8 +1,32 @@
+#[allow(clippy::module_inception)]
 mod app;
 mod app_errors;
 mod app_manager;
+mod client_manager;
+mod environment;
+mod game_mode;
+mod game_options;
 mod game_state;
+mod game_stats;
+mod headless;
+mod headless_summary;
+mod player_mode;
+mod replay;
+mod settings;
+mod spectator_manager;
 
 pub use app::App;
+pub use app_errors::{AppError, AppResult};
 pub use app_manager::*;
+pub use client_manager::ClientManager;
+pub use environment::*;
+pub use game_mode::*;
+pub use game_options::*;
 pub use game_state::*;
+pub use game_stats::*;
+pub use headless::*;
+pub use headless_summary::*;
+pub use player_mode::*;
+pub use replay::*;
+pub use settings::*;
+pub use spectator_manager::SpectatorManager;
@@ -0,0 +1,54 @@
+/// How many ships are playing and how the score is kept, every ship shares the keyboard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerMode {
+    /// The name used to select the player mode in the settings file
+    pub name: &'static str,
+    pub players: usize,
+    /// Each player scores their own points, otherwise the players share a score
+    pub separate_scores: bool,
+}
+
+pub const SINGLE_PLAYER_MODE: PlayerMode = PlayerMode {
+    name: "single",
+    players: 1,
+    separate_scores: false,
+};
+
+pub const CO_OP_SHARED_MODE: PlayerMode = PlayerMode {
+    name: "co_op_shared",
+    players: 2,
+    separate_scores: false,
+};
+
+pub const CO_OP_SEPARATE_MODE: PlayerMode = PlayerMode {
+    name: "co_op_separate",
+    players: 2,
+    separate_scores: true,
+};
+
+pub const PLAYER_MODES: [PlayerMode; 3] =
+    [SINGLE_PLAYER_MODE, CO_OP_SHARED_MODE, CO_OP_SEPARATE_MODE];
+
+impl Default for PlayerMode {
+    fn default() -> Self {
+        SINGLE_PLAYER_MODE
+    }
+}
+
+impl PlayerMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase().replace(['-', ' '], "_");
+
+        PLAYER_MODES.iter().find(|mode| mode.name == name).copied()
+    }
+
+    /// Returns the player mode after this one, looping back to the first player mode
+    pub fn next(&self) -> Self {
+        let index = PLAYER_MODES
+            .iter()
+            .position(|mode| mode == self)
+            .unwrap_or_default();
+
+        PLAYER_MODES[(index + 1) % PLAYER_MODES.len()]
+    }
+}
@@ -0,0 +1,347 @@
+use std::{fmt::Display, fs, io, path::Path};
+
+use crossterm::event::Event;
+
+use crate::{
+    api::{assets::get_assets_directory, display::Point},
+    entities::{Action, PLAYER_KEY_BINDINGS},
+    levels::{load_levels, Level},
+    systems::Difficulty,
+};
+
+use super::{GameMode, PlayerMode, Settings};
+
+const FRAME_PREFIX: &str = "frame ";
+/// The replay in the assets directory played by the attract mode
+const DEMO_REPLAY_FILE: &str = "demo.replay";
+
+/// Errors reading a replay or an input script
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    FileError(io::ErrorKind),
+    /// The line number and contents of a line that couldn't be parsed
+    InvalidLine(usize, String),
+    /// The header key that is missing
+    MissingHeader(&'static str),
+    /// The level the replay was recorded on isn't in the levels directory
+    UnknownLevel(String),
+    /// The dimensions the replay was recorded with, which don't fit the screen
+    ScreenTooSmall(Point<i64>),
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FileError(kind) => write!(f, "Couldn't read the file: {}", kind),
+            Self::InvalidLine(line, contents) => {
+                write!(f, "Line {}: couldn't parse `{}`", line, contents)
+            }
+            Self::MissingHeader(key) => write!(f, "The replay is missing the `{}` header", key),
+            Self::UnknownLevel(name) => write!(
+                f,
+                "The replay was recorded on the level `{}`, which isn't in the levels directory",
+                name
+            ),
+            Self::ScreenTooSmall(dimensions) => write!(
+                f,
+                "The replay was recorded on a {}x{} screen, which doesn't fit this terminal",
+                dimensions.width, dimensions.height
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(error: io::Error) -> Self {
+        Self::FileError(error.kind())
+    }
+}
+
+pub type ReplayResult<T> = Result<T, ReplayError>;
+
+/// The input of a single frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayFrame {
+    /// How long the frame took, in milliseconds
+    pub duration: u128,
+    /// The index of the player and the action they pressed
+    pub actions: Vec<(usize, Action)>,
+}
+
+/// The input of a game and everything needed to play it again, the spawner is seeded so the game plays out the same way
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub mode: GameMode,
+    pub difficulty: Difficulty,
+    pub players: PlayerMode,
+    pub lives: u32,
+    pub dimensions: Point<i64>,
+    /// The name of the level played, None for the endless game mode
+    pub level: Option<String>,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    /// Starts recording a game
+    pub fn new(
+        seed: u64,
+        settings: &Settings,
+        lives: u32,
+        dimensions: Point<i64>,
+        level: Option<String>,
+    ) -> Self {
+        Self {
+            seed,
+            mode: settings.mode,
+            difficulty: settings.difficulty,
+            players: settings.players,
+            lives,
+            dimensions,
+            level,
+            frames: vec![],
+        }
+    }
+
+    /// Records the keys pressed in a frame as the actions of the players they are bound to, other keys are dropped
+    pub fn record_frame(&mut self, duration: u128, events: &[Event]) -> &mut Self {
+        let actions = events
+            .iter()
+            .flat_map(|event| {
+                PLAYER_KEY_BINDINGS
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(player, bindings)| {
+                        bindings.get_action(event).map(|action| (player, action))
+                    })
+            })
+            .collect();
+
+        self.frames.push(ReplayFrame { duration, actions });
+
+        self
+    }
+
+    /// The key presses of a frame, pressed with the key bindings of each player
+    pub fn get_events(frame: &ReplayFrame) -> Vec<Event> {
+        frame
+            .actions
+            .iter()
+            .filter_map(|(player, action)| {
+                PLAYER_KEY_BINDINGS
+                    .get(*player)
+                    .map(|bindings| bindings.get_event(*action))
+            })
+            .collect()
+    }
+
+    /// The settings the replay was recorded with, the theme is kept as it doesn't change the game
+    pub fn get_settings(&self, settings: &Settings) -> Settings {
+        Settings {
+            mode: self.mode,
+            difficulty: self.difficulty,
+            players: self.players,
+            ..*settings
+        }
+    }
+
+    /// Finds the level the replay was recorded on in the levels directory, None for the endless game mode
+    pub fn load_level(&self) -> ReplayResult<Option<Level>> {
+        let Some(name) = &self.level else {
+            return Ok(None);
+        };
+
+        load_levels()
+            .into_iter()
+            .find_map(|file| file.level.ok().filter(|level| &level.name == name))
+            .map(Some)
+            .ok_or_else(|| ReplayError::UnknownLevel(name.clone()))
+    }
+
+    /// Loads the demo replay from the assets directory
+    pub fn load_demo() -> ReplayResult<Self> {
+        Self::load(&get_assets_directory().join(DEMO_REPLAY_FILE))
+    }
+
+    pub fn load(path: &Path) -> ReplayResult<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.get_file_contents())
+    }
+
+    pub fn get_file_contents(&self) -> String {
+        let mut lines = vec![
+            "# Rusty Asteroids replay".to_string(),
+            format!("seed: {}", self.seed),
+            format!("mode: {}", self.mode.name),
+            format!("difficulty: {}", self.difficulty.name),
+            format!("players: {}", self.players.name),
+            format!("lives: {}", self.lives),
+            format!("size: {} {}", self.dimensions.width, self.dimensions.height),
+        ];
+
+        if let Some(level) = &self.level {
+            lines.push(format!("level: {}", level));
+        }
+
+        for frame in &self.frames {
+            let actions = frame
+                .actions
+                .iter()
+                .map(|(player, action)| format!(" {}:{}", player, action.get_name()))
+                .collect::<String>();
+
+            lines.push(format!("{}{}{}", FRAME_PREFIX, frame.duration, actions));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Parses a replay file, a header of `key: value` lines followed by a `frame <duration> [<player>:<action>...]` line
+    /// for each frame
+    pub fn parse(contents: &str) -> ReplayResult<Self> {
+        let mut seed = None;
+        let mut mode = None;
+        let mut difficulty = None;
+        let mut players = None;
+        let mut lives = None;
+        let mut dimensions = None;
+        let mut level = None;
+        let mut frames = vec![];
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            let invalid = || ReplayError::InvalidLine(index + 1, line.to_string());
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(frame) = line.strip_prefix(FRAME_PREFIX) {
+                frames.push(parse_frame(frame).ok_or_else(invalid)?);
+
+                continue;
+            }
+
+            let (key, value) = line.split_once(':').ok_or_else(invalid)?;
+            let value = value.trim();
+
+            match key.trim() {
+                "seed" => seed = Some(value.parse().map_err(|_| invalid())?),
+                "mode" => mode = Some(GameMode::from_name(value).ok_or_else(invalid)?),
+                "difficulty" => {
+                    difficulty = Some(Difficulty::from_name(value).ok_or_else(invalid)?)
+                }
+                "players" => players = Some(PlayerMode::from_name(value).ok_or_else(invalid)?),
+                "lives" => lives = Some(value.parse().map_err(|_| invalid())?),
+                "size" => {
+                    let sizes: Vec<i64> = value
+                        .split_whitespace()
+                        .map(|size| size.parse().map_err(|_| invalid()))
+                        .collect::<ReplayResult<_>>()?;
+
+                    match sizes.as_slice() {
+                        [width, height] => dimensions = Some(Point::new(*width, *height)),
+                        _ => return Err(invalid()),
+                    }
+                }
+                "level" => level = Some(value.to_string()),
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(Self {
+            seed: seed.ok_or(ReplayError::MissingHeader("seed"))?,
+            mode: mode.ok_or(ReplayError::MissingHeader("mode"))?,
+            difficulty: difficulty.ok_or(ReplayError::MissingHeader("difficulty"))?,
+            players: players.ok_or(ReplayError::MissingHeader("players"))?,
+            lives: lives.ok_or(ReplayError::MissingHeader("lives"))?,
+            dimensions: dimensions.ok_or(ReplayError::MissingHeader("size"))?,
+            level,
+            frames,
+        })
+    }
+}
+
+fn parse_frame(value: &str) -> Option<ReplayFrame> {
+    let mut parts = value.split_whitespace();
+
+    let duration = parts.next()?.parse().ok()?;
+
+    let actions = parts
+        .map(|part| {
+            let (player, action) = part.split_once(':')?;
+
+            Some((player.parse().ok()?, Action::from_name(action)?))
+        })
+        .collect::<Option<Vec<(usize, Action)>>>()?;
+
+    Some(ReplayFrame { duration, actions })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        app::{Settings, CLASSIC_MODE, CO_OP_SHARED_MODE},
+        entities::{controller::create_event, Action},
+        systems::HARD_DIFFICULTY,
+    };
+
+    use crossterm::event::KeyCode;
+
+    use super::{Replay, ReplayError};
+
+    #[test]
+    fn it_saves_replays_that_can_be_parsed() {
+        let settings = Settings {
+            mode: CLASSIC_MODE,
+            difficulty: HARD_DIFFICULTY,
+            players: CO_OP_SHARED_MODE,
+            ..Default::default()
+        };
+
+        let mut replay = Replay::new(
+            42,
+            &settings,
+            5,
+            Point::new(120, 40),
+            Some("First contact".to_string()),
+        );
+
+        replay.record_frame(75, &[]).record_frame(
+            60,
+            &[
+                create_event(KeyCode::Up),
+                create_event(KeyCode::Char('g')),
+                // Keys that aren't bound to a player aren't recorded
+                create_event(KeyCode::Char('x')),
+            ],
+        );
+
+        assert_eq!(
+            replay.frames[1].actions,
+            vec![(0, Action::Up), (1, Action::Special)]
+        );
+        assert_eq!(
+            Replay::get_events(&replay.frames[1]),
+            vec![create_event(KeyCode::Up), create_event(KeyCode::Char('g'))]
+        );
+
+        assert_eq!(Replay::parse(&replay.get_file_contents()), Ok(replay));
+    }
+
+    #[test]
+    fn it_reports_invalid_replays() {
+        assert_eq!(
+            Replay::parse("seed: 1\nframe 75 0:jump"),
+            Err(ReplayError::InvalidLine(2, "frame 75 0:jump".to_string()))
+        );
+        assert_eq!(
+            Replay::parse("seed: 1\nmode: standard"),
+            Err(ReplayError::MissingHeader("difficulty"))
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use std::{env, fs, io, path::PathBuf};
+
+use crate::{systems::Difficulty, user_display::Theme};
+
+use super::{GameMode, PlayerMode};
+
+pub const SETTINGS_FILE: &str = "settings.cfg";
+/// Environment variable that overrides the location of the settings file
+pub const SETTINGS_FILE_VARIABLE: &str = "RUSTY_ASTEROIDS_SETTINGS";
+
+const THEME_KEY: &str = "theme";
+const MODE_KEY: &str = "mode";
+const PLAYERS_KEY: &str = "players";
+const DIFFICULTY_KEY: &str = "difficulty";
+
+/// Settings that are persisted between games
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Settings {
+    pub theme: Theme,
+    pub mode: GameMode,
+    pub players: PlayerMode,
+    pub difficulty: Difficulty,
+}
+
+pub fn get_settings_path() -> PathBuf {
+    PathBuf::from(env::var(SETTINGS_FILE_VARIABLE).unwrap_or(SETTINGS_FILE.to_string()))
+}
+
+impl Settings {
+    /// Loads the settings file, falling back to the default settings if the file doesn't exist
+    pub fn load() -> Self {
+        fs::read_to_string(get_settings_path())
+            .map(|contents| Settings::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(get_settings_path(), self.get_file_contents())
+    }
+
+    /// Parses the `key = value` lines of a settings file. Unknown keys and invalid values are skipped, so an outdated
+    /// settings file never stops the game from starting.
+    pub fn parse(contents: &str) -> Self {
+        let mut settings = Settings::default();
+
+        for line in contents.lines() {
+            let line = line.trim();
+
+            if line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            match key.trim() {
+                THEME_KEY => {
+                    if let Some(theme) = Theme::from_name(value) {
+                        settings.theme = theme;
+                    }
+                }
+                MODE_KEY => {
+                    if let Some(mode) = GameMode::from_name(value) {
+                        settings.mode = mode;
+                    }
+                }
+                PLAYERS_KEY => {
+                    if let Some(players) = PlayerMode::from_name(value) {
+                        settings.players = players;
+                    }
+                }
+                DIFFICULTY_KEY => {
+                    if let Some(difficulty) = Difficulty::from_name(value) {
+                        settings.difficulty = difficulty;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        settings
+    }
+
+    pub fn get_file_contents(&self) -> String {
+        format!(
+            "{} = {}\n{} = {}\n{} = {}\n{} = {}\n",
+            THEME_KEY,
+            self.theme.name,
+            MODE_KEY,
+            self.mode.name,
+            PLAYERS_KEY,
+            self.players.name,
+            DIFFICULTY_KEY,
+            self.difficulty.name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::{BOUNCE_MODE, CO_OP_SEPARATE_MODE, SINGLE_PLAYER_MODE, STANDARD_MODE},
+        systems::HARD_DIFFICULTY,
+        user_display::{CLASSIC_THEME, COLORBLIND_THEME},
+    };
+
+    use super::Settings;
+
+    #[test]
+    fn it_parses_the_settings_file() {
+        let settings = Settings::parse(
+            "# Comment\nvolume = 10\ntheme = colorblind\nmode = bounce\nplayers = co_op_separate\ndifficulty = hard\n",
+        );
+
+        assert_eq!(settings.theme, COLORBLIND_THEME);
+        assert_eq!(settings.mode, BOUNCE_MODE);
+        assert_eq!(settings.players, CO_OP_SEPARATE_MODE);
+        assert_eq!(settings.difficulty, HARD_DIFFICULTY);
+
+        // Invalid values keep the default
+        assert_eq!(Settings::parse("theme = neon").theme, CLASSIC_THEME);
+        assert_eq!(Settings::parse("mode = zen").mode, STANDARD_MODE);
+        assert_eq!(Settings::parse("players = 3").players, SINGLE_PLAYER_MODE);
+    }
+
+    #[test]
+    fn it_saves_settings_that_can_be_parsed() {
+        let settings = Settings {
+            theme: COLORBLIND_THEME,
+            mode: BOUNCE_MODE,
+            players: CO_OP_SEPARATE_MODE,
+            difficulty: HARD_DIFFICULTY,
+        };
+
+        assert_eq!(Settings::parse(&settings.get_file_contents()), settings);
+    }
+}
@@ -0,0 +1,69 @@
+use std::io::stdout;
+
+use crossterm::event::{Event, KeyCode};
+
+use crate::{
+    api::display::{ColorMode, Layout, Output, Point},
+    helpers::get_keyboard_events,
+    network::SpectatorClient,
+};
+
+use super::app_errors::AppResult;
+
+const SPECTATOR_LOOP_DELAY: u64 = 25;
+
+/// Watches a game served to spectators in another terminal, every key except ESC is ignored
+pub struct SpectatorManager {
+    dimensions: Point<i64>,
+    output: Output,
+    client: SpectatorClient,
+}
+
+impl SpectatorManager {
+    pub fn new(dimensions: Point<i64>, color_mode: ColorMode, client: SpectatorClient) -> Self {
+        SpectatorManager {
+            dimensions,
+            output: Output::new(stdout(), color_mode),
+            client,
+        }
+    }
+
+    /// Runs until ESC is pressed or the game closes the connection
+    pub fn run(&mut self) -> AppResult<()> {
+        self.output.start()?;
+
+        let result = self.run_spectator();
+
+        self.output.close()?;
+
+        result
+    }
+
+    fn run_spectator(&mut self) -> AppResult<()> {
+        // The frames are drawn onto a layout the size of this terminal, which may differ from the terminal of the game
+        let mut layout = Layout::new(&self.dimensions, None);
+        let mut frame_dimensions = None;
+
+        loop {
+            let events = get_keyboard_events(SPECTATOR_LOOP_DELAY)?;
+
+            if events.contains(&Event::Key(KeyCode::Esc.into())) {
+                return Ok(());
+            }
+
+            let updates = self.client.receive()?;
+
+            if updates.is_empty() {
+                continue;
+            }
+
+            for update in updates {
+                update.apply(&mut layout, frame_dimensions);
+
+                frame_dimensions = Some(update.dimensions);
+            }
+
+            self.output.print_display(&layout)?;
+        }
+    }
+}
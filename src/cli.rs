@@ -0,0 +1,464 @@
+use std::path::PathBuf;
+
+use games::{
+    api::display::{ColorMode, Point, MINIMUM_SCREEN_WIDTH},
+    app::{
+        GameMode, GameOptions, HeadlessInput, PlayerMode, Settings, StatsFormat, GAME_MODES,
+        MAX_LIVES, PLAYER_MODES,
+    },
+    entities::{AutopilotKind, AUTOPILOTS},
+    systems::{Difficulty, DIFFICULTIES},
+    user_display::{Theme, THEMES},
+};
+
+/// The HUD and the ship's starting position need about this many rows
+pub const MINIMUM_SCREEN_HEIGHT: i64 = 30;
+/// The size of a headless game when --size isn't passed
+pub const DEFAULT_HEADLESS_SIZE: Point<i64> = Point {
+    width: 120,
+    height: 40,
+};
+const MAX_FPS: u64 = 60;
+const MAX_GAMES: usize = 1_000_000;
+/// An hour, in seconds
+const MAX_ATTRACT_DELAY: u64 = 3_600;
+
+pub const HELP: &str = "Rusty Asteroids
+
+Usage: games [options]
+
+Options:
+  -h, --help                Show this help
+  --difficulty <name>       easy, normal or hard
+  --mode <name>             standard, bounce or classic
+  --players <name>          single, co_op_shared or co_op_separate
+  --theme <name>            classic, colorblind, high_contrast or monochrome
+  --color <mode>            truecolor, 256, 16 or mono, detected from the terminal by default
+  --seed <number>           Seeds the asteroids so every game plays out the same way
+  --lives <number>          Starting lives of each ship, 1 to 9
+  --size <width>x<height>   Plays on a fixed area instead of the whole terminal
+  --fps <number>            Frames per second, 1 to 60
+  --record <file>           Saves the input of each game to the file
+  --replay <file>           Plays back a recorded game
+  --attract <seconds>       Plays a demo after the title or game over screen is idle this long, 0 never plays it
+  --headless                Runs games without a terminal and prints their stats
+  --games <number>          Headless games to run, each seeded with the next number
+  --ticks <number>          Frames each headless game runs for at most
+  --bot <name>              Flies the ships of the headless games with an autopilot: random or dodger
+  --script <file>           Plays the headless games with the actions listed on each line of the file
+  --stats <format>          Prints the headless stats as json or csv
+  --host <address>          Hosts a game for a second player to join
+  --connect <address>       Joins a hosted game
+  --spectators <address>    Lets spectators watch the game
+  --spectate <address>      Watches a game
+
+The difficulty, mode, players and theme override the settings file for this session.";
+
+/// The arguments the game was started with
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CommandLine {
+    pub help: bool,
+    pub color_mode: Option<ColorMode>,
+    pub difficulty: Option<Difficulty>,
+    pub mode: Option<GameMode>,
+    pub players: Option<PlayerMode>,
+    pub theme: Option<Theme>,
+    pub size: Option<Point<i64>>,
+    pub options: GameOptions,
+    pub headless: bool,
+    pub games: Option<usize>,
+    pub ticks: Option<u64>,
+    pub bot: Option<HeadlessInput>,
+    pub script: Option<PathBuf>,
+    pub stats_format: Option<StatsFormat>,
+    pub host: Option<String>,
+    pub connect: Option<String>,
+    pub spectators: Option<String>,
+    pub spectate: Option<String>,
+}
+
+impl CommandLine {
+    /// The settings file with the settings passed on the command line, which aren't saved
+    pub fn get_settings(&self, settings: Settings) -> Settings {
+        Settings {
+            difficulty: self.difficulty.unwrap_or(settings.difficulty),
+            mode: self.mode.unwrap_or(settings.mode),
+            players: self.players.unwrap_or(settings.players),
+            theme: self.theme.unwrap_or(settings.theme),
+        }
+    }
+}
+
+fn get_names<T>(values: &[T], get_name: impl Fn(&T) -> &str) -> String {
+    values
+        .iter()
+        .map(get_name)
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+fn parse_number<T: std::str::FromStr + PartialOrd + std::fmt::Display>(
+    name: &str,
+    value: &str,
+    min: T,
+    max: T,
+) -> Result<T, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|number| *number >= min && *number <= max)
+        .ok_or(format!(
+            "Expected {} to be a number from {} to {}, got `{}`",
+            name, min, max, value
+        ))
+}
+
+fn parse_size(value: &str) -> Result<Point<i64>, String> {
+    let invalid = || {
+        format!(
+            "Expected --size to be <width>x<height> of at least {}x{}, got `{}`",
+            MINIMUM_SCREEN_WIDTH, MINIMUM_SCREEN_HEIGHT, value
+        )
+    };
+
+    let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+    let size = Point::new(
+        width.parse().map_err(|_| invalid())?,
+        height.parse().map_err(|_| invalid())?,
+    );
+
+    if size.width < MINIMUM_SCREEN_WIDTH as i64 || size.height < MINIMUM_SCREEN_HEIGHT {
+        return Err(invalid());
+    }
+
+    Ok(size)
+}
+
+/// Parses the arguments, without the program name. Values are passed as `--name value` or `--name=value`.
+pub fn parse_arguments(args: impl IntoIterator<Item = String>) -> Result<CommandLine, String> {
+    let mut command_line = CommandLine::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+
+        match name.as_str() {
+            "-h" | "--help" => {
+                command_line.help = true;
+
+                continue;
+            }
+            "--headless" => {
+                command_line.headless = true;
+
+                continue;
+            }
+            _ => {}
+        }
+
+        let mut get_value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or(format!("Expected {} to be followed by a value", name))
+        };
+
+        match name.as_str() {
+            "--difficulty" => {
+                let value = get_value()?;
+
+                command_line.difficulty = Some(Difficulty::from_name(&value).ok_or(format!(
+                    "Expected --difficulty to be one of: {}",
+                    get_names(&DIFFICULTIES, |difficulty| difficulty.name)
+                ))?);
+            }
+            "--mode" => {
+                let value = get_value()?;
+
+                command_line.mode = Some(GameMode::from_name(&value).ok_or(format!(
+                    "Expected --mode to be one of: {}",
+                    get_names(&GAME_MODES, |mode| mode.name)
+                ))?);
+            }
+            "--players" => {
+                let value = get_value()?;
+
+                command_line.players = Some(PlayerMode::from_name(&value).ok_or(format!(
+                    "Expected --players to be one of: {}",
+                    get_names(&PLAYER_MODES, |mode| mode.name)
+                ))?);
+            }
+            "--theme" => {
+                let value = get_value()?;
+
+                command_line.theme = Some(Theme::from_name(&value).ok_or(format!(
+                    "Expected --theme to be one of: {}",
+                    get_names(&THEMES, |theme| theme.name)
+                ))?);
+            }
+            "--color" => {
+                command_line.color_mode = Some(
+                    ColorMode::parse(&get_value()?)
+                        .ok_or("Expected --color to be one of: truecolor, 256, 16, mono")?,
+                );
+            }
+            "--seed" => {
+                let value = get_value()?;
+
+                command_line.options.seed = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Expected --seed to be a number, got `{}`", value))?,
+                );
+            }
+            "--lives" => {
+                command_line.options.lives = parse_number(&name, &get_value()?, 1, MAX_LIVES)?;
+            }
+            "--fps" => {
+                command_line.options.frame_delay =
+                    1000 / parse_number(&name, &get_value()?, 1, MAX_FPS)?;
+            }
+            "--attract" => {
+                command_line.options.attract_delay =
+                    Some(parse_number(&name, &get_value()?, 0, MAX_ATTRACT_DELAY)?)
+                        .filter(|delay| *delay > 0);
+            }
+            "--size" => command_line.size = Some(parse_size(&get_value()?)?),
+            "--record" => command_line.options.record = Some(PathBuf::from(get_value()?)),
+            "--replay" => command_line.options.replay = Some(PathBuf::from(get_value()?)),
+            "--games" => {
+                command_line.games = Some(parse_number(&name, &get_value()?, 1, MAX_GAMES)?)
+            }
+            "--ticks" => {
+                command_line.ticks = Some(parse_number(&name, &get_value()?, 1, u64::MAX)?)
+            }
+            "--bot" => {
+                let value = get_value()?;
+
+                command_line.bot = Some(HeadlessInput::Autopilot(
+                    AutopilotKind::from_name(&value).ok_or(format!(
+                        "Expected --bot to be one of: {}",
+                        get_names(&AUTOPILOTS, |kind| kind.name)
+                    ))?,
+                ));
+            }
+            "--script" => command_line.script = Some(PathBuf::from(get_value()?)),
+            "--stats" => {
+                command_line.stats_format = Some(
+                    StatsFormat::from_name(&get_value()?)
+                        .ok_or("Expected --stats to be one of: json, csv")?,
+                );
+            }
+            "--host" => command_line.host = Some(get_value()?),
+            "--connect" => command_line.connect = Some(get_value()?),
+            "--spectators" => command_line.spectators = Some(get_value()?),
+            "--spectate" => command_line.spectate = Some(get_value()?),
+            _ => return Err(format!("Unknown argument `{}`, see --help", name)),
+        }
+    }
+
+    let conflicts = [
+        (
+            "--record",
+            command_line.options.record.is_some(),
+            "--replay",
+            command_line.options.replay.is_some(),
+        ),
+        (
+            "--host",
+            command_line.host.is_some(),
+            "--connect",
+            command_line.connect.is_some(),
+        ),
+        (
+            "--connect",
+            command_line.connect.is_some(),
+            "--spectate",
+            command_line.spectate.is_some(),
+        ),
+        (
+            "--headless",
+            command_line.headless,
+            "--connect",
+            command_line.connect.is_some(),
+        ),
+        (
+            "--headless",
+            command_line.headless,
+            "--spectate",
+            command_line.spectate.is_some(),
+        ),
+        (
+            "--headless",
+            command_line.headless,
+            "--host",
+            command_line.host.is_some(),
+        ),
+        (
+            "--headless",
+            command_line.headless,
+            "--spectators",
+            command_line.spectators.is_some(),
+        ),
+        (
+            "--bot",
+            command_line.bot.is_some(),
+            "--script",
+            command_line.script.is_some(),
+        ),
+        (
+            "--replay",
+            command_line.options.replay.is_some(),
+            "--bot",
+            command_line.bot.is_some(),
+        ),
+        (
+            "--replay",
+            command_line.options.replay.is_some(),
+            "--script",
+            command_line.script.is_some(),
+        ),
+    ];
+
+    if let Some((first, _, second, _)) = conflicts
+        .iter()
+        .find(|(_, first_passed, _, second_passed)| *first_passed && *second_passed)
+    {
+        return Err(format!("{} can't be used with {}", first, second));
+    }
+
+    let headless_arguments = [
+        ("--games", command_line.games.is_some()),
+        ("--ticks", command_line.ticks.is_some()),
+        ("--bot", command_line.bot.is_some()),
+        ("--script", command_line.script.is_some()),
+        ("--stats", command_line.stats_format.is_some()),
+    ];
+
+    if let Some((name, _)) = headless_arguments
+        .iter()
+        .find(|(_, passed)| *passed && !command_line.headless)
+    {
+        return Err(format!("{} can only be used with --headless", name));
+    }
+
+    // Every game of a batch would be saved to the same file, leaving only the last one
+    if command_line.options.record.is_some() && command_line.games.is_some_and(|games| games > 1) {
+        return Err(
+            "--record only saves one game, it can't be used with more than one --games".to_string(),
+        );
+    }
+
+    Ok(command_line)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use games::{
+        api::display::{ColorMode, Point},
+        app::{HeadlessInput, Settings, StatsFormat, BOUNCE_MODE, CO_OP_SHARED_MODE},
+        entities::RANDOM_AUTOPILOT,
+        systems::HARD_DIFFICULTY,
+    };
+
+    use super::parse_arguments;
+
+    fn parse(args: &[&str]) -> Result<super::CommandLine, String> {
+        parse_arguments(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn it_parses_arguments() {
+        let command_line = parse(&[
+            "--difficulty",
+            "hard",
+            "--mode=bounce",
+            "--players",
+            "co-op-shared",
+            "--color",
+            "256",
+            "--seed",
+            "42",
+            "--lives=5",
+            "--size",
+            "120x40",
+            "--fps",
+            "20",
+            "--attract",
+            "0",
+            "--record",
+            "game.replay",
+            "--headless",
+            "--games=1",
+            "--bot",
+            "random",
+            "--stats",
+            "csv",
+        ])
+        .unwrap();
+
+        assert_eq!(command_line.color_mode, Some(ColorMode::Ansi256));
+        assert_eq!(command_line.size, Some(Point::new(120, 40)));
+        assert_eq!(command_line.options.seed, Some(42));
+        assert_eq!(command_line.options.lives, 5);
+        assert_eq!(command_line.options.frame_delay, 50);
+        assert_eq!(command_line.options.attract_delay, None);
+        assert_eq!(
+            command_line.options.record,
+            Some(PathBuf::from("game.replay"))
+        );
+        assert!(command_line.headless);
+        assert_eq!(command_line.games, Some(1));
+        assert_eq!(
+            command_line.bot,
+            Some(HeadlessInput::Autopilot(RANDOM_AUTOPILOT))
+        );
+        assert_eq!(command_line.stats_format, Some(StatsFormat::Csv));
+
+        let settings = command_line.get_settings(Settings::default());
+
+        assert_eq!(settings.difficulty, HARD_DIFFICULTY);
+        assert_eq!(settings.mode, BOUNCE_MODE);
+        assert_eq!(settings.players, CO_OP_SHARED_MODE);
+    }
+
+    #[test]
+    fn it_reports_invalid_arguments() {
+        assert!(parse(&["--difficulty", "impossible"])
+            .unwrap_err()
+            .contains("easy, normal, hard"));
+        assert_eq!(
+            parse(&["--lives", "0"]).unwrap_err(),
+            "Expected --lives to be a number from 1 to 9, got `0`"
+        );
+        assert!(parse(&["--size", "20x10"]).is_err());
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--fast"]).unwrap_err().contains("--fast"));
+        assert_eq!(
+            parse(&["--record", "a", "--replay", "b"]).unwrap_err(),
+            "--record can't be used with --replay"
+        );
+        assert_eq!(
+            parse(&["--headless", "--host", "127.0.0.1:7878"]).unwrap_err(),
+            "--headless can't be used with --host"
+        );
+        assert_eq!(
+            parse(&["--headless", "--spectators", "127.0.0.1:7879"]).unwrap_err(),
+            "--headless can't be used with --spectators"
+        );
+        assert_eq!(
+            parse(&["--games", "10"]).unwrap_err(),
+            "--games can only be used with --headless"
+        );
+        assert_eq!(
+            parse(&["--headless", "--games", "10", "--record", "a"]).unwrap_err(),
+            "--record only saves one game, it can't be used with more than one --games"
+        );
+        assert!(parse(&["--headless", "--games", "1", "--record", "a"]).is_ok());
+    }
+}
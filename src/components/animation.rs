@@ -0,0 +1,188 @@
+use crossterm::style::Color;
+
+use crate::api::display::Layout;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AnimationFrame {
+    pub layout: Layout,
+    /// How long the frame is shown for in milliseconds, a duration of 0 holds the frame indefinitely
+    pub duration: u128,
+}
+
+impl AnimationFrame {
+    pub fn new(layout: Layout, duration: u128) -> Self {
+        Self { layout, duration }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Animation {
+    frames: Vec<AnimationFrame>,
+    current_frame: usize,
+    // Same approach as the asteroid spawn timer, the game_loop_duration is accumulated each loop rather than fetching the system time
+    time_elapsed_in_frame: u128,
+}
+
+impl Animation {
+    /// Creates a new animation that loops over the provided frames
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - The frames to cycle through, there must be at least one frame
+    pub fn new(frames: Vec<AnimationFrame>) -> Self {
+        assert!(
+            !frames.is_empty(),
+            "An animation requires at least one frame"
+        );
+
+        Self {
+            frames,
+            current_frame: 0,
+            time_elapsed_in_frame: 0,
+        }
+    }
+
+    /// Creates an animation from ascii art frames that are all shown for the same duration
+    pub fn from_ascii_frames(ascii_frames: &[&str], color: Color, frame_duration: u128) -> Self {
+        Animation::new(
+            ascii_frames
+                .iter()
+                .map(|ascii| AnimationFrame::new(Layout::from_ascii(ascii, color), frame_duration))
+                .collect(),
+        )
+    }
+
+    /// Sets the foreground color of every frame
+    pub fn set_foreground(&mut self, color: Color) -> &mut Self {
+        for frame in self.frames.iter_mut() {
+            frame.layout.set_foreground(color);
+        }
+
+        self
+    }
+
+    /// The time it takes to play every frame once, a frame with no duration holds the animation so it never finishes
+    pub fn get_total_duration(&self) -> Option<u128> {
+        self.frames
+            .iter()
+            .map(|frame| (frame.duration != 0).then_some(frame.duration))
+            .sum()
+    }
+
+    /// Jumps to a frame, used for sprites where each frame is a pose rather than a step of an animation
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The frame to show, wraps around if it is past the last frame
+    pub fn set_current_frame(&mut self, index: usize) -> &mut Self {
+        self.current_frame = index % self.frames.len();
+        self.time_elapsed_in_frame = 0;
+
+        self
+    }
+
+    pub fn get_current_layout(&self) -> &Layout {
+        &self.frames[self.current_frame].layout
+    }
+
+    /// Moves the animation forward by the game_loop_duration, skipping over multiple frames if the duration is longer than the frames.
+    ///
+    /// # Returns
+    ///
+    /// * True if the current frame changed
+    pub fn advance(&mut self, game_loop_duration: u128) -> bool {
+        if self.frames.len() < 2 {
+            return false;
+        }
+
+        let starting_frame = self.current_frame;
+
+        self.time_elapsed_in_frame += game_loop_duration;
+
+        loop {
+            let duration = self.frames[self.current_frame].duration;
+
+            if duration == 0 || self.time_elapsed_in_frame < duration {
+                break;
+            }
+
+            self.time_elapsed_in_frame -= duration;
+            self.current_frame = (self.current_frame + 1) % self.frames.len();
+        }
+
+        starting_frame != self.current_frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::api::display::Layout;
+
+    use super::{Animation, AnimationFrame};
+
+    /// Each mock frame is a single character, so the character identifies the frame
+    fn get_current_frame(animation: &Animation) -> char {
+        animation.get_current_layout().map[0][0].unwrap().value
+    }
+
+    fn get_animation_mock() -> Animation {
+        Animation::new(vec![
+            AnimationFrame::new(Layout::from_ascii("a", Color::White), 100),
+            AnimationFrame::new(Layout::from_ascii("b", Color::White), 50),
+            AnimationFrame::new(Layout::from_ascii("c", Color::White), 200),
+        ])
+    }
+
+    #[test]
+    fn it_stays_on_a_frame_until_its_duration_has_elapsed() {
+        let mut animation = get_animation_mock();
+
+        assert!(!animation.advance(99));
+        assert_eq!(get_current_frame(&animation), 'a');
+
+        assert!(animation.advance(1));
+        assert_eq!(get_current_frame(&animation), 'b');
+    }
+
+    #[test]
+    fn it_uses_per_frame_durations_and_loops_back_to_the_first_frame() {
+        let mut animation = get_animation_mock();
+
+        animation.advance(100);
+        animation.advance(50);
+        assert_eq!(get_current_frame(&animation), 'c');
+
+        animation.advance(200);
+        assert_eq!(
+            animation.get_current_layout(),
+            &Layout::from_ascii("a", Color::White)
+        );
+    }
+
+    #[test]
+    fn it_skips_frames_when_the_game_loop_is_longer_than_a_frame() {
+        let mut animation = get_animation_mock();
+
+        // 100 + 50 covers the first two frames, leaving 10ms in the third
+        animation.advance(160);
+
+        assert_eq!(get_current_frame(&animation), 'c');
+
+        // Only 190ms remain for the third frame
+        assert!(animation.advance(190));
+        assert_eq!(get_current_frame(&animation), 'a');
+    }
+
+    #[test]
+    fn it_holds_frames_with_no_duration() {
+        let mut animation = Animation::new(vec![
+            AnimationFrame::new(Layout::from_ascii("a", Color::White), 0),
+            AnimationFrame::new(Layout::from_ascii("b", Color::White), 10),
+        ]);
+
+        assert!(!animation.advance(1000));
+        assert_eq!(get_current_frame(&animation), 'a');
+    }
+}
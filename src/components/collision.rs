@@ -0,0 +1,124 @@
+use super::DrawableType;
+
+/// The layer a drawable sits on for collision detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CollisionLayer {
+    Player,
+    Enemy,
+    Ammunition,
+    Border,
+    Effect,
+    Pickup,
+    /// The parts of a boss, which survive ramming the player
+    Boss,
+}
+
+impl CollisionLayer {
+    const fn get_bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// The set of layers a drawable collides with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CollisionMask(u32);
+
+impl CollisionMask {
+    pub const fn from_layers(layers: &[CollisionLayer]) -> Self {
+        let mut bits = 0;
+        let mut index = 0;
+
+        // Iterators can't be used in const functions
+        while index < layers.len() {
+            bits |= layers[index].get_bit();
+            index += 1;
+        }
+
+        CollisionMask(bits)
+    }
+
+    pub fn contains(&self, layer: CollisionLayer) -> bool {
+        self.0 & layer.get_bit() != 0
+    }
+}
+
+const PLAYER_MASK: CollisionMask = CollisionMask::from_layers(&[
+    CollisionLayer::Enemy,
+    CollisionLayer::Pickup,
+    CollisionLayer::Boss,
+]);
+// Enemies only collide with each other when the interaction table has a rule for it, e.g. when asteroids bounce
+const ENEMY_MASK: CollisionMask = CollisionMask::from_layers(&[
+    CollisionLayer::Player,
+    CollisionLayer::Ammunition,
+    CollisionLayer::Enemy,
+]);
+const AMMUNITION_MASK: CollisionMask =
+    CollisionMask::from_layers(&[CollisionLayer::Enemy, CollisionLayer::Boss]);
+const PICKUP_MASK: CollisionMask = CollisionMask::from_layers(&[CollisionLayer::Player]);
+const BOSS_MASK: CollisionMask =
+    CollisionMask::from_layers(&[CollisionLayer::Player, CollisionLayer::Ammunition]);
+
+impl DrawableType {
+    pub fn get_collision_layer(&self) -> CollisionLayer {
+        match self {
+            DrawableType::Player => CollisionLayer::Player,
+            DrawableType::Enemy(..) => CollisionLayer::Enemy,
+            DrawableType::Ammunition(..) => CollisionLayer::Ammunition,
+            DrawableType::Border => CollisionLayer::Border,
+            DrawableType::Effect => CollisionLayer::Effect,
+            DrawableType::Pickup => CollisionLayer::Pickup,
+            DrawableType::Boss(..) => CollisionLayer::Boss,
+        }
+    }
+
+    pub fn get_collision_mask(&self) -> CollisionMask {
+        match self {
+            DrawableType::Player => PLAYER_MASK,
+            DrawableType::Enemy(..) => ENEMY_MASK,
+            DrawableType::Ammunition(..) => AMMUNITION_MASK,
+            DrawableType::Pickup => PICKUP_MASK,
+            DrawableType::Boss(..) => BOSS_MASK,
+            DrawableType::Border | DrawableType::Effect => CollisionMask::default(),
+        }
+    }
+
+    /// The damage the drawable deals to the drawables it collides with
+    pub fn get_damage(&self) -> u32 {
+        match self {
+            DrawableType::Enemy(damage)
+            | DrawableType::Ammunition(damage, _)
+            | DrawableType::Boss(damage) => *damage,
+            DrawableType::Player
+            | DrawableType::Border
+            | DrawableType::Effect
+            | DrawableType::Pickup => 0,
+        }
+    }
+
+    /// The points awarded when the drawable destroys another drawable
+    pub fn get_points(&self) -> u64 {
+        match self {
+            DrawableType::Ammunition(_, points) => *points as u64,
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::DrawableType;
+
+    use super::CollisionLayer;
+
+    #[test]
+    fn it_only_collides_with_the_layers_in_the_mask() {
+        let player_mask = DrawableType::Player.get_collision_mask();
+
+        assert!(player_mask.contains(CollisionLayer::Enemy));
+        assert!(!player_mask.contains(CollisionLayer::Ammunition));
+        assert!(!DrawableType::Border
+            .get_collision_mask()
+            .contains(CollisionLayer::Enemy));
+    }
+}
@@ -1,9 +1,13 @@
 use crate::api::display::{Layout, Point};
+
 use uuid::Uuid;
 
 #[derive(Debug, PartialEq)]
 pub struct DrawableState {
+    /// The layout currently being displayed, for entities in the world this is the current frame of their sprite
     pub layout: Layout,
+    /// The point within the layout used to line the drawable up with other drawables
+    pub anchor: Point<i64>,
     pub location: Point<i64>,
     pub velocity: Point<i64>,
     pub drawable_type: DrawableType,
@@ -18,6 +22,12 @@ pub enum DrawableType {
     Border,
     // Damage of ammunition
     Ammunition(u32, u32),
+    // Visual effects such as explosions, these never collide
+    Effect,
+    // Collected by the player, such as extra lives
+    Pickup,
+    // Damage of the boss part
+    Boss(u32),
 }
 
 impl DrawableState {
@@ -29,19 +39,40 @@ impl DrawableState {
     ) -> Self {
         Self {
             layout,
+            anchor: Point::default(),
             location,
             drawable_type,
-            velocity: velocity.unwrap_or(Default::default()),
+            velocity: velocity.unwrap_or_default(),
             uuid: Uuid::new_v4(),
         }
     }
 }
 
+/// Wraps a location around the edges of the dimensions. A drawable only wraps once it has fully left the screen, so it slides off one edge
+/// before appearing on the opposite edge.
+///
+/// # Arguments
+///
+/// * `location` - The top left of the drawable
+/// * `size` - The dimensions of the drawable's layout
+/// * `dimensions` - The dimensions of the area the drawable wraps around
+pub fn get_wrapped_location(
+    location: Point<i64>,
+    size: Point<i64>,
+    dimensions: &Point<i64>,
+) -> Point<i64> {
+    Point {
+        width: (location.width + size.width).rem_euclid(dimensions.width + size.width) - size.width,
+        height: (location.height + size.height).rem_euclid(dimensions.height + size.height)
+            - size.height,
+    }
+}
+
 const PADDING_OFFSET: i64 = 1;
 
 pub fn get_rated_velocity(velocity: Point<i64>, game_loop_duration: u128) -> Point<i64> {
     // RUST IS SO CLEAN, this seamless cast from i64 to point with .into();
-    let rated_velocity = velocity * game_loop_duration.into() / (1000 as i64).into();
+    let rated_velocity = velocity * game_loop_duration.into() / 1000_i64.into();
 
     // Use a minimum velocity of 1 if we round to 0
     fn get_safe_rated_value(rated_value: i64, provided_value: i64) -> i64 {
@@ -62,54 +93,66 @@ pub fn get_rated_velocity(velocity: Point<i64>, game_loop_duration: u128) -> Poi
     }
 }
 
-pub trait Drawable {
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self;
-
-    fn get_drawable_state(&self) -> &DrawableState;
-
-    fn update_position(
-        &mut self,
-        dimensions: Option<&Point<i64>>,
-        game_loop_duration: u128,
-    ) -> &mut Self {
-        let drawable_state = self.get_drawable_state();
-
-        // Convert location to i64 so we can handle an underflow of the position; if the position goes below (0,0)
-        let converted_location: Point<i64> = drawable_state.location.into();
+/// Blocks a drawable from moving outside the dimensions, leaving a padding for the borders
+///
+/// # Arguments
+///
+/// * `location` - The top left of the drawable
+/// * `size` - The dimensions of the drawable's layout
+/// * `dimensions` - The dimensions of the area the drawable is kept in
+pub fn get_clamped_location(
+    location: Point<i64>,
+    size: Point<i64>,
+    dimensions: &Point<i64>,
+) -> Point<i64> {
+    let max_height = dimensions.height - size.height - PADDING_OFFSET;
+    let max_width = dimensions.width - size.width - PADDING_OFFSET;
 
-        let mut updated_position: Point<i64> =
-            converted_location + get_rated_velocity(drawable_state.velocity, game_loop_duration);
-
-        // Block the player from moving outside the boundary
-        if drawable_state.drawable_type == DrawableType::Player {
-            if dimensions.is_none() {
-                panic!("Missing required dimensions for rendering player");
-            }
-
-            if updated_position.height < PADDING_OFFSET {
-                updated_position.height = PADDING_OFFSET;
-            }
+    Point {
+        width: location.width.max(PADDING_OFFSET).min(max_width),
+        height: location.height.max(PADDING_OFFSET).min(max_height),
+    }
+}
 
-            if updated_position.width < PADDING_OFFSET {
-                updated_position.width = PADDING_OFFSET;
-            }
+pub trait Drawable {
+    fn get_drawable_state(&self) -> &DrawableState;
+}
 
-            let dimensions = dimensions.unwrap();
+#[cfg(test)]
+mod tests {
+    use crate::api::display::Point;
 
-            let drawable_dimensions = drawable_state.layout.dimensions;
+    use super::get_wrapped_location;
 
-            let max_height = dimensions.height - drawable_dimensions.height - PADDING_OFFSET;
-            let max_width = dimensions.width - drawable_dimensions.width - PADDING_OFFSET;
+    const DIMENSIONS: &Point<i64> = &Point::new(20, 10);
+    const SIZE: Point<i64> = Point::new(3, 2);
 
-            if updated_position.height > max_height {
-                updated_position.height = max_height;
-            }
+    #[test]
+    fn it_keeps_locations_inside_the_dimensions() {
+        assert_eq!(
+            get_wrapped_location(Point::new(5, 5), SIZE, DIMENSIONS),
+            Point::new(5, 5)
+        );
 
-            if updated_position.width > max_width {
-                updated_position.width = max_width;
-            }
-        }
+        // Partially off screen drawables are not wrapped yet
+        assert_eq!(
+            get_wrapped_location(Point::new(-2, 9), SIZE, DIMENSIONS),
+            Point::new(-2, 9)
+        );
+    }
 
-        self.set_position(updated_position.into())
+    #[test]
+    fn it_wraps_drawables_that_have_fully_left_the_dimensions() {
+        // Leaving the right and bottom edges re-enters from the left and top edges
+        assert_eq!(
+            get_wrapped_location(Point::new(20, 10), SIZE, DIMENSIONS),
+            Point::new(-3, -2)
+        );
+
+        // Leaving the left and top edges re-enters from the right and bottom edges
+        assert_eq!(
+            get_wrapped_location(Point::new(-4, -3), SIZE, DIMENSIONS),
+            Point::new(19, 9)
+        );
     }
 }
@@ -0,0 +1,85 @@
+/// The eight directions a free flying ship can face, in clockwise order starting from up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Heading {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+const HEADINGS: [Heading; 8] = [
+    Heading::North,
+    Heading::NorthEast,
+    Heading::East,
+    Heading::SouthEast,
+    Heading::South,
+    Heading::SouthWest,
+    Heading::West,
+    Heading::NorthWest,
+];
+
+/// Terminal cells are roughly twice as tall as they are wide, so vertical speeds are scaled down to look the same as horizontal speeds
+const HEIGHT_SPEED_RATIO: f64 = 0.6;
+
+impl Heading {
+    /// The position of the heading in clockwise order, also used as the frame of the ship sprite
+    pub fn get_index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        HEADINGS[(self.get_index() + 1) % HEADINGS.len()]
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        HEADINGS[(self.get_index() + HEADINGS.len() - 1) % HEADINGS.len()]
+    }
+
+    /// The unit vector of the heading as (width, height), height increases downwards
+    pub fn get_unit_vector(&self) -> (f64, f64) {
+        let diagonal = std::f64::consts::FRAC_1_SQRT_2;
+
+        match self {
+            Heading::North => (0.0, -1.0),
+            Heading::NorthEast => (diagonal, -diagonal),
+            Heading::East => (1.0, 0.0),
+            Heading::SouthEast => (diagonal, diagonal),
+            Heading::South => (0.0, 1.0),
+            Heading::SouthWest => (-diagonal, diagonal),
+            Heading::West => (-1.0, 0.0),
+            Heading::NorthWest => (-diagonal, -diagonal),
+        }
+    }
+
+    /// Returns the velocity of moving at the speed in this heading, in cells per second
+    pub fn get_velocity(&self, speed: f64) -> (f64, f64) {
+        let (width, height) = self.get_unit_vector();
+
+        (width * speed, height * speed * HEIGHT_SPEED_RATIO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Heading;
+
+    #[test]
+    fn it_rotates_through_all_eight_headings() {
+        let mut heading = Heading::North;
+
+        for _ in 0..8 {
+            heading = heading.rotate_clockwise();
+        }
+
+        assert_eq!(heading, Heading::North);
+        assert_eq!(
+            Heading::North.rotate_counter_clockwise(),
+            Heading::NorthWest
+        );
+        assert_eq!(Heading::West.rotate_clockwise(), Heading::NorthWest);
+    }
+}
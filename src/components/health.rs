@@ -1,7 +1,3 @@
-struct HasHealth {
-    health: u32,
-}
-
 pub fn get_updated_health(mut health: u32, damage: u32) -> u32 {
     if health < damage {
         health = 0;
@@ -11,9 +7,3 @@ pub fn get_updated_health(mut health: u32, damage: u32) -> u32 {
 
     health
 }
-
-pub trait Health {
-    fn get_health(&self) -> u32;
-
-    fn apply_damage(&mut self, damage: u32) -> &mut Self;
-}
@@ -0,0 +1,48 @@
+use crate::{api::display::Point, world::Entity};
+
+/// A follow-up entity to spawn after a lifecycle hook, the commands are carried out by the spawn command system once the current
+/// system has finished with the world
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpawnCommand {
+    /// An explosion centered on the position
+    Explosion(Point<i64>),
+}
+
+/// A snapshot of the entity a lifecycle hook is invoked for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LifecycleContext {
+    pub entity: Entity,
+    pub location: Point<i64>,
+    pub velocity: Point<i64>,
+    /// The dimensions of the entity's current sprite frame
+    pub size: Point<i64>,
+}
+
+impl LifecycleContext {
+    pub fn get_center(&self) -> Point<i64> {
+        self.location + self.size / 2_i64.into()
+    }
+}
+
+/// Callbacks invoked by the world as an entity goes through its life, each hook can return entities to spawn in response
+pub trait Lifecycle {
+    /// Invoked once the entity has been spawned with all of its components
+    fn on_spawn(&self, _context: &LifecycleContext) -> Vec<SpawnCommand> {
+        vec![]
+    }
+
+    /// Invoked whenever the entity takes damage, including the damage that destroys it
+    fn on_damage(&self, _context: &LifecycleContext, _damage: u32) -> Vec<SpawnCommand> {
+        vec![]
+    }
+
+    /// Invoked when the entity runs out of health, before it is despawned
+    fn on_destroy(&self, _context: &LifecycleContext) -> Vec<SpawnCommand> {
+        vec![]
+    }
+
+    /// Invoked when the entity has left the play field, before it is despawned
+    fn on_offscreen(&self, _context: &LifecycleContext) -> Vec<SpawnCommand> {
+        vec![]
+    }
+}
@@ -1,7 +1,15 @@
+mod animation;
+mod collision;
 mod drawable;
+mod heading;
 mod health;
-mod spawnable;
+mod lifecycle;
+mod sprite;
 
+pub use animation::*;
+pub use collision::*;
 pub use drawable::*;
+pub use heading::*;
 pub use health::*;
-pub use spawnable::*;
+pub use lifecycle::*;
+pub use sprite::*;
@@ -0,0 +1,44 @@
+use crossterm::style::Color;
+
+use crate::api::display::{Layout, Point};
+
+use super::Animation;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sprite {
+    pub animation: Animation,
+    /// The point within the sprite that is used to line it up with other sprites, e.g. the nose of the spaceship that bullets are fired from
+    pub anchor: Point<i64>,
+}
+
+impl Sprite {
+    pub fn new(animation: Animation, anchor: Point<i64>) -> Self {
+        Self { animation, anchor }
+    }
+
+    /// Creates a sprite from the built-in ascii art constants
+    pub fn from_ascii_frames(
+        ascii_frames: &[&str],
+        color: Color,
+        frame_duration: u128,
+        anchor: Point<i64>,
+    ) -> Self {
+        Sprite::new(
+            Animation::from_ascii_frames(ascii_frames, color, frame_duration),
+            anchor,
+        )
+    }
+
+    /// Recolors the sprite, a color of None keeps the colors the sprite was drawn with
+    pub fn with_color(mut self, color: Option<Color>) -> Self {
+        if let Some(color) = color {
+            self.animation.set_foreground(color);
+        }
+
+        self
+    }
+
+    pub fn get_layout(&self) -> &Layout {
+        self.animation.get_current_layout()
+    }
+}
@@ -1,55 +1,54 @@
 use crossterm::style::Color;
 
 use crate::{
-    api::display::{Layout, Point},
-    components::{get_updated_health, Drawable, DrawableState, DrawableType, Health},
+    api::{assets::load_sprite_or_default, display::Point},
+    components::{CollisionLayer, Lifecycle, LifecycleContext, SpawnCommand, Sprite},
+    user_display::Theme,
+    world::{Entity, World},
 };
 
-use super::consts::ASTEROID;
+use super::consts::ASTEROID_FRAMES;
 
 pub const ASTEROID_DAMAGE: u32 = 1;
 
-pub struct Asteroid {
-    pub drawable: DrawableState,
-    pub health: u32,
-}
-
-impl Asteroid {
-    pub fn new(location: Point<i64>, velocity: Point<i64>) -> Self {
-        let map = Layout::from_ascii(ASTEROID, Color::Yellow);
-
-        Self {
-            drawable: DrawableState::new(
-                map,
-                location,
-                DrawableType::Enemy(ASTEROID_DAMAGE),
-                Some(velocity),
-            ),
-            health: 1,
-        }
-    }
-}
-
-impl Drawable for Asteroid {
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
-        self.drawable.location = updated_position;
+const ASTEROID_SPRITE: &str = "asteroid";
+const ASTEROID_FRAME_DURATION: u128 = 250;
+const ASTEROID_ANCHOR: Point<i64> = Point::new(3, 1);
+const ASTEROID_HEALTH: u32 = 1;
 
-        self
-    }
+/// Asteroids explode when they are destroyed
+struct AsteroidLifecycle;
 
-    fn get_drawable_state(&self) -> &DrawableState {
-        &self.drawable
+impl Lifecycle for AsteroidLifecycle {
+    fn on_destroy(&self, context: &LifecycleContext) -> Vec<SpawnCommand> {
+        vec![SpawnCommand::Explosion(context.get_center())]
     }
 }
 
-impl Health for Asteroid {
-    fn apply_damage(&mut self, damage: u32) -> &mut Self {
-        self.health = get_updated_health(self.health, damage);
-
-        self
-    }
-
-    fn get_health(&self) -> u32 {
-        self.health
-    }
+pub fn spawn_asteroid(
+    world: &mut World,
+    location: Point<i64>,
+    velocity: Point<i64>,
+    theme: &Theme,
+) -> Entity {
+    let sprite = load_sprite_or_default(ASTEROID_SPRITE, || {
+        Sprite::from_ascii_frames(
+            &ASTEROID_FRAMES,
+            Color::Yellow,
+            ASTEROID_FRAME_DURATION,
+            ASTEROID_ANCHOR,
+        )
+    })
+    .with_color(theme.asteroid);
+
+    world
+        .spawn()
+        .with_position(location)
+        .with_velocity(velocity)
+        .with_sprite(sprite)
+        .with_health(ASTEROID_HEALTH)
+        .with_collider(CollisionLayer::Enemy)
+        .with_damage(ASTEROID_DAMAGE)
+        .with_lifecycle(AsteroidLifecycle)
+        .build()
 }
@@ -0,0 +1,461 @@
+use std::fmt::Debug;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    api::display::Point,
+    components::{get_clamped_location, CollisionLayer, Heading},
+    world::{Entity, World},
+};
+
+use super::{
+    bullet::BASIC_BULLET_SPEED,
+    controller::{Action, ACTIONS},
+    player::{Flight, HEIGHT_MAX_VELOCITY, WIDTH_MAX_VELOCITY},
+};
+
+/// The chance the random autopilot presses each action in a frame, as a percentage
+const RANDOM_PRESS_CHANCE: u32 = 20;
+
+/// How far ahead the dodging autopilot looks for collisions, in milliseconds
+const LOOKAHEAD: u128 = 900;
+/// The gap kept around the ship when looking for collisions, in cells
+const SAFETY_MARGIN: i64 = 2;
+/// How far above the bottom of the play field the ship waits for asteroids
+const HOME_HEIGHT: i64 = 4;
+/// How close the spread bullet's targets have to be to the ship's nose, the spread bullet is about this wide
+const SPREAD_WIDTH: i64 = 5;
+/// A free flying ship thrusts away from asteroids that come this close
+const DANGER_DISTANCE: f64 = 12.0;
+
+/// What an autopilot can see of the game when it picks the actions of a frame
+pub struct AutopilotView<'a> {
+    pub world: &'a World,
+    /// The ship the autopilot is flying
+    pub ship: Entity,
+    pub flight: Flight,
+    /// The dimensions of the play field, the area the entities move in
+    pub dimensions: Point<i64>,
+    /// How long the frame is expected to take, in milliseconds
+    pub frame_duration: u128,
+}
+
+/// Something in the world that moves, seen by an autopilot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Body {
+    pub entity: Entity,
+    pub location: Point<i64>,
+    pub size: Point<i64>,
+    /// In cells per second
+    pub velocity: Point<i64>,
+    pub anchor: Point<i64>,
+}
+
+impl Body {
+    /// The location of the body after moving for the time, in milliseconds
+    pub fn get_location_after(&self, time: u128) -> Point<i64> {
+        self.location + self.velocity * (time as i64).into() / 1000_i64.into()
+    }
+
+    pub fn get_center(&self) -> Point<i64> {
+        self.location + self.size / 2_i64.into()
+    }
+
+    /// Whether the bodies overlap when this body is at the location and the other body is at its location
+    fn overlaps(&self, location: Point<i64>, other: &Body, other_location: Point<i64>) -> bool {
+        location.width - SAFETY_MARGIN < other_location.width + other.size.width
+            && other_location.width < location.width + self.size.width + SAFETY_MARGIN
+            && location.height - SAFETY_MARGIN < other_location.height + other.size.height
+            && other_location.height < location.height + self.size.height + SAFETY_MARGIN
+    }
+}
+
+impl AutopilotView<'_> {
+    fn get_body(&self, entity: Entity) -> Option<Body> {
+        let sprite = self.world.sprites.get(entity)?;
+
+        Some(Body {
+            entity,
+            location: *self.world.positions.get(entity)?,
+            size: sprite.get_layout().dimensions,
+            velocity: self
+                .world
+                .velocities
+                .get(entity)
+                .copied()
+                .unwrap_or_default(),
+            anchor: sprite.anchor,
+        })
+    }
+
+    /// The ship being flown, None once it is destroyed
+    pub fn get_ship(&self) -> Option<Body> {
+        self.get_body(self.ship)
+    }
+
+    /// The asteroids, UFOs and boss parts, everything that damages the ship and can be shot
+    pub fn get_enemies(&self) -> Vec<Body> {
+        self.world
+            .get_entities()
+            .into_iter()
+            .filter(|entity| {
+                matches!(
+                    self.world.colliders.get(*entity),
+                    Some(CollisionLayer::Enemy | CollisionLayer::Boss)
+                )
+            })
+            .filter_map(|entity| self.get_body(entity))
+            .collect()
+    }
+}
+
+/// Flies a ship in place of a player. An autopilot presses the same actions as the keyboard, so a ship can't tell whether it is
+/// flown by a player or an autopilot.
+pub trait Autopilot {
+    /// The actions pressed this frame, only called while the ship hasn't been destroyed
+    fn get_actions(&mut self, view: &AutopilotView) -> Vec<Action>;
+}
+
+/// Creates an autopilot, seeded with the seed of the game so the same game is always flown the same way
+pub type CreateAutopilot = fn(u64) -> Box<dyn Autopilot>;
+
+/// An autopilot that can be picked by name
+#[derive(Clone, Copy)]
+pub struct AutopilotKind {
+    pub name: &'static str,
+    pub create: CreateAutopilot,
+}
+
+impl PartialEq for AutopilotKind {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Debug for AutopilotKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AutopilotKind")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+fn create_random_autopilot(seed: u64) -> Box<dyn Autopilot> {
+    Box::new(RandomAutopilot::new(seed))
+}
+
+fn create_dodging_autopilot(_: u64) -> Box<dyn Autopilot> {
+    Box::new(DodgingAutopilot)
+}
+
+pub const RANDOM_AUTOPILOT: AutopilotKind = AutopilotKind {
+    name: "random",
+    create: create_random_autopilot,
+};
+
+/// Dodges the asteroids and shoots the asteroids it lines up with
+pub const DODGING_AUTOPILOT: AutopilotKind = AutopilotKind {
+    name: "dodger",
+    create: create_dodging_autopilot,
+};
+
+pub const AUTOPILOTS: [AutopilotKind; 2] = [RANDOM_AUTOPILOT, DODGING_AUTOPILOT];
+
+impl AutopilotKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+
+        AUTOPILOTS.iter().find(|kind| kind.name == name).copied()
+    }
+}
+
+/// Presses random actions, a baseline for the other autopilots
+pub struct RandomAutopilot {
+    rng: StdRng,
+}
+
+impl RandomAutopilot {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Autopilot for RandomAutopilot {
+    fn get_actions(&mut self, _: &AutopilotView) -> Vec<Action> {
+        ACTIONS
+            .iter()
+            .filter(|_| self.rng.gen_range(0..100) < RANDOM_PRESS_CHANCE)
+            .copied()
+            .collect()
+    }
+}
+
+/// Looks ahead for collisions with the asteroids and moves out of their way, otherwise lines up under the closest asteroid. The
+/// spread bullet is fired when several asteroids are lined up and the basic bullet when one is.
+pub struct DodgingAutopilot;
+
+impl DodgingAutopilot {
+    /// The velocity of the ship after pressing the action, a ship in fixed flight keeps moving until another direction is pressed
+    fn get_velocity(action: Option<Action>, velocity: Point<i64>) -> Point<i64> {
+        match action {
+            Some(Action::Up) => Point::new(0, -HEIGHT_MAX_VELOCITY),
+            Some(Action::Down) => Point::new(0, HEIGHT_MAX_VELOCITY),
+            Some(Action::Left) => Point::new(-WIDTH_MAX_VELOCITY, 0),
+            Some(Action::Right) => Point::new(WIDTH_MAX_VELOCITY, 0),
+            _ => velocity,
+        }
+    }
+
+    /// How much the ship is hit over the lookahead when it moves at the velocity, earlier hits count for more
+    fn get_danger(
+        view: &AutopilotView,
+        ship: &Body,
+        velocity: Point<i64>,
+        enemies: &[Body],
+    ) -> u128 {
+        let step = view.frame_duration.max(1);
+        let moving_ship = Body { velocity, ..*ship };
+
+        (1..=LOOKAHEAD / step)
+            .map(|index| index * step)
+            .map(|time| {
+                let location = get_clamped_location(
+                    moving_ship.get_location_after(time),
+                    ship.size,
+                    &view.dimensions,
+                );
+
+                enemies
+                    .iter()
+                    .filter(|enemy| ship.overlaps(location, enemy, enemy.get_location_after(time)))
+                    .count() as u128
+                    * (LOOKAHEAD + step - time)
+            })
+            .sum()
+    }
+
+    /// The asteroid the ship lines up with, the closest asteroid above the ship
+    fn get_target<'a>(ship: &Body, enemies: &'a [Body]) -> Option<&'a Body> {
+        enemies
+            .iter()
+            .filter(|enemy| enemy.location.height + enemy.size.height <= ship.location.height)
+            .min_by_key(|enemy| {
+                (enemy.get_center().width - ship.get_center().width).abs()
+                    + (ship.location.height - enemy.location.height)
+            })
+    }
+
+    /// How far the ship is from where it wants to be after moving at the velocity, under the target and near the bottom
+    fn get_position_cost(
+        view: &AutopilotView,
+        ship: &Body,
+        velocity: Point<i64>,
+        target: Option<&Body>,
+    ) -> i64 {
+        let location = get_clamped_location(
+            Body { velocity, ..*ship }.get_location_after(view.frame_duration * 4),
+            ship.size,
+            &view.dimensions,
+        );
+        let nose = location.width + ship.anchor.width;
+        let home_height = view.dimensions.height - ship.size.height - HOME_HEIGHT;
+
+        let width_cost = target
+            .map(|target| (target.get_center().width - nose).abs())
+            .unwrap_or_else(|| (view.dimensions.width / 2 - nose).abs());
+
+        width_cost + (home_height - location.height).abs() / 2
+    }
+
+    /// Fires at the asteroids that will be in front of the nose when a bullet reaches them
+    fn get_fire_action(ship: &Body, enemies: &[Body]) -> Option<Action> {
+        let nose = ship.location + ship.anchor;
+
+        let lined_up: Vec<(&Body, i64)> = enemies
+            .iter()
+            .filter(|enemy| enemy.location.height + enemy.size.height <= nose.height)
+            .map(|enemy| {
+                let distance = nose.height - (enemy.location.height + enemy.size.height);
+                let time = (distance * 1000 / BASIC_BULLET_SPEED) as u128;
+                let location = enemy.get_location_after(time);
+
+                let offset = if nose.width < location.width {
+                    location.width - nose.width
+                } else if nose.width >= location.width + enemy.size.width {
+                    nose.width - (location.width + enemy.size.width - 1)
+                } else {
+                    0
+                };
+
+                (enemy, offset)
+            })
+            .filter(|(_, offset)| *offset <= SPREAD_WIDTH)
+            .collect();
+
+        let in_spread = lined_up.len();
+        let in_line = lined_up.iter().filter(|(_, offset)| *offset == 0).count();
+
+        match (in_spread, in_line) {
+            (spread, _) if spread >= 2 => Some(Action::Special),
+            (_, line) if line >= 1 => Some(Action::Fire),
+            _ => None,
+        }
+    }
+
+    /// A free flying ship turns to face the closest asteroid and fires when it faces it, thrusting away from asteroids that
+    /// come too close
+    fn get_free_flight_actions(ship: &Body, heading: Heading, enemies: &[Body]) -> Vec<Action> {
+        let center = ship.get_center();
+
+        let Some((target, distance)) = enemies
+            .iter()
+            .map(|enemy| {
+                let offset = enemy.get_center() - center;
+                let distance = ((offset.width.pow(2) + offset.height.pow(2)) as f64).sqrt();
+
+                (offset, distance)
+            })
+            .min_by(|(_, first), (_, second)| first.total_cmp(second))
+        else {
+            return vec![Action::Down];
+        };
+
+        let direction = (target.width as f64, target.height as f64);
+        let get_alignment = |heading: Heading| {
+            let (width, height) = heading.get_unit_vector();
+
+            width * direction.0 + height * direction.1
+        };
+
+        if distance < DANGER_DISTANCE && get_alignment(heading) < 0.0 {
+            return vec![Action::Up];
+        }
+
+        let clockwise = heading.rotate_clockwise();
+        let counter_clockwise = heading.rotate_counter_clockwise();
+        let alignment = get_alignment(heading);
+
+        if get_alignment(clockwise) > alignment
+            && get_alignment(clockwise) >= get_alignment(counter_clockwise)
+        {
+            vec![Action::Right]
+        } else if get_alignment(counter_clockwise) > alignment {
+            vec![Action::Left]
+        } else {
+            vec![Action::Fire]
+        }
+    }
+}
+
+impl Autopilot for DodgingAutopilot {
+    fn get_actions(&mut self, view: &AutopilotView) -> Vec<Action> {
+        let Some(ship) = view.get_ship() else {
+            return vec![];
+        };
+
+        let enemies = view.get_enemies();
+
+        if let Flight::Free(heading) = view.flight {
+            return Self::get_free_flight_actions(&ship, heading, &enemies);
+        }
+
+        let target = Self::get_target(&ship, &enemies);
+
+        // Not pressing anything comes first, so the ship only changes direction when it helps
+        let movement = [
+            None,
+            Some(Action::Up),
+            Some(Action::Down),
+            Some(Action::Left),
+            Some(Action::Right),
+        ]
+        .into_iter()
+        .min_by_key(|action| {
+            let velocity = Self::get_velocity(*action, ship.velocity);
+
+            (
+                Self::get_danger(view, &ship, velocity, &enemies),
+                Self::get_position_cost(view, &ship, velocity, target),
+            )
+        })
+        .flatten();
+
+        movement
+            .into_iter()
+            .chain(Self::get_fire_action(&ship, &enemies))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::{
+        api::display::Point,
+        components::{CollisionLayer, Sprite},
+        entities::{Action, Player},
+        user_display::Theme,
+        world::World,
+    };
+
+    use super::{Autopilot, AutopilotView, DodgingAutopilot};
+
+    fn get_view<'a>(world: &'a World, player: &Player) -> AutopilotView<'a> {
+        AutopilotView {
+            world,
+            ship: player.entity,
+            flight: player.flight,
+            dimensions: Point::new(100, 40),
+            frame_duration: 75,
+        }
+    }
+
+    fn spawn_asteroid(world: &mut World, location: Point<i64>, velocity: Point<i64>) {
+        world
+            .spawn()
+            .with_position(location)
+            .with_velocity(velocity)
+            .with_sprite(Sprite::from_ascii_frames(
+                &["###\n###"],
+                Color::White,
+                0,
+                Point::default(),
+            ))
+            .with_collider(CollisionLayer::Enemy)
+            .build();
+    }
+
+    #[test]
+    fn it_moves_out_of_the_way_of_asteroids() {
+        let mut world = World::new();
+        let player = Player::new(&mut world, Some(Point::new(48, 30)), 3, &Theme::default());
+
+        spawn_asteroid(&mut world, Point::new(49, 24), Point::new(0, 10));
+
+        let actions = DodgingAutopilot.get_actions(&get_view(&world, &player));
+
+        assert!(actions.contains(&Action::Left) || actions.contains(&Action::Right));
+    }
+
+    #[test]
+    fn it_fires_at_asteroids_it_is_lined_up_with() {
+        let mut world = World::new();
+        let player = Player::new(&mut world, Some(Point::new(48, 30)), 3, &Theme::default());
+        let nose = world.positions.get(player.entity).unwrap().width + 4;
+
+        spawn_asteroid(&mut world, Point::new(nose - 1, 2), Point::default());
+
+        assert!(DodgingAutopilot
+            .get_actions(&get_view(&world, &player))
+            .contains(&Action::Fire));
+
+        spawn_asteroid(&mut world, Point::new(nose + 3, 5), Point::default());
+
+        assert!(DodgingAutopilot
+            .get_actions(&get_view(&world, &player))
+            .contains(&Action::Special));
+    }
+}
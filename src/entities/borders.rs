@@ -2,7 +2,7 @@ use crossterm::style::Color;
 use uuid::Uuid;
 
 use crate::{
-    api::display::{element::DEFAULT_BACKGROUND, DisplayControllerError, Element, Layout, Point},
+    api::display::{DisplayControllerError, Element, Layout, Point},
     components::{Drawable, DrawableState, DrawableType},
 };
 
@@ -15,6 +15,7 @@ impl Borders {
     pub fn new(dimensions: &Point<i64>, color: Color) -> Result<Self, DisplayControllerError> {
         let mut drawable = DrawableState {
             layout: Layout::new(dimensions, None),
+            anchor: Point::default(),
             location: Point::default(),
             velocity: Default::default(),
             drawable_type: DrawableType::Border,
@@ -35,10 +36,4 @@ impl Drawable for Borders {
     fn get_drawable_state(&self) -> &DrawableState {
         &self.drawable
     }
-
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
-        self.drawable.location = updated_position;
-
-        self
-    }
 }
@@ -0,0 +1,595 @@
+use crossterm::style::Color;
+
+use crate::{
+    api::{assets::load_sprite_or_default, display::Point},
+    components::{
+        get_rated_velocity, CollisionLayer, Lifecycle, LifecycleContext, SpawnCommand, Sprite,
+    },
+    systems::DESTROY_DAMAGE,
+    user_display::Theme,
+    world::{Entity, World},
+};
+
+use super::{
+    consts::{BOSS_SHOT, MOTHERSHIP_HULL_FRAMES, MOTHERSHIP_TURRET_FRAMES},
+    spawn_pickup,
+};
+
+const BOSS_SHOT_SPRITE: &str = "boss_shot";
+const BOSS_SHOT_DAMAGE: u32 = 1;
+/// In cells per second
+const BOSS_SHOT_SPEED: i64 = 15;
+/// Shots are despawned eventually when the screen wraps, since they never leave the play field
+const BOSS_SHOT_LIFETIME: u128 = 4000;
+/// The sideways speed of the outer shots of a spread, in cells per second
+const SPREAD_WIDTH_SPEED: i64 = 8;
+
+/// How far down the play field bosses move before they start to patrol
+const PATROL_HEIGHT: i64 = 1;
+
+/// The bosses that can be triggered by a spawn table or a level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossKind {
+    Mothership,
+}
+
+impl BossKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "mothership" => Some(Self::Mothership),
+            _ => None,
+        }
+    }
+
+    fn get_definition(&self) -> &'static BossDefinition {
+        match self {
+            Self::Mothership => &MOTHERSHIP,
+        }
+    }
+}
+
+/// How a boss attacks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttackPattern {
+    /// Every turret fires straight down, the core fires once the turrets are destroyed
+    Volley,
+    /// Every turret fires three shots fanning out, the core fires once the turrets are destroyed
+    Spread,
+    /// The core fires at the player
+    Aimed,
+}
+
+/// A stage of the fight, a boss moves to the next phase as it loses health
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BossPhase {
+    /// The phase lasts while the boss has more than this percentage of its health left
+    pub min_health_percent: u32,
+    /// In cells per second
+    pub speed: i64,
+    pub attack: AttackPattern,
+    /// The time between attacks, in milliseconds
+    pub attack_interval: u128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossPartRole {
+    /// The boss is defeated once its core is destroyed
+    Core,
+    /// Fires the volley and spread attacks
+    Turret,
+}
+
+/// A piece of a boss, each part is a separate entity with its own health and hitbox
+pub struct BossPartDefinition {
+    /// The name of the sprite file
+    pub sprite: &'static str,
+    pub frames: &'static [&'static str],
+    pub color: Color,
+    pub frame_duration: u128,
+    /// The top left of the part from the top left of the boss
+    pub offset: Point<i64>,
+    pub health: u32,
+    pub damage: u32,
+    pub role: BossPartRole,
+}
+
+pub struct BossDefinition {
+    /// Shown above the health bar
+    pub name: &'static str,
+    pub parts: &'static [BossPartDefinition],
+    /// Ordered from the first phase to the last
+    pub phases: &'static [BossPhase],
+    /// The points awarded when the boss is defeated
+    pub reward_points: u64,
+    /// The pickups dropped when the boss is defeated
+    pub reward_pickups: usize,
+}
+
+/// A giant saucer with a turret on either side, the turrets have to be taken out to stop the volleys
+pub static MOTHERSHIP: BossDefinition = BossDefinition {
+    name: "Mothership",
+    parts: &[
+        BossPartDefinition {
+            sprite: "mothership_turret",
+            frames: &MOTHERSHIP_TURRET_FRAMES,
+            color: Color::Magenta,
+            frame_duration: 200,
+            offset: Point::new(0, 1),
+            health: 6,
+            damage: 2,
+            role: BossPartRole::Turret,
+        },
+        BossPartDefinition {
+            sprite: "mothership_hull",
+            frames: &MOTHERSHIP_HULL_FRAMES,
+            color: Color::DarkMagenta,
+            frame_duration: 300,
+            offset: Point::new(6, 0),
+            health: 15,
+            damage: 3,
+            role: BossPartRole::Core,
+        },
+        BossPartDefinition {
+            sprite: "mothership_turret",
+            frames: &MOTHERSHIP_TURRET_FRAMES,
+            color: Color::Magenta,
+            frame_duration: 200,
+            offset: Point::new(28, 1),
+            health: 6,
+            damage: 2,
+            role: BossPartRole::Turret,
+        },
+    ],
+    phases: &[
+        BossPhase {
+            min_health_percent: 60,
+            speed: 8,
+            attack: AttackPattern::Volley,
+            attack_interval: 1500,
+        },
+        BossPhase {
+            min_health_percent: 25,
+            speed: 12,
+            attack: AttackPattern::Spread,
+            attack_interval: 1200,
+        },
+        BossPhase {
+            min_health_percent: 0,
+            speed: 16,
+            attack: AttackPattern::Aimed,
+            attack_interval: 700,
+        },
+    ],
+    reward_points: 500,
+    reward_pickups: 2,
+};
+
+/// Parts of a boss explode when they are destroyed, large parts explode along their width
+struct BossPartLifecycle;
+
+impl Lifecycle for BossPartLifecycle {
+    fn on_destroy(&self, context: &LifecycleContext) -> Vec<SpawnCommand> {
+        let center = context.get_center();
+        let quarter = context.size.width / 4;
+
+        vec![
+            SpawnCommand::Explosion(center.sub_width(quarter)),
+            SpawnCommand::Explosion(center),
+            SpawnCommand::Explosion(center.add_width(quarter)),
+        ]
+    }
+}
+
+/// Spawns a shot fired by a boss, centered on the location
+pub fn spawn_boss_shot(
+    world: &mut World,
+    location: Point<i64>,
+    velocity: Point<i64>,
+    theme: &Theme,
+) -> Entity {
+    let sprite = load_sprite_or_default(BOSS_SHOT_SPRITE, || {
+        Sprite::from_ascii_frames(&[BOSS_SHOT], Color::Red, 0, Point::default())
+    })
+    .with_color(theme.asteroid);
+
+    world
+        .spawn()
+        .with_position(location - sprite.anchor)
+        .with_velocity(velocity)
+        .with_sprite(sprite)
+        .with_health(1)
+        .with_collider(CollisionLayer::Enemy)
+        .with_damage(BOSS_SHOT_DAMAGE)
+        .with_lifetime(BOSS_SHOT_LIFETIME)
+        .build()
+}
+
+struct BossPart {
+    entity: Entity,
+    offset: Point<i64>,
+    role: BossPartRole,
+}
+
+/// A large enemy made of several parts. The boss moves its parts together rather than giving them a velocity, so the parts stay
+/// lined up even when the screen wraps.
+pub struct Boss {
+    definition: &'static BossDefinition,
+    parts: Vec<BossPart>,
+    /// The top left of the boss
+    location: Point<i64>,
+    size: Point<i64>,
+    /// 1 when patrolling to the right, -1 to the left
+    direction: i64,
+    max_health: u32,
+    time_since_attack: u128,
+    dimensions: Point<i64>,
+    theme: Theme,
+}
+
+impl Boss {
+    /// Spawns a boss just above the middle of the play field, it moves down onto the play field before it starts to attack
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The boss to spawn
+    /// * `dimensions` - The dimensions of the play field
+    /// * `theme` - The theme used to color the boss
+    pub fn spawn(world: &mut World, kind: BossKind, dimensions: Point<i64>, theme: &Theme) -> Self {
+        let definition = kind.get_definition();
+
+        let mut parts = vec![];
+        let mut size: Point<i64> = Point::default();
+
+        for part in definition.parts {
+            let sprite = load_sprite_or_default(part.sprite, || {
+                Sprite::from_ascii_frames(
+                    part.frames,
+                    part.color,
+                    part.frame_duration,
+                    Point::default(),
+                )
+            })
+            .with_color(theme.asteroid);
+
+            let part_size = sprite.get_layout().dimensions;
+
+            size = Point::new(
+                size.width.max(part.offset.width + part_size.width),
+                size.height.max(part.offset.height + part_size.height),
+            );
+
+            let entity = world
+                .spawn()
+                .with_sprite(sprite)
+                .with_health(part.health)
+                .with_collider(CollisionLayer::Boss)
+                .with_damage(part.damage)
+                .with_lifecycle(BossPartLifecycle)
+                .build();
+
+            parts.push(BossPart {
+                entity,
+                offset: part.offset,
+                role: part.role,
+            });
+        }
+
+        let boss = Self {
+            definition,
+            parts,
+            location: Point::new(dimensions.width / 2 - size.width / 2, -size.height),
+            size,
+            direction: 1,
+            max_health: definition.parts.iter().map(|part| part.health).sum(),
+            time_since_attack: 0,
+            dimensions,
+            theme: *theme,
+        };
+
+        boss.move_parts(world);
+
+        boss
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.definition.name
+    }
+
+    /// The remaining health of every part
+    pub fn get_health(&self, world: &World) -> u32 {
+        self.parts
+            .iter()
+            .filter_map(|part| world.healths.get(part.entity))
+            .sum()
+    }
+
+    pub fn get_max_health(&self) -> u32 {
+        self.max_health
+    }
+
+    /// A boss is defeated once its core has been destroyed, whatever is left of the other parts is destroyed along with it
+    pub fn is_defeated(&self, world: &World) -> bool {
+        self.parts
+            .iter()
+            .filter(|part| part.role == BossPartRole::Core)
+            .all(|part| !world.has_entity(part.entity))
+    }
+
+    pub fn get_phase(&self, world: &World) -> &'static BossPhase {
+        let health_percent = self.get_health(world) * 100 / self.max_health.max(1);
+
+        self.definition
+            .phases
+            .iter()
+            .find(|phase| health_percent > phase.min_health_percent)
+            .or(self.definition.phases.last())
+            .expect("Bosses have at least one phase")
+    }
+
+    /// Moves the boss and attacks
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Where the aimed attacks are fired at, usually the center of the player
+    pub fn handle_game_loop(
+        &mut self,
+        world: &mut World,
+        game_loop_duration: u128,
+        target: Option<Point<i64>>,
+    ) -> &mut Self {
+        self.parts.retain(|part| world.has_entity(part.entity));
+
+        let phase = *self.get_phase(world);
+
+        if self.location.height < PATROL_HEIGHT {
+            self.location =
+                self.location + get_rated_velocity(Point::new(0, phase.speed), game_loop_duration);
+
+            self.move_parts(world);
+
+            return self;
+        }
+
+        if self.location.width <= 0 {
+            self.direction = 1;
+        } else if self.location.width + self.size.width >= self.dimensions.width {
+            self.direction = -1;
+        }
+
+        self.location = self.location
+            + get_rated_velocity(
+                Point::new(phase.speed * self.direction, 0),
+                game_loop_duration,
+            );
+
+        self.move_parts(world);
+
+        self.time_since_attack += game_loop_duration;
+
+        if self.time_since_attack >= phase.attack_interval {
+            self.time_since_attack = 0;
+
+            self.attack(world, phase.attack, target);
+        }
+
+        self
+    }
+
+    /// Destroys whatever is left of the boss and drops its reward
+    ///
+    /// # Returns
+    ///
+    /// * The points awarded for defeating the boss
+    pub fn destroy(self, world: &mut World) -> u64 {
+        for part in &self.parts {
+            world.apply_damage(part.entity, DESTROY_DAMAGE);
+        }
+
+        let center = self.location + self.size / 2_i64.into();
+        let reward_pickups = self.definition.reward_pickups as i64;
+
+        for index in 0..reward_pickups {
+            let offset = (index - (reward_pickups - 1) / 2) * 6;
+
+            spawn_pickup(
+                world,
+                center.add_width(offset),
+                Point::new(0, 5),
+                &self.theme,
+            );
+        }
+
+        self.definition.reward_points
+    }
+
+    fn move_parts(&self, world: &mut World) {
+        for part in &self.parts {
+            world
+                .positions
+                .insert(part.entity, self.location + part.offset);
+        }
+    }
+
+    /// Where a part fires from, just below the middle of the part so the shots don't overlap it
+    fn get_muzzle(&self, world: &World, part: &BossPart) -> Point<i64> {
+        let size = world
+            .sprites
+            .get(part.entity)
+            .map(|sprite| sprite.get_layout().dimensions)
+            .unwrap_or_default();
+
+        self.location + part.offset + Point::new(size.width / 2, size.height)
+    }
+
+    fn attack(&self, world: &mut World, attack: AttackPattern, target: Option<Point<i64>>) {
+        let turrets: Vec<&BossPart> = self
+            .parts
+            .iter()
+            .filter(|part| part.role == BossPartRole::Turret)
+            .collect();
+
+        let cores: Vec<&BossPart> = self
+            .parts
+            .iter()
+            .filter(|part| part.role == BossPartRole::Core)
+            .collect();
+
+        let mut shots: Vec<(Point<i64>, Point<i64>)> = vec![];
+
+        match attack {
+            AttackPattern::Volley | AttackPattern::Spread => {
+                let velocities = match attack {
+                    AttackPattern::Spread => vec![
+                        Point::new(-SPREAD_WIDTH_SPEED, BOSS_SHOT_SPEED),
+                        Point::new(0, BOSS_SHOT_SPEED),
+                        Point::new(SPREAD_WIDTH_SPEED, BOSS_SHOT_SPEED),
+                    ],
+                    _ => vec![Point::new(0, BOSS_SHOT_SPEED)],
+                };
+
+                let gunners = if turrets.is_empty() { &cores } else { &turrets };
+
+                for part in gunners {
+                    let muzzle = self.get_muzzle(world, part);
+
+                    shots.extend(velocities.iter().map(|velocity| (muzzle, *velocity)));
+                }
+            }
+            AttackPattern::Aimed => {
+                for part in &cores {
+                    let muzzle = self.get_muzzle(world, part);
+
+                    shots.push((muzzle, get_aimed_velocity(muzzle, target)));
+                }
+            }
+        }
+
+        for (location, velocity) in shots {
+            spawn_boss_shot(world, location, velocity, &self.theme);
+        }
+    }
+}
+
+/// The velocity of a shot fired from the origin straight at the target, shots without a target fire straight down
+fn get_aimed_velocity(origin: Point<i64>, target: Option<Point<i64>>) -> Point<i64> {
+    let Some(target) = target else {
+        return Point::new(0, BOSS_SHOT_SPEED);
+    };
+
+    let direction = target - origin;
+    let distance = ((direction.width.pow(2) + direction.height.pow(2)) as f64).sqrt();
+
+    if distance == 0. {
+        return Point::new(0, BOSS_SHOT_SPEED);
+    }
+
+    Point::new(
+        (direction.width as f64 * BOSS_SHOT_SPEED as f64 / distance).round() as i64,
+        (direction.height as f64 * BOSS_SHOT_SPEED as f64 / distance).round() as i64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point, components::CollisionLayer, user_display::Theme, world::World,
+    };
+
+    use super::{get_aimed_velocity, AttackPattern, Boss, BossKind, BOSS_SHOT_SPEED};
+
+    const DIMENSIONS: Point<i64> = Point::new(80, 30);
+
+    #[test]
+    fn it_moves_the_parts_together() {
+        let mut world = World::new();
+        let mut boss = Boss::spawn(
+            &mut world,
+            BossKind::Mothership,
+            DIMENSIONS,
+            &Theme::default(),
+        );
+
+        let get_offsets = |world: &World| {
+            let locations: Vec<Point<i64>> = world
+                .get_entities()
+                .iter()
+                // Skips the shots fired by the boss
+                .take(3)
+                .map(|entity| *world.positions.get(*entity).unwrap())
+                .collect();
+
+            locations
+                .iter()
+                .map(|location| *location - locations[0])
+                .collect::<Vec<Point<i64>>>()
+        };
+
+        let offsets = get_offsets(&world);
+
+        for _ in 0..20 {
+            boss.handle_game_loop(&mut world, 100, None);
+        }
+
+        assert_eq!(get_offsets(&world), offsets);
+        assert!(world.positions.get(world.get_entities()[0]).unwrap().height >= 1);
+    }
+
+    #[test]
+    fn it_changes_phase_as_it_loses_health() {
+        let mut world = World::new();
+        let boss = Boss::spawn(
+            &mut world,
+            BossKind::Mothership,
+            DIMENSIONS,
+            &Theme::default(),
+        );
+
+        assert_eq!(boss.get_phase(&world).attack, AttackPattern::Volley);
+        assert_eq!(boss.get_health(&world), boss.get_max_health());
+
+        let entities = world.get_entities();
+
+        // Destroying both turrets takes the boss below 60% health
+        world.apply_damage(entities[0], 6);
+        world.apply_damage(entities[2], 6);
+
+        assert_eq!(boss.get_phase(&world).attack, AttackPattern::Spread);
+        assert!(!boss.is_defeated(&world));
+
+        world.apply_damage(entities[1], 10);
+
+        assert_eq!(boss.get_phase(&world).attack, AttackPattern::Aimed);
+
+        world.apply_damage(entities[1], 5);
+
+        assert!(boss.is_defeated(&world));
+    }
+
+    #[test]
+    fn it_drops_the_reward_when_destroyed() {
+        let mut world = World::new();
+        let boss = Boss::spawn(
+            &mut world,
+            BossKind::Mothership,
+            DIMENSIONS,
+            &Theme::default(),
+        );
+
+        assert_eq!(boss.destroy(&mut world), 500);
+        assert_eq!(world.count_on_layer(CollisionLayer::Boss), 0);
+        assert_eq!(world.count_on_layer(CollisionLayer::Pickup), 2);
+    }
+
+    #[test]
+    fn it_aims_at_the_target() {
+        assert_eq!(
+            get_aimed_velocity(Point::new(10, 0), Some(Point::new(10, 20))),
+            Point::new(0, BOSS_SHOT_SPEED)
+        );
+
+        assert_eq!(
+            get_aimed_velocity(Point::new(10, 10), Some(Point::new(0, 10))),
+            Point::new(-BOSS_SHOT_SPEED, 0)
+        );
+    }
+}
@@ -1,82 +1,112 @@
 use crossterm::style::Color;
 
 use crate::{
-    api::display::{
-        element::{DEFAULT_BACKGROUND, DEFAULT_FOREGROUND},
-        Element, Layout, Point,
-    },
-    components::{get_updated_health, Drawable, DrawableState, DrawableType, Health},
+    api::{assets::load_sprite_or_default, display::Point},
+    components::{CollisionLayer, Sprite},
+    user_display::Theme,
+    world::{Entity, World},
 };
 
-use super::consts::{BASIC_BULLET, SPREAD_BULLET};
-
-pub struct Bullet {
-    pub drawable: DrawableState,
-    pub health: u32,
-}
+use super::consts::{BASIC_BULLET, CLASSIC_BULLET, SPREAD_BULLET};
 
 pub const BULLET_DAMAGE: u32 = 1;
+/// How fast the basic bullet flies up the screen, in cells per second
+pub(super) const BASIC_BULLET_SPEED: i64 = 20;
 
-impl Bullet {
-    pub fn build_basic_bullet(location: Point<i64>) -> Self {
-        let map = Layout::from_ascii(BASIC_BULLET, Color::White);
-
-        let velocity: Point<i64> = Point {
-            height: -20,
-            width: 0,
-        };
-
-        Self {
-            drawable: DrawableState::new(
-                map,
-                location,
-                DrawableType::Ammunition(BULLET_DAMAGE, 3),
-                Some(velocity),
-            ),
-            health: 1,
-        }
-    }
-
-    pub fn build_spread_bullet(location: Point<i64>) -> Self {
-        let map = Layout::from_ascii(SPREAD_BULLET, Color::White);
-
-        let velocity: Point<i64> = Point {
-            height: -10,
-            width: 0,
-        };
-
-        Self {
-            drawable: DrawableState::new(
-                map,
-                location,
-                DrawableType::Ammunition(BULLET_DAMAGE, 1),
-                Some(velocity),
-            ),
-            health: 5,
-        }
-    }
-}
+const BASIC_BULLET_SPRITE: &str = "basic_bullet";
+const SPREAD_BULLET_SPRITE: &str = "spread_bullet";
+const CLASSIC_BULLET_SPRITE: &str = "classic_bullet";
+
+// The anchors line up with the nose of the spaceship when the bullets are fired
+const BASIC_BULLET_ANCHOR: Point<i64> = Point::new(1, 2);
+const SPREAD_BULLET_ANCHOR: Point<i64> = Point::new(5, 3);
 
-impl Drawable for Bullet {
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
-        self.drawable.location = updated_position;
+/// Classic bullets wrap around the screen, so they despawn after a while instead of when they leave the screen
+const CLASSIC_BULLET_LIFETIME: u128 = 1200;
 
-        self
-    }
+fn get_bullet_sprite(name: &str, ascii: &str, anchor: Point<i64>, theme: &Theme) -> Sprite {
+    load_sprite_or_default(name, || {
+        Sprite::from_ascii_frames(&[ascii], Color::White, 0, anchor)
+    })
+    .with_color(theme.bullet)
+}
+
+/// Spawns a bullet with its anchor on the location
+///
+/// # Arguments
+///
+/// * `health` - How many asteroids the bullet can hit before it is destroyed
+/// * `points` - The points awarded for each asteroid the bullet destroys
+fn spawn_bullet(
+    world: &mut World,
+    sprite: Sprite,
+    location: Point<i64>,
+    velocity: Point<i64>,
+    health: u32,
+    points: u64,
+) -> Entity {
+    world
+        .spawn()
+        .with_position(location - sprite.anchor)
+        .with_velocity(velocity)
+        .with_sprite(sprite)
+        .with_health(health)
+        .with_collider(CollisionLayer::Ammunition)
+        .with_damage(BULLET_DAMAGE)
+        .with_score(points)
+        .build()
+}
 
-    fn get_drawable_state(&self) -> &DrawableState {
-        &self.drawable
-    }
+pub fn spawn_basic_bullet(world: &mut World, location: Point<i64>, theme: &Theme) -> Entity {
+    let sprite = get_bullet_sprite(
+        BASIC_BULLET_SPRITE,
+        BASIC_BULLET,
+        BASIC_BULLET_ANCHOR,
+        theme,
+    );
+
+    spawn_bullet(
+        world,
+        sprite,
+        location,
+        Point::new(0, -BASIC_BULLET_SPEED),
+        1,
+        3,
+    )
 }
 
-impl Health for Bullet {
-    fn apply_damage(&mut self, damage: u32) -> &mut Self {
-        self.health = get_updated_health(self.health, damage);
+pub fn spawn_spread_bullet(world: &mut World, location: Point<i64>, theme: &Theme) -> Entity {
+    let sprite = get_bullet_sprite(
+        SPREAD_BULLET_SPRITE,
+        SPREAD_BULLET,
+        SPREAD_BULLET_ANCHOR,
+        theme,
+    );
 
-        self
-    }
+    spawn_bullet(world, sprite, location, Point::new(0, -10), 5, 1)
+}
 
-    fn get_health(&self) -> u32 {
-        self.health
-    }
+/// A single cell bullet fired in any direction, used by the free flying ship
+///
+/// # Arguments
+///
+/// * `velocity` - The velocity of the bullet, including the velocity of the ship that fired it
+pub fn spawn_classic_bullet(
+    world: &mut World,
+    location: Point<i64>,
+    velocity: Point<i64>,
+    theme: &Theme,
+) -> Entity {
+    let sprite = get_bullet_sprite(
+        CLASSIC_BULLET_SPRITE,
+        CLASSIC_BULLET,
+        Point::default(),
+        theme,
+    );
+
+    let bullet = spawn_bullet(world, sprite, location, velocity, 1, 1);
+
+    world.lifetimes.insert(bullet, CLASSIC_BULLET_LIFETIME);
+
+    bullet
 }
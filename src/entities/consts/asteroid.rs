@@ -2,3 +2,26 @@ pub const ASTEROID: &str = " ,-,-.
 /.( +.\\
 \\ {. */
  `-`-'";
+
+const ASTEROID_MIRRORED: &str = " ,-,-.
+/.+ (.\\
+\\* .{ /
+ `-`-'";
+
+const ASTEROID_ROTATED: &str = " ,-,-.
+/* .{ \\
+\\.+ (./
+ `-`-'";
+
+const ASTEROID_FLIPPED: &str = " ,-,-.
+/ {. *\\
+\\.( +./
+ `-`-'";
+
+/// The asteroid tumbling, each frame keeps the same outline so the asteroid doesn't appear to change size
+pub const ASTEROID_FRAMES: [&str; 4] = [
+    ASTEROID,
+    ASTEROID_MIRRORED,
+    ASTEROID_ROTATED,
+    ASTEROID_FLIPPED,
+];
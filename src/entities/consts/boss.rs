@@ -0,0 +1,23 @@
+/// The hull of the mothership, the lights run along its belly
+pub const MOTHERSHIP_HULL_FRAMES: [&str; 2] = [
+    "    ___/=====\\___
+  /~~~~~~~~~~~~~~~\\
+<=O===O===O===O===O=>
+  \\_______________/",
+    "    ___/=====\\___
+  /~~~~~~~~~~~~~~~\\
+<=o===O===o===O===o=>
+  \\_______________/",
+];
+
+/// The turrets on either side of the mothership, the barrel flashes as it charges
+pub const MOTHERSHIP_TURRET_FRAMES: [&str; 2] = [
+    " _|_
+[ooo]
+ \\v/",
+    " _|_
+[oOo]
+ \\V/",
+];
+
+pub const BOSS_SHOT: &str = "o";
@@ -6,3 +6,5 @@ pub const SPREAD_BULLET: &str = "
      ^
   ^  ^  ^
 ^  ^   ^  ^";
+
+pub const CLASSIC_BULLET: &str = "*";
@@ -0,0 +1,10 @@
+pub const EXPLOSION_FRAMES: [&str; 3] = [
+    "
+ .*.",
+    " \\|/
+-- --
+ /|\\",
+    ".   .
+  .
+.   .",
+];
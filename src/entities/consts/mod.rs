@@ -1,7 +1,15 @@
 mod asteroid;
+mod boss;
 mod bullet;
+mod explosion;
+mod pickup;
 mod spaceship;
+mod ufo;
 
 pub use asteroid::*;
+pub use boss::*;
 pub use bullet::*;
+pub use explosion::*;
+pub use pickup::*;
 pub use spaceship::*;
+pub use ufo::*;
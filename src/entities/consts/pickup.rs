@@ -0,0 +1 @@
+pub const PICKUP_FRAMES: [&str; 2] = ["[+]", "(+)"];
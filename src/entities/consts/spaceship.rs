@@ -9,6 +9,19 @@ pub const SPACE_SHIP: &str = "    !
    ( )
     .";
 
+const SPACE_SHIP_EXHAUST_FLICKER: &str = "    !
+    ^
+   /_\\
+  |= =|
+  |   |
+  |   |
+ /|#!#|\\
+/ \\   / \\
+   (*)
+    :";
+
+pub const SPACE_SHIP_FRAMES: [&str; 2] = [SPACE_SHIP, SPACE_SHIP_EXHAUST_FLICKER];
+
 // pub const SPACE_SHIP: &str = "       !
 //        !
 //        ^
@@ -39,3 +52,31 @@ pub const SPACE_SHIP: &str = "    !
 //        .
 //        .
 //        .";
+
+/// The ship used in the classic mode, a frame for each heading in clockwise order starting from up
+pub const CLASSIC_SHIP_FRAMES: [&str; 8] = [
+    " ^
+ #
+. .",
+    "  /
+.#
+ .",
+    ".
+ #>
+.",
+    " .
+.#
+  \\",
+    ". .
+ #
+ v",
+    " .
+ #.
+/",
+    "  .
+<#
+  .",
+    "\\
+ #.
+ .",
+];
@@ -0,0 +1,9 @@
+/// The lights of the saucer chase around its rim
+pub const UFO_FRAMES: [&str; 2] = [
+    "  _.-._
+<=o=o=o=>
+  `---'",
+    "  _.-._
+<=o-o-o=>
+  `---'",
+];
@@ -1,28 +1,133 @@
 use crossterm::event::{Event, KeyCode};
 
+use crate::world::World;
+
 pub fn create_event(keycode: KeyCode) -> Event {
     Event::Key(keycode.into())
 }
 
+/// The keys that control a ship
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    /// Fires the basic bullet, or the classic bullet in free flight
+    pub fire: KeyCode,
+    /// Fires the spread bullet
+    pub special: KeyCode,
+}
+
+/// What a key binding does, used to send inputs without the keys that were pressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Fire,
+    Special,
+}
+
+pub const ACTIONS: [Action; 6] = [
+    Action::Up,
+    Action::Down,
+    Action::Left,
+    Action::Right,
+    Action::Fire,
+    Action::Special,
+];
+
+impl Action {
+    pub const fn get_name(&self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Fire => "fire",
+            Self::Special => "special",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        ACTIONS
+            .iter()
+            .find(|action| action.get_name() == name)
+            .copied()
+    }
+}
+
+impl KeyBindings {
+    /// Returns the action of the key that was pressed, None if the key isn't bound
+    pub fn get_action(&self, event: &Event) -> Option<Action> {
+        ACTIONS
+            .iter()
+            .find(|action| event == &self.get_event(**action))
+            .copied()
+    }
+
+    /// Returns the key press event that performs the action
+    pub fn get_event(&self, action: Action) -> Event {
+        let keycode = match action {
+            Action::Up => self.up,
+            Action::Down => self.down,
+            Action::Left => self.left,
+            Action::Right => self.right,
+            Action::Fire => self.fire,
+            Action::Special => self.special,
+        };
+
+        create_event(keycode)
+    }
+}
+
+pub const ARROW_KEY_BINDINGS: KeyBindings = KeyBindings {
+    up: KeyCode::Up,
+    down: KeyCode::Down,
+    left: KeyCode::Left,
+    right: KeyCode::Right,
+    fire: KeyCode::Char('q'),
+    special: KeyCode::Char(' '),
+};
+
+/// Used by the second player, so both players can share a keyboard
+pub const WASD_KEY_BINDINGS: KeyBindings = KeyBindings {
+    up: KeyCode::Char('w'),
+    down: KeyCode::Char('s'),
+    left: KeyCode::Char('a'),
+    right: KeyCode::Char('d'),
+    fire: KeyCode::Char('f'),
+    special: KeyCode::Char('g'),
+};
+
+/// The key bindings of each player, in player order
+pub const PLAYER_KEY_BINDINGS: [KeyBindings; 2] = [ARROW_KEY_BINDINGS, WASD_KEY_BINDINGS];
+
 pub trait Controller {
-    fn up(&mut self) -> &mut Self;
-    fn down(&mut self) -> &mut Self;
-    fn left(&mut self) -> &mut Self;
-    fn right(&mut self) -> &mut Self;
-
-    fn additional_event_logic(&mut self, event: &Event) -> &mut Self;
-
-    fn handle_event(&mut self, event: &Event) {
-        if event == &create_event(KeyCode::Up) {
-            self.up();
-        } else if event == &create_event(KeyCode::Down) {
-            self.down();
-        } else if event == &create_event(KeyCode::Left) {
-            self.left();
-        } else if event == &create_event(KeyCode::Right) {
-            self.right();
+    fn get_key_bindings(&self) -> KeyBindings;
+
+    fn up(&mut self, world: &mut World) -> &mut Self;
+    fn down(&mut self, world: &mut World) -> &mut Self;
+    fn left(&mut self, world: &mut World) -> &mut Self;
+    fn right(&mut self, world: &mut World) -> &mut Self;
+
+    fn additional_event_logic(&mut self, event: &Event, world: &mut World) -> &mut Self;
+
+    fn handle_event(&mut self, event: &Event, world: &mut World) {
+        let bindings = self.get_key_bindings();
+
+        if event == &create_event(bindings.up) {
+            self.up(world);
+        } else if event == &create_event(bindings.down) {
+            self.down(world);
+        } else if event == &create_event(bindings.left) {
+            self.left(world);
+        } else if event == &create_event(bindings.right) {
+            self.right(world);
         } else {
-            self.additional_event_logic(event);
+            self.additional_event_logic(event, world);
         }
     }
 }
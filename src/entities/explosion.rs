@@ -0,0 +1,41 @@
+use crossterm::style::Color;
+
+use crate::{
+    api::{assets::load_sprite_or_default, display::Point},
+    components::Sprite,
+    user_display::Theme,
+    world::{Entity, World},
+};
+
+use super::consts::EXPLOSION_FRAMES;
+
+const EXPLOSION_SPRITE: &str = "explosion";
+const EXPLOSION_FRAME_DURATION: u128 = 80;
+// The center of the explosion
+const EXPLOSION_ANCHOR: Point<i64> = Point::new(2, 1);
+
+/// Spawns an explosion centered on the position that plays its animation once. It is purely visual, so it has no collider.
+pub fn spawn_explosion(world: &mut World, position: Point<i64>, theme: &Theme) -> Entity {
+    let sprite = load_sprite_or_default(EXPLOSION_SPRITE, || {
+        Sprite::from_ascii_frames(
+            &EXPLOSION_FRAMES,
+            Color::Yellow,
+            EXPLOSION_FRAME_DURATION,
+            EXPLOSION_ANCHOR,
+        )
+    })
+    .with_color(theme.explosion);
+
+    // A sprite that holds a frame would never finish, so it is shown for a single frame duration instead
+    let lifetime = sprite
+        .animation
+        .get_total_duration()
+        .unwrap_or(EXPLOSION_FRAME_DURATION);
+
+    world
+        .spawn()
+        .with_position(position - sprite.anchor)
+        .with_sprite(sprite)
+        .with_lifetime(lifetime)
+        .build()
+}
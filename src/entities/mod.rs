@@ -1,12 +1,24 @@
 mod asteroid;
+mod autopilot;
 mod borders;
+mod boss;
 mod bullet;
 mod consts;
 pub mod controller;
+mod explosion;
+mod pickup;
 pub mod player;
+mod ufo;
 
 pub use asteroid::*;
+pub use autopilot::*;
 pub use borders::Borders;
+pub use boss::*;
 pub use bullet::*;
-pub use controller::Controller;
+pub use controller::{
+    Action, Controller, ARROW_KEY_BINDINGS, PLAYER_KEY_BINDINGS, WASD_KEY_BINDINGS,
+};
+pub use explosion::*;
+pub use pickup::*;
 pub use player::Player;
+pub use ufo::*;
@@ -0,0 +1,44 @@
+use crossterm::style::Color;
+
+use crate::{
+    api::{assets::load_sprite_or_default, display::Point},
+    components::{CollisionLayer, Sprite},
+    user_display::Theme,
+    world::{Entity, World},
+};
+
+use super::consts::PICKUP_FRAMES;
+
+/// The lives restored when the player collects a pickup
+pub const PICKUP_HEALING: u32 = 1;
+
+const PICKUP_SPRITE: &str = "pickup";
+const PICKUP_FRAME_DURATION: u128 = 300;
+const PICKUP_ANCHOR: Point<i64> = Point::new(1, 0);
+
+/// Spawns an extra life that the player collects by flying into it
+pub fn spawn_pickup(
+    world: &mut World,
+    location: Point<i64>,
+    velocity: Point<i64>,
+    theme: &Theme,
+) -> Entity {
+    let sprite = load_sprite_or_default(PICKUP_SPRITE, || {
+        Sprite::from_ascii_frames(
+            &PICKUP_FRAMES,
+            Color::Red,
+            PICKUP_FRAME_DURATION,
+            PICKUP_ANCHOR,
+        )
+    })
+    .with_color(theme.heart);
+
+    world
+        .spawn()
+        .with_position(location)
+        .with_velocity(velocity)
+        .with_sprite(sprite)
+        .with_health(1)
+        .with_collider(CollisionLayer::Pickup)
+        .build()
+}
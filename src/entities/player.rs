@@ -1,28 +1,67 @@
-use crossterm::{event::KeyCode, style::Color};
+use crossterm::style::Color;
 
 use crate::{
-    api::display::{Layout, Point},
-    components::{get_updated_health, Drawable, DrawableState, DrawableType, Health, Spawnable},
-    systems::EntityController,
+    api::{assets::load_sprite_or_default, display::Point},
+    components::{CollisionLayer, Heading, Sprite},
+    user_display::Theme,
+    world::{Entity, World},
 };
 
-use super::{consts::SPACE_SHIP, controller::create_event, Bullet, Controller};
+use super::{
+    consts::{CLASSIC_SHIP_FRAMES, SPACE_SHIP_FRAMES},
+    controller::{create_event, KeyBindings, ARROW_KEY_BINDINGS},
+    spawn_basic_bullet, spawn_classic_bullet, spawn_spread_bullet, Controller,
+};
+
+/// How the arrow keys move the ship
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Flight {
+    /// The ship moves in the direction of the arrow key at a fixed speed
+    Fixed,
+    /// The ship rotates to face the heading and thrusts forward, keeping its speed until drag slows it down
+    Free(Heading),
+}
 
+/// Controls the player's ship, the ship itself is an entity in the world
 pub struct Player {
-    pub drawable: DrawableState,
-    pub health: u32,
-    pub bullet_entity_controller: EntityController<Bullet>,
+    pub entity: Entity,
+    pub flight: Flight,
+    /// The velocity of a free flying ship in cells per second, kept in fractions of a cell so drag and diagonal thrust aren't lost
+    /// to rounding every frame. The world only gets the rounded velocity.
+    free_velocity: (f64, f64),
+    bindings: KeyBindings,
+    // Kept so bullets fired by the player use the same theme
+    theme: Theme,
 }
 
-const WIDTH_MAX_VELOCITY: i64 = 33;
-const HEIGHT_MAX_VELOCITY: i64 = 20;
+pub(super) const WIDTH_MAX_VELOCITY: i64 = 33;
+pub(super) const HEIGHT_MAX_VELOCITY: i64 = 20;
 
-trait CanSpawn {
-    fn get_spawnable_entities<T>(&self) -> Spawnable<T>;
-}
+const SPACE_SHIP_SPRITE: &str = "spaceship";
+const EXHAUST_FRAME_DURATION: u128 = 100;
+// The nose of the ship, this is where bullets are fired from
+const SPACE_SHIP_ANCHOR: Point<i64> = Point::new(4, 0);
+
+const CLASSIC_SHIP_SPRITE: &str = "classic_ship";
+// The center of the ship, the ship rotates around it
+const CLASSIC_SHIP_ANCHOR: Point<i64> = Point::new(1, 1);
+
+/// The speed gained with each thrust, in cells per second
+const THRUST_SPEED: f64 = 8.0;
+/// The fraction of the speed lost each second
+const DRAG_PER_SECOND: f64 = 0.6;
+const CLASSIC_BULLET_SPEED: f64 = 60.0;
+/// How far from the center of the ship bullets are fired, so they start just past the nose
+const CLASSIC_BULLET_OFFSET: f64 = 2.0;
 
 impl Player {
-    pub fn new(mut default_position: Option<Point<i64>>, initial_health: u32) -> Self {
+    /// Spawns the player's ship into the world
+    pub fn new(
+        world: &mut World,
+        mut default_position: Option<Point<i64>>,
+        initial_health: u32,
+        theme: &Theme,
+    ) -> Self {
         if default_position.is_none() {
             default_position = Some(Point {
                 width: 5,
@@ -30,89 +69,374 @@ impl Player {
             });
         }
 
-        let layout = Layout::from_ascii(SPACE_SHIP, Color::Green);
+        let sprite = load_sprite_or_default(SPACE_SHIP_SPRITE, || {
+            Sprite::from_ascii_frames(
+                &SPACE_SHIP_FRAMES,
+                Color::Green,
+                EXHAUST_FRAME_DURATION,
+                SPACE_SHIP_ANCHOR,
+            )
+        })
+        .with_color(theme.player);
+
+        let entity = world
+            .spawn()
+            .with_position(default_position.unwrap())
+            .with_velocity(Point::default())
+            .with_sprite(sprite)
+            .with_health(initial_health)
+            .with_collider(CollisionLayer::Player)
+            .build();
 
         Self {
-            drawable: DrawableState::new(
-                layout,
-                default_position.unwrap(),
-                DrawableType::Player,
-                None,
-            ),
-            health: initial_health,
-            bullet_entity_controller: EntityController::new(),
+            entity,
+            flight: Flight::Fixed,
+            free_velocity: (0.0, 0.0),
+            bindings: ARROW_KEY_BINDINGS,
+            theme: *theme,
         }
     }
 
-    fn get_center_of_player(&self) -> Point<i64> {
-        self.drawable
-            .location
-            .add_width(self.drawable.layout.dimensions.width / 2 - 1)
+    /// Controls the ship with other keys, used when several players share a keyboard
+    pub fn with_key_bindings(mut self, bindings: KeyBindings) -> Self {
+        self.bindings = bindings;
+
+        self
     }
-}
 
-impl Drawable for Player {
-    fn get_drawable_state(&self) -> &DrawableState {
-        &self.drawable
+    /// Recolors the ship, used to tell the ships apart when several players are playing
+    pub fn with_color(self, world: &mut World, color: Option<Color>) -> Self {
+        if let (Some(sprite), Some(color)) = (world.sprites.get_mut(self.entity), color) {
+            sprite.animation.set_foreground(color);
+        }
+
+        self
     }
 
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
-        self.drawable.location = updated_position;
+    /// Switches the ship to free flight, facing up
+    pub fn with_free_flight(mut self, world: &mut World) -> Self {
+        let sprite = load_sprite_or_default(CLASSIC_SHIP_SPRITE, || {
+            Sprite::from_ascii_frames(&CLASSIC_SHIP_FRAMES, Color::Green, 0, CLASSIC_SHIP_ANCHOR)
+        })
+        .with_color(self.theme.player);
+
+        world.sprites.insert(self.entity, sprite);
+        self.set_heading(world, Heading::North);
 
         self
     }
-}
 
-impl Controller for Player {
-    fn up(&mut self) -> &mut Self {
-        self.drawable.velocity = Point::new(0, -HEIGHT_MAX_VELOCITY);
+    /// The health of the ship, a destroyed ship has no health
+    pub fn get_health(&self, world: &World) -> u32 {
+        world.healths.get(self.entity).copied().unwrap_or_default()
+    }
+
+    fn get_velocity(&self, world: &World) -> Point<i64> {
+        world
+            .velocities
+            .get(self.entity)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn set_velocity(&mut self, world: &mut World, velocity: Point<i64>) -> &mut Self {
+        if world.has_entity(self.entity) {
+            world.velocities.insert(self.entity, velocity);
+        }
 
         self
     }
 
-    fn down(&mut self) -> &mut Self {
-        self.drawable.velocity = Point::new(0, HEIGHT_MAX_VELOCITY);
+    /// Where bullets are fired from, the anchor of the ship's sprite
+    fn get_anchor_location(&self, world: &World) -> Option<Point<i64>> {
+        let location = world.positions.get(self.entity)?;
+        let sprite = world.sprites.get(self.entity)?;
+
+        Some(*location + sprite.anchor)
+    }
+
+    fn set_heading(&mut self, world: &mut World, heading: Heading) -> &mut Self {
+        self.flight = Flight::Free(heading);
+
+        if let Some(sprite) = world.sprites.get_mut(self.entity) {
+            sprite.animation.set_current_frame(heading.get_index());
+        }
 
         self
     }
 
-    fn left(&mut self) -> &mut Self {
-        self.drawable.velocity = Point::new(-WIDTH_MAX_VELOCITY, 0);
+    /// Slows the ship down when it is in free flight, the speed lost each second doesn't depend on the frame rate
+    pub fn handle_game_loop(&mut self, world: &mut World, game_loop_duration: u128) -> &mut Self {
+        if let Flight::Free(_) = self.flight {
+            let remaining = (1.0 - DRAG_PER_SECOND).powf(game_loop_duration as f64 / 1000.0);
+            let (width, height) = self.free_velocity;
+
+            self.set_free_velocity(world, (width * remaining, height * remaining));
+        }
 
         self
     }
 
-    fn right(&mut self) -> &mut Self {
-        self.drawable.velocity = Point::new(WIDTH_MAX_VELOCITY, 0);
+    /// Sets the velocity of a free flying ship, the ship stops once its speed rounds to zero
+    fn set_free_velocity(&mut self, world: &mut World, velocity: (f64, f64)) -> &mut Self {
+        self.free_velocity = velocity;
+
+        self.set_velocity(
+            world,
+            Point::new(velocity.0.round() as i64, velocity.1.round() as i64),
+        )
+    }
+
+    /// Adds speed in the direction the ship is facing, up to the max velocity
+    fn thrust(&mut self, world: &mut World, heading: Heading) -> &mut Self {
+        let (width, height) = heading.get_velocity(THRUST_SPEED);
+        let (velocity_width, velocity_height) = self.free_velocity;
+
+        self.set_free_velocity(
+            world,
+            (
+                (velocity_width + width)
+                    .clamp(-WIDTH_MAX_VELOCITY as f64, WIDTH_MAX_VELOCITY as f64),
+                (velocity_height + height)
+                    .clamp(-HEIGHT_MAX_VELOCITY as f64, HEIGHT_MAX_VELOCITY as f64),
+            ),
+        )
+    }
+
+    /// Fires a bullet in the direction the ship is facing, the bullet keeps the velocity of the ship
+    fn fire_classic_bullet(&mut self, world: &mut World, heading: Heading) -> &mut Self {
+        let Some(anchor_location) = self.get_anchor_location(world) else {
+            return self;
+        };
+
+        let (offset_width, offset_height) = heading.get_unit_vector();
+        let (velocity_width, velocity_height) = heading.get_velocity(CLASSIC_BULLET_SPEED);
+
+        let location = anchor_location
+            + Point::new(
+                (offset_width * CLASSIC_BULLET_OFFSET).round() as i64,
+                (offset_height * CLASSIC_BULLET_OFFSET).round() as i64,
+            );
+
+        let velocity = self.get_velocity(world)
+            + Point::new(
+                velocity_width.round() as i64,
+                velocity_height.round() as i64,
+            );
+
+        let bullet = spawn_classic_bullet(world, location, velocity, &self.theme);
+        world.owners.insert(bullet, self.entity);
 
         self
     }
+}
+
+impl Controller for Player {
+    fn get_key_bindings(&self) -> KeyBindings {
+        self.bindings
+    }
+
+    fn up(&mut self, world: &mut World) -> &mut Self {
+        match self.flight {
+            Flight::Fixed => self.set_velocity(world, Point::new(0, -HEIGHT_MAX_VELOCITY)),
+            Flight::Free(heading) => self.thrust(world, heading),
+        }
+    }
+
+    fn down(&mut self, world: &mut World) -> &mut Self {
+        match self.flight {
+            Flight::Fixed => self.set_velocity(world, Point::new(0, HEIGHT_MAX_VELOCITY)),
+            // Braking halves the speed rather than reversing the ship
+            Flight::Free(_) => {
+                let velocity = self.get_velocity(world);
+
+                self.set_velocity(world, velocity / 2_i64.into())
+            }
+        }
+    }
+
+    fn left(&mut self, world: &mut World) -> &mut Self {
+        match self.flight {
+            Flight::Fixed => self.set_velocity(world, Point::new(-WIDTH_MAX_VELOCITY, 0)),
+            Flight::Free(heading) => self.set_heading(world, heading.rotate_counter_clockwise()),
+        }
+    }
 
-    fn additional_event_logic(&mut self, event: &crossterm::event::Event) -> &mut Self {
-        if event == &create_event(KeyCode::Char('q')) {
-            self.bullet_entity_controller
-                .spawn_entity(Bullet::build_basic_bullet(
-                    self.get_center_of_player().sub_height(2),
-                ));
-        } else if event == &create_event(KeyCode::Char(' ')) {
-            self.bullet_entity_controller
-                .spawn_entity(Bullet::build_spread_bullet(
-                    self.get_center_of_player().sub_width(4).sub_height(3),
-                ));
+    fn right(&mut self, world: &mut World) -> &mut Self {
+        match self.flight {
+            Flight::Fixed => self.set_velocity(world, Point::new(WIDTH_MAX_VELOCITY, 0)),
+            Flight::Free(heading) => self.set_heading(world, heading.rotate_clockwise()),
         }
+    }
+
+    fn additional_event_logic(
+        &mut self,
+        event: &crossterm::event::Event,
+        world: &mut World,
+    ) -> &mut Self {
+        let is_fire_event = event == &create_event(self.bindings.fire)
+            || event == &create_event(self.bindings.special);
+
+        if let Flight::Free(heading) = self.flight {
+            if is_fire_event {
+                self.fire_classic_bullet(world, heading);
+            }
+
+            return self;
+        }
+
+        // Bullets are fired from the nose of the ship, by lining up the anchors of the ship and the bullet
+        let Some(nose_location) = self.get_anchor_location(world) else {
+            return self;
+        };
+
+        let bullet = if event == &create_event(self.bindings.fire) {
+            spawn_basic_bullet(world, nose_location, &self.theme)
+        } else if event == &create_event(self.bindings.special) {
+            spawn_spread_bullet(world, nose_location, &self.theme)
+        } else {
+            return self;
+        };
+
+        world.owners.insert(bullet, self.entity);
 
         self
     }
 }
 
-impl Health for Player {
-    fn apply_damage(&mut self, damage: u32) -> &mut Self {
-        self.health = get_updated_health(self.health, damage);
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode};
 
-        self
+    use crate::{
+        api::display::{Layout, Point},
+        components::{CollisionLayer, Heading},
+        entities::{Controller, WASD_KEY_BINDINGS},
+        systems::run_lifetime_system,
+        user_display::Theme,
+        world::World,
+    };
+
+    use super::{Flight, Player};
+
+    fn get_free_flying_player(world: &mut World) -> Player {
+        Player::new(world, Some(Point::new(10, 10)), 3, &Theme::default()).with_free_flight(world)
+    }
+
+    fn get_layout(world: &World, player: &Player) -> Layout {
+        world
+            .sprites
+            .get(player.entity)
+            .unwrap()
+            .get_layout()
+            .clone()
+    }
+
+    #[test]
+    fn it_rotates_the_ship_and_shows_the_matching_frame() {
+        let mut world = World::new();
+        let mut player = get_free_flying_player(&mut world);
+        let north_layout = get_layout(&world, &player);
+
+        player.right(&mut world);
+
+        assert_eq!(player.flight, Flight::Free(Heading::NorthEast));
+        assert_ne!(get_layout(&world, &player), north_layout);
+
+        player.left(&mut world);
+
+        assert_eq!(player.flight, Flight::Free(Heading::North));
+        assert_eq!(get_layout(&world, &player), north_layout);
+    }
+
+    #[test]
+    fn it_keeps_thrust_until_drag_stops_the_ship() {
+        let mut world = World::new();
+        let mut player = get_free_flying_player(&mut world);
+
+        player
+            .right(&mut world)
+            .right(&mut world)
+            .up(&mut world)
+            .up(&mut world);
+
+        let velocity = player.get_velocity(&world);
+        assert_eq!(velocity, Point::new(16, 0));
+
+        player.handle_game_loop(&mut world, 100);
+
+        let slowed_velocity = player.get_velocity(&world);
+        assert!(slowed_velocity.width > 0 && slowed_velocity.width < velocity.width);
+
+        for _ in 0..100 {
+            player.handle_game_loop(&mut world, 100);
+        }
+
+        assert_eq!(player.get_velocity(&world), Point::new(0, 0));
+    }
+
+    #[test]
+    fn it_drifts_the_same_at_any_frame_rate() {
+        let get_velocity_after_a_second = |frame_duration: u128| {
+            let mut world = World::new();
+            let mut player = get_free_flying_player(&mut world);
+
+            player
+                .right(&mut world)
+                .right(&mut world)
+                .up(&mut world)
+                .up(&mut world);
+
+            for _ in 0..1000 / frame_duration {
+                player.handle_game_loop(&mut world, frame_duration);
+            }
+
+            player.get_velocity(&world)
+        };
+
+        // 60% of the speed is lost each second, so 40% of the 16 cells per second is left
+        assert_eq!(get_velocity_after_a_second(16), Point::new(6, 0));
+        assert_eq!(get_velocity_after_a_second(33), Point::new(6, 0));
+        assert_eq!(get_velocity_after_a_second(100), Point::new(6, 0));
     }
 
-    fn get_health(&self) -> u32 {
-        self.health
+    #[test]
+    fn it_fires_bullets_that_expire() {
+        let mut world = World::new();
+        let mut player = get_free_flying_player(&mut world);
+
+        player.additional_event_logic(&Event::Key(KeyCode::Char('q').into()), &mut world);
+
+        assert_eq!(world.count_on_layer(CollisionLayer::Ammunition), 1);
+
+        let bullet = world.get_entities()[1];
+        let bullet_velocity = *world.velocities.get(bullet).unwrap();
+        assert!(bullet_velocity.height < 0 && bullet_velocity.width == 0);
+
+        run_lifetime_system(&mut world, 5000);
+
+        assert_eq!(world.count_on_layer(CollisionLayer::Ammunition), 0);
+    }
+
+    #[test]
+    fn it_only_responds_to_its_own_key_bindings() {
+        let mut world = World::new();
+        let mut player = Player::new(&mut world, Some(Point::new(10, 10)), 3, &Theme::default())
+            .with_key_bindings(WASD_KEY_BINDINGS);
+
+        player.handle_event(&Event::Key(KeyCode::Up.into()), &mut world);
+        player.handle_event(&Event::Key(KeyCode::Char('q').into()), &mut world);
+
+        assert_eq!(player.get_velocity(&world), Point::new(0, 0));
+        assert_eq!(world.count_on_layer(CollisionLayer::Ammunition), 0);
+
+        player.handle_event(&Event::Key(KeyCode::Char('w').into()), &mut world);
+        player.handle_event(&Event::Key(KeyCode::Char('f').into()), &mut world);
+
+        assert!(player.get_velocity(&world).height < 0);
+
+        // Bullets remember which player fired them, so the points go to that player
+        let bullet = world.get_entities()[1];
+        assert_eq!(world.owners.get(bullet), Some(&player.entity));
     }
 }
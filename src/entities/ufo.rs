@@ -0,0 +1,51 @@
+use crossterm::style::Color;
+
+use crate::{
+    api::{assets::load_sprite_or_default, display::Point},
+    components::{CollisionLayer, Lifecycle, LifecycleContext, SpawnCommand, Sprite},
+    user_display::Theme,
+    world::{Entity, World},
+};
+
+use super::consts::UFO_FRAMES;
+
+pub const UFO_DAMAGE: u32 = 2;
+
+const UFO_SPRITE: &str = "ufo";
+const UFO_FRAME_DURATION: u128 = 150;
+const UFO_ANCHOR: Point<i64> = Point::new(4, 1);
+/// Saucers take a few hits to bring down
+const UFO_HEALTH: u32 = 3;
+
+/// Saucers explode when they are destroyed
+struct UfoLifecycle;
+
+impl Lifecycle for UfoLifecycle {
+    fn on_destroy(&self, context: &LifecycleContext) -> Vec<SpawnCommand> {
+        vec![SpawnCommand::Explosion(context.get_center())]
+    }
+}
+
+/// Spawns a flying saucer, an enemy that crosses the screen and takes several hits to destroy
+pub fn spawn_ufo(
+    world: &mut World,
+    location: Point<i64>,
+    velocity: Point<i64>,
+    theme: &Theme,
+) -> Entity {
+    let sprite = load_sprite_or_default(UFO_SPRITE, || {
+        Sprite::from_ascii_frames(&UFO_FRAMES, Color::Magenta, UFO_FRAME_DURATION, UFO_ANCHOR)
+    })
+    .with_color(theme.asteroid);
+
+    world
+        .spawn()
+        .with_position(location)
+        .with_velocity(velocity)
+        .with_sprite(sprite)
+        .with_health(UFO_HEALTH)
+        .with_collider(CollisionLayer::Enemy)
+        .with_damage(UFO_DAMAGE)
+        .with_lifecycle(UfoLifecycle)
+        .build()
+}
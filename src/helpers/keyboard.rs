@@ -15,3 +15,19 @@ pub fn get_keyboard_event(delay: u64) -> Result<Option<Event>> {
 
     Ok(None)
 }
+
+/// Waits up to the delay for a keyboard event, then takes every other event that is already waiting so players sharing the
+/// keyboard don't have to take turns
+pub fn get_keyboard_events(delay: u64) -> Result<Vec<Event>> {
+    let mut events = vec![];
+
+    if let Some(event) = get_keyboard_event(delay)? {
+        events.push(event);
+
+        while let Some(event) = get_keyboard_event(0)? {
+            events.push(event);
+        }
+    }
+
+    Ok(events)
+}
@@ -0,0 +1,44 @@
+use std::{env, fs::OpenOptions, io::Write};
+
+use super::get_now;
+
+pub const LOG_FILE: &str = "rusty_asteroids.log";
+/// Environment variable that overrides the location of the log file
+pub const LOG_FILE_VARIABLE: &str = "RUSTY_ASTEROIDS_LOG";
+
+/// Appends a warning to the log file, `rusty_asteroids.log` in the working directory unless the environment variable sets another
+/// location. The game takes over the terminal, so warnings can't be printed while it is running.
+pub fn log_warning(message: &str) {
+    let path = env::var(LOG_FILE_VARIABLE).unwrap_or(LOG_FILE.to_string());
+
+    // Failing to log shouldn't stop the game
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "[{}] WARN {}", get_now(), message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use uuid::Uuid;
+
+    use super::{log_warning, LOG_FILE_VARIABLE};
+
+    #[test]
+    fn it_appends_warnings_to_the_log_file() {
+        let path = env::temp_dir().join(format!("rusty_asteroids_{}.log", Uuid::new_v4()));
+
+        // Tests log to the temporary directory so they don't leave a log file in the working directory
+        env::set_var(LOG_FILE_VARIABLE, &path);
+
+        log_warning("first");
+        log_warning("second");
+
+        let log = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(log.contains("WARN first\n"));
+        assert!(log.contains("WARN second\n"));
+    }
+}
@@ -1,7 +1,9 @@
 mod drawing;
 mod keyboard;
+mod logger;
 mod system_time;
 
 pub use drawing::*;
 pub use keyboard::*;
+pub use logger::*;
 pub use system_time::*;
@@ -0,0 +1,131 @@
+use crate::{api::display::Point, entities::BossKind, systems::SpawnKind};
+
+/// The edge of the play field an event spawns from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Edge {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "top" => Some(Self::Top),
+            "bottom" => Some(Self::Bottom),
+            "left" => Some(Self::Left),
+            "right" => Some(Self::Right),
+            _ => None,
+        }
+    }
+}
+
+/// How a group of entities is arranged, the spacing is the number of cells between each entity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Formation {
+    /// Side by side along the edge
+    Line { count: usize, spacing: i64 },
+    /// One behind the other, so they arrive in a stream
+    Column { count: usize, spacing: i64 },
+    /// An arrowhead with the first entity leading and the rest trailing behind on either side
+    V { count: usize, spacing: i64 },
+}
+
+impl Formation {
+    /// The offset of each entity in the formation, as the distance along the edge and the distance behind the edge
+    pub fn get_offsets(&self) -> Vec<(i64, i64)> {
+        match *self {
+            Formation::Line { count, spacing } => {
+                let start = -(count as i64 - 1) * spacing / 2;
+
+                (0..count as i64)
+                    .map(|index| (start + index * spacing, 0))
+                    .collect()
+            }
+            Formation::Column { count, spacing } => (0..count as i64)
+                .map(|index| (0, index * spacing))
+                .collect(),
+            Formation::V { count, spacing } => (0..count as i64)
+                .map(|index| {
+                    let row = (index + 1) / 2;
+                    let side = if index % 2 == 0 { 1 } else { -1 };
+
+                    (row * side * spacing, row * spacing)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Something that happens at a point on the level's timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelEvent {
+    /// When the event happens, in milliseconds from the start of the level
+    pub time: u128,
+    pub kind: SpawnKind,
+    pub edge: Edge,
+    /// Where along the edge the entity spawns, as a percentage of the edge's length
+    pub position: i64,
+    pub velocity: Point<i64>,
+    /// Spawns a group of entities instead of a single entity
+    pub formation: Option<Formation>,
+}
+
+/// A boss appearing at a point on the level's timeline, the boss waits until the previous boss has been defeated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BossEvent {
+    /// In milliseconds from the start of the level
+    pub time: u128,
+    pub kind: BossKind,
+}
+
+/// What the player has to do to complete the level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WinCondition {
+    /// Stay alive for the duration, in milliseconds
+    Survive(u128),
+    /// Reach the score
+    Score(u64),
+    /// Destroy the number of enemies
+    Destroy(u32),
+    /// Destroy every enemy once the timeline has finished
+    Clear,
+}
+
+/// An authored level, a timeline of events that replaces the random spawning of the game modes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub name: String,
+    pub win_condition: WinCondition,
+    /// Sorted by time
+    pub events: Vec<LevelEvent>,
+    /// Sorted by time
+    pub bosses: Vec<BossEvent>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Formation;
+
+    #[test]
+    fn it_arranges_formations_around_the_first_entity() {
+        assert_eq!(
+            Formation::Line {
+                count: 3,
+                spacing: 4
+            }
+            .get_offsets(),
+            vec![(-4, 0), (0, 0), (4, 0)]
+        );
+
+        assert_eq!(
+            Formation::V {
+                count: 3,
+                spacing: 2
+            }
+            .get_offsets(),
+            vec![(0, 0), (-2, 2), (2, 2)]
+        );
+    }
+}
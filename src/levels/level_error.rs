@@ -0,0 +1,86 @@
+use std::{fmt::Display, io};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelError {
+    FileError(io::ErrorKind),
+    /// The line number and contents of a header line that couldn't be parsed
+    InvalidHeader(usize, String),
+    InvalidWinCondition(usize, String),
+    /// The line number and contents of an event line that couldn't be parsed
+    InvalidEvent(usize, String),
+    UnknownEntity(usize, String),
+    UnknownBoss(usize, String),
+    UnknownEdge(usize, String),
+    UnknownFormation(usize, String),
+    /// The line number and position of an event that isn't between 0 and 100 percent
+    PositionOutOfRange(usize, i64),
+    /// The line number of an event that happens before the event above it
+    EventOutOfOrder(usize),
+    MissingName,
+    MissingWinCondition,
+    MissingEvents,
+}
+
+impl Display for LevelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FileError(kind) => write!(f, "Couldn't read the level file: {}", kind),
+            Self::InvalidHeader(line, contents) => write!(
+                f,
+                "Line {}: expected `name: <name>` or `win: <condition>`, found `{}`",
+                line, contents
+            ),
+            Self::InvalidWinCondition(line, contents) => write!(
+                f,
+                "Line {}: expected the win condition to be `survive <seconds>`, `score <points>`, `destroy <count>` or `clear`, found `{}`",
+                line, contents
+            ),
+            Self::InvalidEvent(line, contents) => write!(
+                f,
+                "Line {}: expected `at <seconds>: <entity> <edge> <position> [velocity <width> <height>] [formation <shape> <count> <spacing>]` or `at <seconds>: boss <name>`, found `{}`",
+                line, contents
+            ),
+            Self::UnknownEntity(line, name) => write!(
+                f,
+                "Line {}: unknown entity `{}`, expected asteroid, ufo or pickup",
+                line, name
+            ),
+            Self::UnknownBoss(line, name) => write!(
+                f,
+                "Line {}: unknown boss `{}`, expected mothership",
+                line, name
+            ),
+            Self::UnknownEdge(line, name) => write!(
+                f,
+                "Line {}: unknown edge `{}`, expected top, bottom, left or right",
+                line, name
+            ),
+            Self::UnknownFormation(line, name) => write!(
+                f,
+                "Line {}: unknown formation `{}`, expected line, column or v",
+                line, name
+            ),
+            Self::PositionOutOfRange(line, position) => write!(
+                f,
+                "Line {}: the position {} is outside of the edge, expected a percentage between 0 and 100",
+                line, position
+            ),
+            Self::EventOutOfOrder(line) => write!(
+                f,
+                "Line {}: the event happens before the event above it, events must be in time order",
+                line
+            ),
+            Self::MissingName => write!(f, "The level is missing a `name:` line"),
+            Self::MissingWinCondition => write!(f, "The level is missing a `win:` line"),
+            Self::MissingEvents => write!(f, "The level doesn't have any `at` events"),
+        }
+    }
+}
+
+impl From<io::Error> for LevelError {
+    fn from(error: io::Error) -> Self {
+        Self::FileError(error.kind())
+    }
+}
+
+pub type LevelResult<T> = Result<T, LevelError>;
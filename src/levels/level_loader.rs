@@ -0,0 +1,395 @@
+use std::{env, fs, path::PathBuf};
+
+use crate::{api::display::Point, entities::BossKind, systems::SpawnKind};
+
+use super::{BossEvent, Edge, Formation, Level, LevelError, LevelEvent, LevelResult, WinCondition};
+
+pub const LEVELS_DIRECTORY: &str = "levels";
+/// Environment variable that overrides the levels directory, useful when running the game outside of the repository
+pub const LEVELS_DIRECTORY_VARIABLE: &str = "RUSTY_ASTEROIDS_LEVELS";
+const LEVEL_EXTENSION: &str = "level";
+
+const EVENT_PREFIX: &str = "at ";
+
+/// A level file from the levels directory, invalid files are kept so their errors can be shown
+pub struct LevelFile {
+    pub file_name: String,
+    pub level: LevelResult<Level>,
+}
+
+pub fn get_levels_directory() -> PathBuf {
+    PathBuf::from(env::var(LEVELS_DIRECTORY_VARIABLE).unwrap_or(LEVELS_DIRECTORY.to_string()))
+}
+
+pub fn load_level(path: &PathBuf) -> LevelResult<Level> {
+    parse_level(&fs::read_to_string(path)?)
+}
+
+/// Loads every level in the levels directory, sorted by file name. A missing directory has no levels.
+pub fn load_levels() -> Vec<LevelFile> {
+    let Ok(entries) = fs::read_dir(get_levels_directory()) else {
+        return vec![];
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == LEVEL_EXTENSION)
+        })
+        .collect();
+
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| LevelFile {
+            file_name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            level: load_level(path),
+        })
+        .collect()
+}
+
+/// Parses seconds, which can be fractional, into milliseconds
+fn parse_seconds(value: &str) -> Option<u128> {
+    let seconds: f64 = value.parse().ok()?;
+
+    if !seconds.is_finite() || seconds < 0. {
+        return None;
+    }
+
+    Some((seconds * 1000.).round() as u128)
+}
+
+fn parse_win_condition(value: &str) -> Option<WinCondition> {
+    let mut parts = value.split_whitespace();
+
+    let condition = match (parts.next()?, parts.next()) {
+        ("survive", Some(seconds)) => WinCondition::Survive(parse_seconds(seconds)?),
+        ("score", Some(points)) => WinCondition::Score(points.parse().ok()?),
+        ("destroy", Some(count)) => WinCondition::Destroy(count.parse().ok()?),
+        ("clear", None) => WinCondition::Clear,
+        _ => return None,
+    };
+
+    match parts.next() {
+        Some(_) => None,
+        None => Some(condition),
+    }
+}
+
+fn parse_entity(name: &str) -> Option<SpawnKind> {
+    match name {
+        "asteroid" => Some(SpawnKind::Asteroid),
+        "ufo" => Some(SpawnKind::Ufo),
+        "pickup" => Some(SpawnKind::Pickup),
+        _ => None,
+    }
+}
+
+/// A line of the timeline
+enum TimelineEvent {
+    Spawn(LevelEvent),
+    Boss(BossEvent),
+}
+
+/// Parses the line of an event, the `at` prefix has already been removed
+fn parse_event(line_number: usize, line: &str, details: &str) -> LevelResult<TimelineEvent> {
+    let invalid_event = || LevelError::InvalidEvent(line_number, line.to_string());
+
+    let (time, description) = details.split_once(':').ok_or_else(invalid_event)?;
+    let time = parse_seconds(time.trim()).ok_or_else(invalid_event)?;
+
+    let parts: Vec<&str> = description.split_whitespace().collect();
+
+    if let ["boss", name] = parts[..] {
+        let kind = BossKind::from_name(name)
+            .ok_or(LevelError::UnknownBoss(line_number, name.to_string()))?;
+
+        return Ok(TimelineEvent::Boss(BossEvent { time, kind }));
+    }
+
+    let [entity, edge, position, options @ ..] = &parts[..] else {
+        return Err(invalid_event());
+    };
+
+    let kind =
+        parse_entity(entity).ok_or(LevelError::UnknownEntity(line_number, entity.to_string()))?;
+    let edge =
+        Edge::from_name(edge).ok_or(LevelError::UnknownEdge(line_number, edge.to_string()))?;
+    let position: i64 = position.parse().map_err(|_| invalid_event())?;
+
+    if !(0..=100).contains(&position) {
+        return Err(LevelError::PositionOutOfRange(line_number, position));
+    }
+
+    let mut velocity = Point::default();
+    let mut formation = None;
+    let mut options = options.iter();
+
+    while let Some(option) = options.next() {
+        let mut next_number = || -> LevelResult<i64> {
+            options
+                .next()
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(invalid_event)
+        };
+
+        match *option {
+            "velocity" => velocity = Point::new(next_number()?, next_number()?),
+            "formation" => {
+                let shape = options.next().ok_or_else(invalid_event)?;
+                let count = options
+                    .next()
+                    .and_then(|value| value.parse::<usize>().ok())
+                    .filter(|count| *count > 0)
+                    .ok_or_else(invalid_event)?;
+                let spacing = options
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .ok_or_else(invalid_event)?;
+
+                formation = Some(match *shape {
+                    "line" => Formation::Line { count, spacing },
+                    "column" => Formation::Column { count, spacing },
+                    "v" => Formation::V { count, spacing },
+                    _ => return Err(LevelError::UnknownFormation(line_number, shape.to_string())),
+                });
+            }
+            _ => return Err(invalid_event()),
+        }
+    }
+
+    Ok(TimelineEvent::Spawn(LevelEvent {
+        time,
+        kind,
+        edge,
+        position,
+        velocity,
+        formation,
+    }))
+}
+
+/// Parses the contents of a level file.
+///
+/// A level file has a `name: <name>` and a `win: <condition>` line, followed by a timeline of
+/// `at <seconds>: <entity> <edge> <position> [velocity <width> <height>] [formation <shape> <count> <spacing>]` lines in time order.
+/// The position is a percentage along the edge and the velocity is in cells per second. Bosses appear with `at <seconds>: boss <name>`.
+pub fn parse_level(contents: &str) -> LevelResult<Level> {
+    let mut name: Option<String> = None;
+    let mut win_condition: Option<WinCondition> = None;
+    let mut events: Vec<LevelEvent> = vec![];
+    let mut bosses: Vec<BossEvent> = vec![];
+    let mut last_time = 0;
+
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(details) = trimmed.strip_prefix(EVENT_PREFIX) {
+            let event = parse_event(line_number, line, details)?;
+
+            let time = match &event {
+                TimelineEvent::Spawn(event) => event.time,
+                TimelineEvent::Boss(event) => event.time,
+            };
+
+            if time < last_time {
+                return Err(LevelError::EventOutOfOrder(line_number));
+            }
+
+            last_time = time;
+
+            match event {
+                TimelineEvent::Spawn(event) => events.push(event),
+                TimelineEvent::Boss(event) => bosses.push(event),
+            }
+
+            continue;
+        }
+
+        let invalid_header = || LevelError::InvalidHeader(line_number, line.to_string());
+
+        let (key, value) = trimmed.split_once(':').ok_or_else(invalid_header)?;
+        let value = value.trim();
+
+        match key.trim() {
+            "name" if !value.is_empty() => name = Some(value.to_string()),
+            "win" => {
+                win_condition = Some(parse_win_condition(value).ok_or(
+                    LevelError::InvalidWinCondition(line_number, line.to_string()),
+                )?)
+            }
+            _ => return Err(invalid_header()),
+        }
+    }
+
+    let name = name.ok_or(LevelError::MissingName)?;
+    let win_condition = win_condition.ok_or(LevelError::MissingWinCondition)?;
+
+    if events.is_empty() && bosses.is_empty() {
+        return Err(LevelError::MissingEvents);
+    }
+
+    Ok(Level {
+        name,
+        win_condition,
+        events,
+        bosses,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::{
+        api::display::Point,
+        entities::BossKind,
+        levels::{BossEvent, Edge, Formation, LevelError, WinCondition},
+        systems::SpawnKind,
+    };
+
+    use super::{parse_level, LEVELS_DIRECTORY, LEVEL_EXTENSION};
+
+    const LEVEL: &str = "# A test level
+name: Test level
+win: survive 30
+
+at 0: asteroid top 50 velocity 0 10
+at 1.5: ufo left 20 velocity 15 0 formation column 3 12
+at 1.5: pickup bottom 100
+at 20: boss mothership";
+
+    #[test]
+    fn it_parses_the_header_and_timeline() {
+        let level = parse_level(LEVEL).unwrap();
+
+        assert_eq!(level.name, "Test level");
+        assert_eq!(level.win_condition, WinCondition::Survive(30000));
+        assert_eq!(level.events.len(), 3);
+
+        let event = &level.events[1];
+
+        assert_eq!(event.time, 1500);
+        assert_eq!(event.kind, SpawnKind::Ufo);
+        assert_eq!(event.edge, Edge::Left);
+        assert_eq!(event.position, 20);
+        assert_eq!(event.velocity, Point::new(15, 0));
+        assert_eq!(
+            event.formation,
+            Some(Formation::Column {
+                count: 3,
+                spacing: 12
+            })
+        );
+
+        // Events without a velocity stay still
+        assert_eq!(level.events[2].velocity, Point::default());
+
+        assert_eq!(
+            level.bosses,
+            vec![BossEvent {
+                time: 20000,
+                kind: BossKind::Mothership
+            }]
+        );
+    }
+
+    #[test]
+    fn it_returns_errors_for_invalid_levels() {
+        let header = "name: Test\nwin: clear\n";
+
+        let parse_events = |events: &str| parse_level(&format!("{}{}", header, events)).map(|_| ());
+
+        assert_eq!(
+            parse_level("speed: 10"),
+            Err(LevelError::InvalidHeader(1, "speed: 10".to_string()))
+        );
+
+        assert_eq!(
+            parse_level("name: Test\nwin: survive"),
+            Err(LevelError::InvalidWinCondition(
+                2,
+                "win: survive".to_string()
+            ))
+        );
+
+        assert_eq!(
+            parse_events("at 1: asteroid top"),
+            Err(LevelError::InvalidEvent(
+                3,
+                "at 1: asteroid top".to_string()
+            ))
+        );
+
+        assert_eq!(
+            parse_events("at 1: comet top 50"),
+            Err(LevelError::UnknownEntity(3, "comet".to_string()))
+        );
+
+        assert_eq!(
+            parse_events("at 1: boss comet"),
+            Err(LevelError::UnknownBoss(3, "comet".to_string()))
+        );
+
+        assert_eq!(
+            parse_events("at 2: boss mothership\nat 1: asteroid top 50"),
+            Err(LevelError::EventOutOfOrder(4))
+        );
+
+        assert_eq!(
+            parse_events("at 1: asteroid middle 50"),
+            Err(LevelError::UnknownEdge(3, "middle".to_string()))
+        );
+
+        assert_eq!(
+            parse_events("at 1: asteroid top 50 formation square 2 2"),
+            Err(LevelError::UnknownFormation(3, "square".to_string()))
+        );
+
+        assert_eq!(
+            parse_events("at 1: asteroid top 150"),
+            Err(LevelError::PositionOutOfRange(3, 150))
+        );
+
+        assert_eq!(
+            parse_events("at 2: asteroid top 50\nat 1: asteroid top 50"),
+            Err(LevelError::EventOutOfOrder(4))
+        );
+
+        assert_eq!(parse_level("win: clear"), Err(LevelError::MissingName));
+        assert_eq!(
+            parse_level("name: Test"),
+            Err(LevelError::MissingWinCondition)
+        );
+        assert_eq!(parse_events(""), Err(LevelError::MissingEvents));
+    }
+
+    #[test]
+    fn it_parses_the_bundled_levels() {
+        let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(LEVELS_DIRECTORY);
+
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path
+                .extension()
+                .is_some_and(|extension| extension == LEVEL_EXTENSION)
+            {
+                let contents = fs::read_to_string(&path).unwrap();
+
+                if let Err(error) = parse_level(&contents) {
+                    panic!("Invalid level {:?}: {}", path, error);
+                }
+            }
+        }
+    }
+}
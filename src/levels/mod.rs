@@ -0,0 +1,7 @@
+mod level;
+mod level_error;
+mod level_loader;
+
+pub use level::*;
+pub use level_error::*;
+pub use level_loader::*;
@@ -0,0 +1,18 @@
+//! Rusty Asteroids, an asteroids game drawn in the terminal.
+//!
+//! - `api` - drawing to the terminal (`Layout`, `Point`, `DisplayController`, `Output`) and loading sprites
+//! - `components` - the data entities are made of, such as sprites and collision layers
+//! - `systems` - the logic run on the world every frame, such as movement and collision detection
+//! - `entities` - the player, asteroids, bosses and the other things in the world
+//! - `app` - running a game, the menus and the settings
+
+pub mod api;
+pub mod app;
+pub mod components;
+pub mod entities;
+mod helpers;
+pub mod levels;
+pub mod network;
+pub mod systems;
+pub mod user_display;
+pub mod world;
@@ -1,22 +1,126 @@
-mod api;
-mod app;
-mod components;
-mod entities;
-mod helpers;
-mod systems;
-mod user_display;
+mod cli;
 
-use std::io::stdout;
+use std::env;
 
-use app::{App, AppManager};
+use games::{
+    api::display::{get_screen_size, ColorMode},
+    app::{
+        AppManager, ClientManager, HeadlessInput, HeadlessRunner, HeadlessSummary, InputScript,
+        Settings, SpectatorManager,
+    },
+    network::{NetworkClient, NetworkHost, SpectatorClient, SpectatorServer},
+};
 
-use crate::api::display::{get_screen_size, Output};
+use cli::{parse_arguments, CommandLine, DEFAULT_HEADLESS_SIZE, HELP};
 
-// Run tests with logging: cargo test -- --nocapture
-fn main() {
+/// Runs games without a terminal and prints the stats of every game
+fn run_headless(command_line: CommandLine, settings: Settings) -> Result<(), String> {
+    let input = match &command_line.script {
+        Some(path) => {
+            HeadlessInput::Script(InputScript::load(path).map_err(|error| error.to_string())?)
+        }
+        None => command_line.bot.unwrap_or_default(),
+    };
+
+    let mut runner =
+        HeadlessRunner::new(command_line.size.unwrap_or(DEFAULT_HEADLESS_SIZE), settings)
+            .with_options(command_line.options)
+            .map_err(|error| error.to_string())?
+            .with_input(input);
+
+    if let Some(ticks) = command_line.ticks {
+        runner = runner.with_max_frames(ticks);
+    }
+
+    let results = runner
+        .run(command_line.games.unwrap_or(1))
+        .map_err(|error| error.to_string())?;
+
+    println!(
+        "{}",
+        HeadlessSummary::new(&results).format(command_line.stats_format.unwrap_or_default())
+    );
+
+    Ok(())
+}
+
+fn run() -> Result<(), String> {
+    let command_line = parse_arguments(env::args().skip(1))?;
+
+    if command_line.help {
+        println!("{}", HELP);
+
+        return Ok(());
+    }
+
+    let settings = command_line.get_settings(Settings::load());
+
+    if command_line.headless {
+        return run_headless(command_line, settings);
+    }
+
+    let color_mode = command_line.color_mode.unwrap_or_else(ColorMode::detect);
     let screen_size = get_screen_size();
 
-    let mut app_manager = AppManager::new(screen_size).unwrap();
+    if let Some(address) = command_line.spectate {
+        let client = SpectatorClient::connect(&address).map_err(|error| error.to_string())?;
+
+        return SpectatorManager::new(screen_size, color_mode, client)
+            .run()
+            .map_err(|error| error.to_string());
+    }
+
+    // The play area is drawn from the top left of the terminal, so it has to fit inside it
+    let dimensions = match command_line.size {
+        Some(size) if size.width > screen_size.width || size.height > screen_size.height => {
+            return Err(format!(
+                "--size {}x{} doesn't fit the {}x{} terminal",
+                size.width, size.height, screen_size.width, screen_size.height
+            ))
+        }
+        Some(size) => size,
+        None => screen_size,
+    };
+
+    // The client is connected before the terminal is taken over, so a host that can't be reached is a plain error
+    if let Some(address) = command_line.connect {
+        let client =
+            NetworkClient::connect(&address, dimensions).map_err(|error| error.to_string())?;
+
+        return ClientManager::new(dimensions, color_mode, settings, client)
+            .and_then(|mut client_manager| client_manager.run())
+            .map_err(|error| error.to_string());
+    }
+
+    let network_host = match command_line.host {
+        Some(address) => Some(NetworkHost::bind(&address).map_err(|error| error.to_string())?),
+        None => None,
+    };
+
+    let spectator_server = match command_line.spectators {
+        Some(address) => Some(SpectatorServer::bind(&address).map_err(|error| error.to_string())?),
+        None => None,
+    };
+
+    AppManager::new(dimensions, color_mode, settings)
+        .and_then(|app_manager| app_manager.with_options(command_line.options))
+        .map(|app_manager| match network_host {
+            Some(network_host) => app_manager.with_network_host(network_host),
+            None => app_manager,
+        })
+        .map(|app_manager| match spectator_server {
+            Some(spectator_server) => app_manager.with_spectator_server(spectator_server),
+            None => app_manager,
+        })
+        .and_then(|mut app_manager| app_manager.run())
+        .map_err(|error| error.to_string())
+}
+
+// Run tests with logging: cargo test -- --nocapture
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("{}", error);
 
-    app_manager.run();
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,63 @@
+use std::net::TcpStream;
+
+use crate::{api::display::Point, entities::Action};
+
+use super::{
+    connection::Connection,
+    network_error::{NetworkError, NetworkResult},
+    protocol::{ClientMessage, HostMessage, HostMessageParser, PROTOCOL_VERSION},
+};
+
+/// Plays a game run by a host, sending the actions of the second ship and receiving what to draw
+pub struct NetworkClient {
+    connection: Connection,
+    parser: HostMessageParser,
+}
+
+impl NetworkClient {
+    /// Connects to a host, e.g. `127.0.0.1:7878`, and asks to join its game. Snapshots with more cells than a screen of the
+    /// dimensions has are refused.
+    pub fn connect(address: &str, dimensions: Point<i64>) -> NetworkResult<Self> {
+        let mut connection = Connection::new(TcpStream::connect(address)?)?;
+
+        connection.send_lines(&[ClientMessage::Join(PROTOCOL_VERSION).encode()])?;
+
+        Ok(Self {
+            connection,
+            parser: HostMessageParser::new(dimensions),
+        })
+    }
+
+    pub fn send_action(&mut self, action: Action) -> NetworkResult<()> {
+        self.connection
+            .send_lines(&[ClientMessage::Action(action).encode()])
+    }
+
+    /// Tells the host the client is leaving, so the host doesn't wait for the connection to time out
+    pub fn leave(&mut self) {
+        let _ = self.connection.send_lines(&[ClientMessage::Leave.encode()]);
+    }
+
+    /// Returns the messages received since the last call, the host refusing the client or closing the connection is an error
+    pub fn receive(&mut self) -> NetworkResult<Vec<HostMessage>> {
+        let mut messages = vec![];
+
+        for line in self.connection.receive_lines()? {
+            match self.parser.parse_line(&line)? {
+                Some(HostMessage::Welcome(version)) if version != PROTOCOL_VERSION => {
+                    return Err(NetworkError::VersionMismatch(version, PROTOCOL_VERSION));
+                }
+                Some(HostMessage::Welcome(_)) => {}
+                Some(HostMessage::Full) => return Err(NetworkError::HostFull),
+                Some(message) => messages.push(message),
+                None => {}
+            }
+        }
+
+        if messages.is_empty() && self.connection.is_closed() {
+            return Err(NetworkError::Disconnected);
+        }
+
+        Ok(messages)
+    }
+}
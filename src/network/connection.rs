@@ -0,0 +1,207 @@
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::TcpStream,
+};
+
+use super::network_error::{NetworkError, NetworkResult};
+
+const READ_BUFFER_SIZE: usize = 4096;
+/// A peer that stops reading is disconnected once this much is waiting to be sent, several whole frames of a large terminal
+const MAX_QUEUED_BYTES: usize = 4 * 1024 * 1024;
+/// The longest line a peer can send, every message of the protocols is much shorter
+const MAX_LINE_LENGTH: usize = 1024;
+
+/// A TCP stream that sends and receives lines of text without blocking the game loop
+pub struct Connection {
+    stream: TcpStream,
+    /// Bytes received after the last complete line
+    buffer: Vec<u8>,
+    /// Bytes waiting for the socket to take them
+    outgoing: Vec<u8>,
+    closed: bool,
+}
+
+impl Connection {
+    pub fn new(stream: TcpStream) -> NetworkResult<Self> {
+        stream.set_nonblocking(true)?;
+        // Snapshots are sent every frame, waiting to fill a packet would add a frame of lag
+        stream.set_nodelay(true)?;
+
+        Ok(Self {
+            stream,
+            buffer: vec![],
+            outgoing: vec![],
+            closed: false,
+        })
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Queues the lines and writes as much of the queue as the socket takes without blocking, the rest is written by the next
+    /// call. A peer that stops reading is disconnected once too much is waiting, so it can never stall the game loop.
+    pub fn send_lines(&mut self, lines: &[String]) -> NetworkResult<()> {
+        if self.closed {
+            return Err(NetworkError::Disconnected);
+        }
+
+        for line in lines {
+            self.outgoing.extend_from_slice(line.as_bytes());
+            self.outgoing.push(b'\n');
+        }
+
+        self.flush()?;
+
+        if self.outgoing.len() > MAX_QUEUED_BYTES {
+            self.close();
+
+            return Err(NetworkError::SendQueueFull);
+        }
+
+        Ok(())
+    }
+
+    /// Writes the queued bytes until the socket would block
+    fn flush(&mut self) -> NetworkResult<()> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => {
+                    self.close();
+
+                    return Err(NetworkError::Disconnected);
+                }
+                Ok(count) => {
+                    self.outgoing.drain(..count);
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.close();
+
+                    return Err(error.into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Frees the queued bytes, a closed connection never sends or receives again
+    fn close(&mut self) {
+        self.closed = true;
+        self.buffer.clear();
+        self.outgoing.clear();
+    }
+
+    /// Returns every complete line received since the last call, a partial line is kept until the rest of it arrives. A peer that
+    /// sends a line longer than the limit is disconnected, so it can't fill the memory with a line that never ends.
+    pub fn receive_lines(&mut self) -> NetworkResult<Vec<String>> {
+        let mut read_buffer = [0; READ_BUFFER_SIZE];
+        let mut lines = vec![];
+
+        while !self.closed {
+            match self.stream.read(&mut read_buffer) {
+                Ok(0) => self.closed = true,
+                Ok(count) => {
+                    self.buffer.extend_from_slice(&read_buffer[..count]);
+
+                    // Lines are taken as they arrive so only the partial line has to be checked
+                    lines.extend(self.take_lines()?);
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.close();
+
+                    return Err(error.into());
+                }
+            }
+        }
+
+        Ok(lines)
+    }
+
+    /// Removes the complete lines from the buffer
+    fn take_lines(&mut self) -> NetworkResult<Vec<String>> {
+        let mut lines = vec![];
+
+        while let Some(index) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            if index > MAX_LINE_LENGTH {
+                break;
+            }
+
+            let line: Vec<u8> = self.buffer.drain(..=index).collect();
+
+            lines.push(String::from_utf8_lossy(&line[..index]).to_string());
+        }
+
+        if self.buffer.len() > MAX_LINE_LENGTH {
+            self.close();
+
+            return Err(NetworkError::LineTooLong(MAX_LINE_LENGTH));
+        }
+
+        Ok(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        net::{TcpListener, TcpStream},
+        thread::sleep,
+        time::Duration,
+    };
+
+    use crate::network::NetworkError;
+
+    use super::{Connection, MAX_LINE_LENGTH};
+
+    /// A connection and the stream of the peer it is connected to
+    fn get_connected_pair() -> (Connection, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let peer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+
+        (Connection::new(stream).unwrap(), peer)
+    }
+
+    #[test]
+    fn it_disconnects_a_peer_that_stops_reading() {
+        let (mut connection, _peer) = get_connected_pair();
+        let lines = vec!["x".repeat(MAX_LINE_LENGTH); 1000];
+
+        // Each call only writes what the socket takes, so this returns even though the peer never reads
+        let result = (0..1000)
+            .map(|_| connection.send_lines(&lines))
+            .find(|result| result.is_err());
+
+        assert_eq!(result, Some(Err(NetworkError::SendQueueFull)));
+        assert!(connection.is_closed());
+    }
+
+    #[test]
+    fn it_disconnects_a_peer_that_sends_a_line_that_is_too_long() {
+        let (mut connection, mut peer) = get_connected_pair();
+
+        peer.write_all(b"action fire\n").unwrap();
+        peer.write_all(&vec![b'x'; MAX_LINE_LENGTH * 4]).unwrap();
+
+        let mut result = Ok(vec![]);
+
+        for _ in 0..100 {
+            result = connection.receive_lines();
+
+            if result.is_err() {
+                break;
+            }
+
+            sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(result, Err(NetworkError::LineTooLong(MAX_LINE_LENGTH)));
+        assert!(connection.is_closed());
+    }
+}
@@ -0,0 +1,204 @@
+//! The messages sent to spectators. Spectators are sent every frame the game prints, as the elements that changed since the
+//! previous frame, and never send anything back.
+
+use crate::api::display::{Element, Layout, Point};
+
+use super::{
+    network_error::{NetworkError, NetworkResult},
+    protocol::{decode_color, encode_color},
+};
+
+/// The elements of a printed frame that changed since the previous frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameUpdate {
+    /// The dimensions of the printed layout, a spectator clears its screen when they change
+    pub dimensions: Point<i64>,
+    pub changes: Vec<(Point<i64>, Option<Element>)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpectatorMessage {
+    /// Sent when a spectator connects, with the protocol version of the game
+    Welcome(u32),
+    Frame(FrameUpdate),
+}
+
+impl FrameUpdate {
+    /// Returns the changes between two frames, a spectator without a previous frame is sent every element that is drawn
+    pub fn new(layout: &Layout, previous: Option<&Layout>) -> Self {
+        let changes = match previous {
+            Some(previous) => layout.get_changed_elements(previous),
+            None => layout
+                .get_changed_elements(&Layout::new(&Point::default(), None))
+                .into_iter()
+                .filter(|(_, element)| element.is_some())
+                .collect(),
+        };
+
+        Self {
+            dimensions: layout.dimensions,
+            changes,
+        }
+    }
+
+    /// Applies the changes to the layout of a spectator, changes outside the layout are skipped so a smaller terminal shows the
+    /// top left of the game
+    pub fn apply(&self, layout: &mut Layout, previous_dimensions: Option<Point<i64>>) {
+        if previous_dimensions != Some(self.dimensions) {
+            layout.reset();
+        }
+
+        for (location, element) in &self.changes {
+            if let Ok(existing_element) = layout.get_element_mut(location) {
+                *existing_element = *element;
+            }
+        }
+    }
+}
+
+impl SpectatorMessage {
+    pub fn encode(&self) -> Vec<String> {
+        match self {
+            Self::Welcome(version) => vec![format!("spectate {}", version)],
+            Self::Frame(update) => {
+                let mut lines = vec![format!(
+                    "frame {} {}",
+                    update.dimensions.width, update.dimensions.height
+                )];
+
+                lines.extend(
+                    update
+                        .changes
+                        .iter()
+                        .map(|(location, element)| match element {
+                            Some(element) => format!(
+                                "set {} {} {} {} {}",
+                                location.width,
+                                location.height,
+                                element.value as u32,
+                                encode_color(element.foreground),
+                                encode_color(element.background)
+                            ),
+                            None => format!("clear {} {}", location.width, location.height),
+                        }),
+                );
+
+                lines.push("end".to_string());
+
+                lines
+            }
+        }
+    }
+}
+
+/// Parses the lines sent to a spectator, a frame is only returned once its last line has arrived
+#[derive(Default)]
+pub struct SpectatorMessageParser {
+    update: Option<FrameUpdate>,
+}
+
+impl SpectatorMessageParser {
+    pub fn parse_line(&mut self, line: &str) -> NetworkResult<Option<SpectatorMessage>> {
+        let invalid = || NetworkError::InvalidMessage(line.to_string());
+        let parse_number = |value: &str| value.parse::<i64>().map_err(|_| invalid());
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        if let Some(update) = &mut self.update {
+            match parts.as_slice() {
+                ["set", width, height, value, foreground, background] => update.changes.push((
+                    Point::new(parse_number(width)?, parse_number(height)?),
+                    Some(Element::new(
+                        value
+                            .parse()
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(invalid)?,
+                        decode_color(background).ok_or_else(invalid)?,
+                        decode_color(foreground).ok_or_else(invalid)?,
+                    )),
+                )),
+                ["clear", width, height] => update.changes.push((
+                    Point::new(parse_number(width)?, parse_number(height)?),
+                    None,
+                )),
+                ["end"] => return Ok(self.update.take().map(SpectatorMessage::Frame)),
+                _ => return Err(invalid()),
+            }
+
+            return Ok(None);
+        }
+
+        match parts.as_slice() {
+            ["spectate", version] => Ok(Some(SpectatorMessage::Welcome(
+                version.parse().map_err(|_| invalid())?,
+            ))),
+            ["frame", width, height] => {
+                self.update = Some(FrameUpdate {
+                    dimensions: Point::new(parse_number(width)?, parse_number(height)?),
+                    changes: vec![],
+                });
+
+                Ok(None)
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::api::display::{Element, Layout, Point};
+
+    use super::{FrameUpdate, SpectatorMessage, SpectatorMessageParser};
+
+    #[test]
+    fn it_round_trips_frames() {
+        let mut previous = Layout::new(&Point::new(4, 3), None);
+        previous
+            .draw_item(
+                Element::new('o', Color::Black, Color::Green),
+                &Point::new(0, 0),
+            )
+            .unwrap();
+
+        let mut layout = Layout::new(&Point::new(4, 3), None);
+        layout
+            .draw_item(
+                Element::new(' ', Color::Blue, Color::Rgb { r: 1, g: 2, b: 3 }),
+                &Point::new(3, 2),
+            )
+            .unwrap();
+
+        let message = SpectatorMessage::Frame(FrameUpdate::new(&layout, Some(&previous)));
+
+        let mut parser = SpectatorMessageParser::default();
+        let parsed: Vec<SpectatorMessage> = message
+            .encode()
+            .iter()
+            .filter_map(|line| parser.parse_line(line).unwrap())
+            .collect();
+
+        assert_eq!(parsed, vec![message]);
+    }
+
+    #[test]
+    fn it_rebuilds_the_frame_from_updates() {
+        let mut frames = vec![Layout::new(&Point::new(5, 2), None); 2];
+        frames[0]
+            .draw_str("ab", &Point::new(0, 0), None, Some(Color::Red))
+            .unwrap();
+        frames[1]
+            .draw_str("cd", &Point::new(3, 1), None, Some(Color::Red))
+            .unwrap();
+
+        let mut spectator_layout = Layout::new(&Point::new(5, 2), None);
+
+        FrameUpdate::new(&frames[0], None).apply(&mut spectator_layout, None);
+        FrameUpdate::new(&frames[1], Some(&frames[0]))
+            .apply(&mut spectator_layout, Some(Point::new(5, 2)));
+
+        assert_eq!(spectator_layout, frames[1]);
+    }
+}
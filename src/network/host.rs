@@ -0,0 +1,284 @@
+use std::{
+    io::ErrorKind,
+    net::{SocketAddr, TcpListener},
+};
+
+use crate::{entities::Action, helpers::get_now};
+
+use super::{
+    connection::Connection,
+    network_error::NetworkResult,
+    protocol::{ClientMessage, HostMessage, Snapshot, PROTOCOL_VERSION},
+};
+
+/// A refused client that hasn't sent its join by then is closed without being told the host is full, in milliseconds
+const REFUSED_CLIENT_TIMEOUT: u128 = 5000;
+/// Connections past this many waiting to be refused are closed straight away
+const MAX_REFUSED_CLIENTS: usize = 8;
+
+/// Runs the game for a client on another machine, the client plays the second ship
+pub struct NetworkHost {
+    listener: TcpListener,
+    client: Option<Connection>,
+    /// The client has sent a join with a matching protocol version, actions are ignored until it has
+    joined: bool,
+    /// Clients that connected while another client was playing and when they connected, they are told the host is full once
+    /// their join arrives
+    refused_clients: Vec<(Connection, u128)>,
+    frame: u64,
+}
+
+impl NetworkHost {
+    /// Listens for a client on the address, e.g. `0.0.0.0:7878` to accept clients from other machines
+    pub fn bind(address: &str) -> NetworkResult<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+
+        Ok(Self {
+            listener,
+            client: None,
+            joined: false,
+            refused_clients: vec![],
+            frame: 0,
+        })
+    }
+
+    pub fn get_address(&self) -> NetworkResult<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    pub fn has_client(&self) -> bool {
+        self.client.is_some() && self.joined
+    }
+
+    /// Accepts a waiting client, then returns the actions the client has sent since the last call. A client that leaves or
+    /// breaks the protocol is dropped so another client can join, without ending the game.
+    pub fn poll_actions(&mut self) -> NetworkResult<Vec<Action>> {
+        self.accept_clients()?;
+        self.refuse_clients();
+
+        let Some(client) = &mut self.client else {
+            return Ok(vec![]);
+        };
+
+        let lines = client.receive_lines();
+        let mut actions = vec![];
+        let mut drop_client = client.is_closed();
+
+        for line in lines.unwrap_or_default() {
+            match ClientMessage::parse(&line) {
+                Ok(ClientMessage::Join(version)) => {
+                    self.joined = version == PROTOCOL_VERSION;
+
+                    // The client compares the versions, so it is told the version of the host before being dropped
+                    let _ = client.send_lines(&HostMessage::Welcome(PROTOCOL_VERSION).encode());
+
+                    drop_client |= !self.joined;
+                }
+                Ok(ClientMessage::Action(action)) if self.joined => actions.push(action),
+                Ok(ClientMessage::Action(_)) => {}
+                Ok(ClientMessage::Leave) | Err(_) => drop_client = true,
+            }
+
+            if drop_client {
+                break;
+            }
+        }
+
+        if drop_client || client.is_closed() {
+            self.drop_client();
+        }
+
+        Ok(actions)
+    }
+
+    /// Sends what is on screen to the client, the frame number is set by the host
+    pub fn send_snapshot(&mut self, snapshot: Snapshot) {
+        self.frame += 1;
+
+        self.send(HostMessage::Snapshot(Snapshot {
+            frame: self.frame,
+            ..snapshot
+        }));
+    }
+
+    pub fn send_game_over(&mut self, score: u64, player_scores: &[u64]) {
+        self.send(HostMessage::GameOver(score, player_scores.to_vec()));
+    }
+
+    /// Sends a message to the client once it has joined, a client that can't be reached is dropped
+    fn send(&mut self, message: HostMessage) {
+        if !self.joined {
+            return;
+        }
+
+        if let Some(client) = &mut self.client {
+            if client.send_lines(&message.encode()).is_err() {
+                self.drop_client();
+            }
+        }
+    }
+
+    fn drop_client(&mut self) {
+        self.client = None;
+        self.joined = false;
+    }
+
+    /// Accepts every waiting connection, only one client can play so later clients are told the host is full
+    fn accept_clients(&mut self) -> NetworkResult<()> {
+        loop {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(error) if error.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(error) => return Err(error.into()),
+            };
+
+            let Ok(connection) = Connection::new(stream) else {
+                continue;
+            };
+
+            match self.client {
+                // Dropping the connection closes it
+                Some(_) if self.refused_clients.len() >= MAX_REFUSED_CLIENTS => {}
+                Some(_) => self.refused_clients.push((connection, get_now())),
+                None => self.client = Some(connection),
+            }
+        }
+    }
+
+    /// Tells refused clients the host is full once their join has been read, closing a connection with unread lines would
+    /// reset it before the client reads the reply. A client that never sends its join is closed after a timeout.
+    fn refuse_clients(&mut self) {
+        let now = get_now();
+
+        self.refused_clients
+            .retain_mut(|(connection, connected_at)| {
+                match connection.receive_lines() {
+                    Ok(lines) if lines.is_empty() && !connection.is_closed() => {
+                        return now - *connected_at < REFUSED_CLIENT_TIMEOUT
+                    }
+                    Ok(_) => {
+                        let _ = connection.send_lines(&HostMessage::Full.encode());
+                    }
+                    Err(_) => {}
+                }
+
+                false
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpStream, thread::sleep, time::Duration};
+
+    use crate::{
+        api::display::Point,
+        entities::Action,
+        network::{HostMessage, NetworkClient, NetworkError, Snapshot},
+    };
+
+    use super::{NetworkHost, MAX_REFUSED_CLIENTS, REFUSED_CLIENT_TIMEOUT};
+
+    const DIMENSIONS: Point<i64> = Point::new(120, 40);
+
+    /// Polls until the other end of the loopback connection has received something, or gives up after a second
+    fn poll_until<T>(mut poll: impl FnMut() -> Vec<T>) -> Vec<T> {
+        for _ in 0..100 {
+            let items = poll();
+
+            if !items.is_empty() {
+                return items;
+            }
+
+            sleep(Duration::from_millis(10));
+        }
+
+        vec![]
+    }
+
+    #[test]
+    fn it_plays_over_loopback() {
+        let mut host = NetworkHost::bind("127.0.0.1:0").unwrap();
+        let mut client =
+            NetworkClient::connect(&host.get_address().unwrap().to_string(), DIMENSIONS).unwrap();
+
+        client.send_action(Action::Fire).unwrap();
+
+        let actions = poll_until(|| host.poll_actions().unwrap());
+
+        assert_eq!(actions, vec![Action::Fire]);
+        assert!(host.has_client());
+
+        host.send_snapshot(Snapshot {
+            score: 12,
+            lives: vec![3, 2],
+            ..Default::default()
+        });
+
+        let messages = poll_until(|| client.receive().unwrap());
+
+        match messages.as_slice() {
+            [HostMessage::Snapshot(snapshot)] => {
+                assert_eq!(snapshot.frame, 1);
+                assert_eq!(snapshot.score, 12);
+                assert_eq!(snapshot.lives, vec![3, 2]);
+            }
+            other => panic!("Expected a snapshot, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_turns_away_a_second_client() {
+        let mut host = NetworkHost::bind("127.0.0.1:0").unwrap();
+        let address = host.get_address().unwrap().to_string();
+
+        let mut first_client = NetworkClient::connect(&address, DIMENSIONS).unwrap();
+        first_client.send_action(Action::Up).unwrap();
+        poll_until(|| host.poll_actions().unwrap());
+
+        let mut second_client = NetworkClient::connect(&address, DIMENSIONS).unwrap();
+
+        let mut result = Ok(vec![]);
+        for _ in 0..100 {
+            host.poll_actions().unwrap();
+            result = second_client.receive();
+
+            if result.is_err() {
+                break;
+            }
+
+            sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(result, Err(NetworkError::HostFull));
+    }
+
+    #[test]
+    fn it_closes_refused_clients_that_never_join() {
+        let mut host = NetworkHost::bind("127.0.0.1:0").unwrap();
+        let address = host.get_address().unwrap().to_string();
+
+        let mut client = NetworkClient::connect(&address, DIMENSIONS).unwrap();
+        client.send_action(Action::Up).unwrap();
+        poll_until(|| host.poll_actions().unwrap());
+
+        // Clients that connect and stay silent
+        let _silent_clients: Vec<TcpStream> = (0..MAX_REFUSED_CLIENTS + 4)
+            .map(|_| TcpStream::connect(&address).unwrap())
+            .collect();
+
+        for _ in 0..10 {
+            host.poll_actions().unwrap();
+            sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(host.refused_clients.len(), MAX_REFUSED_CLIENTS);
+
+        host.refused_clients[0].1 -= REFUSED_CLIENT_TIMEOUT;
+        host.poll_actions().unwrap();
+
+        assert_eq!(host.refused_clients.len(), MAX_REFUSED_CLIENTS - 1);
+        assert!(host.has_client());
+    }
+}
@@ -0,0 +1,15 @@
+mod client;
+mod connection;
+mod frame_protocol;
+mod host;
+mod network_error;
+mod protocol;
+mod spectator_client;
+mod spectator_server;
+
+pub use client::NetworkClient;
+pub use host::NetworkHost;
+pub use network_error::*;
+pub use protocol::*;
+pub use spectator_client::SpectatorClient;
+pub use spectator_server::SpectatorServer;
@@ -0,0 +1,47 @@
+use std::{fmt::Display, io};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkError {
+    IoError(io::ErrorKind),
+    /// A line that isn't part of the protocol
+    InvalidMessage(String),
+    /// The protocol version of the host and the version of the client
+    VersionMismatch(u32, u32),
+    /// The host already has a client playing
+    HostFull,
+    Disconnected,
+    /// The other side stopped reading and too much was waiting to be sent to it
+    SendQueueFull,
+    /// The other side sent a line longer than the limit
+    LineTooLong(usize),
+}
+
+impl Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IoError(kind) => write!(f, "Network error: {}", kind),
+            Self::InvalidMessage(message) => {
+                write!(f, "Received an invalid message: `{}`", message)
+            }
+            Self::VersionMismatch(host, client) => write!(
+                f,
+                "The host uses version {} of the protocol, but this game uses version {}",
+                host, client
+            ),
+            Self::HostFull => write!(f, "The host already has a second player"),
+            Self::Disconnected => write!(f, "The connection was closed"),
+            Self::SendQueueFull => write!(f, "The other side stopped reading"),
+            Self::LineTooLong(limit) => {
+                write!(f, "Received a line longer than {} bytes", limit)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for NetworkError {
+    fn from(error: io::Error) -> Self {
+        Self::IoError(error.kind())
+    }
+}
+
+pub type NetworkResult<T> = Result<T, NetworkError>;
@@ -0,0 +1,385 @@
+//! The messages sent between the host and the client, one message per line of text except for snapshots which span a line per cell.
+//!
+//! The host runs the only simulation of the game. The client sends the actions of its player as they are pressed, and the host
+//! sends a snapshot of what is on screen after every frame, which the client draws as it arrives.
+
+use crossterm::style::Color;
+
+use crate::{
+    api::display::{
+        element::{parse_color, DEFAULT_BACKGROUND},
+        Element, Layout, Point,
+    },
+    entities::Action,
+};
+
+use super::network_error::{NetworkError, NetworkResult};
+
+/// Bumped whenever a message changes, so a host and client built from different versions refuse to play together
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Sent by the client
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientMessage {
+    /// The first message, with the protocol version of the client
+    Join(u32),
+    Action(Action),
+    Leave,
+}
+
+/// Sent by the host
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostMessage {
+    /// The reply to a join, with the protocol version of the host
+    Welcome(u32),
+    /// The reply to a join when the host already has a client
+    Full,
+    Snapshot(Snapshot),
+    /// The final score and the score of each player
+    GameOver(u64, Vec<u64>),
+}
+
+/// A character on screen, the location is within the play field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellSnapshot {
+    pub location: Point<i64>,
+    pub value: char,
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BossSnapshot {
+    pub name: String,
+    pub health: u32,
+    pub max_health: u32,
+}
+
+/// Everything the client needs to draw a frame
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Snapshot {
+    pub frame: u64,
+    pub score: u64,
+    /// Empty when the players share the score
+    pub player_scores: Vec<u64>,
+    pub lives: Vec<u32>,
+    pub boss: Option<BossSnapshot>,
+    pub cells: Vec<CellSnapshot>,
+}
+
+impl Snapshot {
+    /// Draws the cells onto a layout the size of the play field, cells outside the dimensions are skipped
+    pub fn get_layout(&self, dimensions: &Point<i64>) -> Layout {
+        let mut layout = Layout::new(dimensions, None);
+
+        for cell in &self.cells {
+            if let Ok(element) = layout.get_element_mut(&cell.location) {
+                *element = Some(Element::new(cell.value, DEFAULT_BACKGROUND, cell.color));
+            }
+        }
+
+        layout
+    }
+}
+
+impl ClientMessage {
+    pub fn encode(&self) -> String {
+        match self {
+            Self::Join(version) => format!("join {}", version),
+            Self::Action(action) => format!("action {}", action.get_name()),
+            Self::Leave => "leave".to_string(),
+        }
+    }
+
+    pub fn parse(line: &str) -> NetworkResult<Self> {
+        let invalid = || NetworkError::InvalidMessage(line.to_string());
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        match parts.as_slice() {
+            ["join", version] => Ok(Self::Join(version.parse().map_err(|_| invalid())?)),
+            ["action", name] => Action::from_name(name)
+                .map(Self::Action)
+                .ok_or_else(invalid),
+            ["leave"] => Ok(Self::Leave),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl HostMessage {
+    pub fn encode(&self) -> Vec<String> {
+        match self {
+            Self::Welcome(version) => vec![format!("welcome {}", version)],
+            Self::Full => vec!["full".to_string()],
+            Self::GameOver(score, player_scores) => {
+                vec![
+                    format!("game_over {} {}", score, join_numbers(player_scores))
+                        .trim_end()
+                        .to_string(),
+                ]
+            }
+            Self::Snapshot(snapshot) => {
+                let mut lines = vec![
+                    format!("snapshot {} {}", snapshot.frame, snapshot.score),
+                    format!("scores {}", join_numbers(&snapshot.player_scores))
+                        .trim_end()
+                        .to_string(),
+                    format!("lives {}", join_numbers(&snapshot.lives))
+                        .trim_end()
+                        .to_string(),
+                ];
+
+                if let Some(boss) = &snapshot.boss {
+                    lines.push(format!(
+                        "boss {} {} {}",
+                        boss.health, boss.max_health, boss.name
+                    ));
+                }
+
+                // Characters are sent as their code so spaces and other whitespace survive the split
+                lines.extend(snapshot.cells.iter().map(|cell| {
+                    format!(
+                        "cell {} {} {} {}",
+                        cell.location.width,
+                        cell.location.height,
+                        cell.value as u32,
+                        encode_color(cell.color)
+                    )
+                }));
+
+                lines.push("end".to_string());
+
+                lines
+            }
+        }
+    }
+}
+
+/// Parses the lines sent by the host, a snapshot is only returned once its last line has arrived
+pub struct HostMessageParser {
+    snapshot: Option<Snapshot>,
+    /// A snapshot with more cells than the screen has is invalid, so a host can't fill the memory with a snapshot that never ends
+    max_cells: usize,
+}
+
+impl HostMessageParser {
+    /// Parses snapshots of a screen of the dimensions
+    pub fn new(dimensions: Point<i64>) -> Self {
+        Self {
+            snapshot: None,
+            max_cells: (dimensions.width.max(0) * dimensions.height.max(0)) as usize,
+        }
+    }
+
+    pub fn parse_line(&mut self, line: &str) -> NetworkResult<Option<HostMessage>> {
+        let invalid = || NetworkError::InvalidMessage(line.to_string());
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        if let Some(snapshot) = &mut self.snapshot {
+            match parts.as_slice() {
+                ["scores", scores @ ..] => {
+                    snapshot.player_scores = parse_numbers(scores).ok_or_else(invalid)?
+                }
+                ["lives", lives @ ..] => {
+                    snapshot.lives = parse_numbers(lives).ok_or_else(invalid)?
+                }
+                ["boss", health, max_health, name @ ..] => {
+                    snapshot.boss = Some(BossSnapshot {
+                        name: name.join(" "),
+                        health: health.parse().map_err(|_| invalid())?,
+                        max_health: max_health.parse().map_err(|_| invalid())?,
+                    })
+                }
+                ["cell", ..] if snapshot.cells.len() >= self.max_cells => return Err(invalid()),
+                ["cell", width, height, value, color] => snapshot.cells.push(CellSnapshot {
+                    location: Point::new(
+                        width.parse().map_err(|_| invalid())?,
+                        height.parse().map_err(|_| invalid())?,
+                    ),
+                    value: value
+                        .parse()
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(invalid)?,
+                    color: decode_color(color).ok_or_else(invalid)?,
+                }),
+                ["end"] => return Ok(self.snapshot.take().map(HostMessage::Snapshot)),
+                _ => return Err(invalid()),
+            }
+
+            return Ok(None);
+        }
+
+        match parts.as_slice() {
+            ["welcome", version] => Ok(Some(HostMessage::Welcome(
+                version.parse().map_err(|_| invalid())?,
+            ))),
+            ["full"] => Ok(Some(HostMessage::Full)),
+            ["game_over", score, player_scores @ ..] => Ok(Some(HostMessage::GameOver(
+                score.parse().map_err(|_| invalid())?,
+                parse_numbers(player_scores).ok_or_else(invalid)?,
+            ))),
+            ["snapshot", frame, score] => {
+                self.snapshot = Some(Snapshot {
+                    frame: frame.parse().map_err(|_| invalid())?,
+                    score: score.parse().map_err(|_| invalid())?,
+                    ..Default::default()
+                });
+
+                Ok(None)
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+fn join_numbers<T: ToString>(numbers: &[T]) -> String {
+    numbers
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn parse_numbers<T: std::str::FromStr>(values: &[&str]) -> Option<Vec<T>> {
+    values.iter().map(|value| value.parse().ok()).collect()
+}
+
+/// Encodes a color as a name or hex value that parse_color reads back, with the two colors it doesn't know about added
+pub(super) fn encode_color(color: Color) -> String {
+    let name = match color {
+        Color::Reset => "reset",
+        Color::Black => "black",
+        Color::DarkGrey => "dark_grey",
+        Color::Red => "red",
+        Color::DarkRed => "dark_red",
+        Color::Green => "green",
+        Color::DarkGreen => "dark_green",
+        Color::Yellow => "yellow",
+        Color::DarkYellow => "dark_yellow",
+        Color::Blue => "blue",
+        Color::DarkBlue => "dark_blue",
+        Color::Magenta => "magenta",
+        Color::DarkMagenta => "dark_magenta",
+        Color::Cyan => "cyan",
+        Color::DarkCyan => "dark_cyan",
+        Color::White => "white",
+        Color::Grey => "grey",
+        Color::Rgb { r, g, b } => return format!("#{:02x}{:02x}{:02x}", r, g, b),
+        Color::AnsiValue(value) => return format!("ansi:{}", value),
+    };
+
+    name.to_string()
+}
+
+pub(super) fn decode_color(value: &str) -> Option<Color> {
+    if value == "reset" {
+        return Some(Color::Reset);
+    }
+
+    if let Some(ansi_value) = value.strip_prefix("ansi:") {
+        return ansi_value.parse().ok().map(Color::AnsiValue);
+    }
+
+    parse_color(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::{api::display::Point, entities::Action, network::NetworkError};
+
+    use super::{
+        BossSnapshot, CellSnapshot, ClientMessage, HostMessage, HostMessageParser, Snapshot,
+    };
+
+    fn parse_host_message(message: &HostMessage) -> Vec<HostMessage> {
+        let mut parser = HostMessageParser::new(Point::new(120, 40));
+
+        message
+            .encode()
+            .iter()
+            .filter_map(|line| parser.parse_line(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn it_round_trips_client_messages() {
+        for message in [
+            ClientMessage::Join(1),
+            ClientMessage::Action(Action::Special),
+            ClientMessage::Leave,
+        ] {
+            assert_eq!(ClientMessage::parse(&message.encode()), Ok(message));
+        }
+
+        assert!(ClientMessage::parse("action jump").is_err());
+    }
+
+    #[test]
+    fn it_round_trips_snapshots() {
+        let snapshot = HostMessage::Snapshot(Snapshot {
+            frame: 12,
+            score: 30,
+            player_scores: vec![10, 20],
+            lives: vec![3, 0],
+            boss: Some(BossSnapshot {
+                name: "The mothership".to_string(),
+                health: 4,
+                max_health: 27,
+            }),
+            cells: vec![
+                CellSnapshot {
+                    location: Point::new(4, 2),
+                    value: ' ',
+                    color: Color::DarkRed,
+                },
+                CellSnapshot {
+                    location: Point::new(5, 2),
+                    value: '█',
+                    color: Color::Rgb {
+                        r: 255,
+                        g: 136,
+                        b: 0,
+                    },
+                },
+            ],
+        });
+
+        assert_eq!(parse_host_message(&snapshot), vec![snapshot]);
+
+        let game_over = HostMessage::GameOver(30, vec![]);
+
+        assert_eq!(parse_host_message(&game_over), vec![game_over]);
+    }
+
+    #[test]
+    fn it_only_returns_a_snapshot_once_it_has_ended() {
+        let mut parser = HostMessageParser::new(Point::new(120, 40));
+
+        assert_eq!(parser.parse_line("snapshot 1 0"), Ok(None));
+        assert_eq!(parser.parse_line("cell 1 1 65 green"), Ok(None));
+        assert!(parser.parse_line("cell 1 1 65 not_a_color").is_err());
+        assert_eq!(parser.parse_line("cell 2 1 66 green"), Ok(None));
+
+        match parser.parse_line("end") {
+            Ok(Some(HostMessage::Snapshot(snapshot))) => assert_eq!(snapshot.cells.len(), 2),
+            other => panic!("Expected a snapshot, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_refuses_a_snapshot_with_more_cells_than_the_screen() {
+        let mut parser = HostMessageParser::new(Point::new(2, 1));
+
+        assert_eq!(parser.parse_line("snapshot 1 0"), Ok(None));
+        assert_eq!(parser.parse_line("cell 0 0 65 green"), Ok(None));
+        assert_eq!(parser.parse_line("cell 1 0 65 green"), Ok(None));
+        assert_eq!(
+            parser.parse_line("cell 0 0 66 green"),
+            Err(NetworkError::InvalidMessage(
+                "cell 0 0 66 green".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use std::net::TcpStream;
+
+use super::{
+    connection::Connection,
+    frame_protocol::{FrameUpdate, SpectatorMessage, SpectatorMessageParser},
+    network_error::{NetworkError, NetworkResult},
+    protocol::PROTOCOL_VERSION,
+};
+
+/// Watches a game that serves its frames to spectators
+pub struct SpectatorClient {
+    connection: Connection,
+    parser: SpectatorMessageParser,
+}
+
+impl SpectatorClient {
+    pub fn connect(address: &str) -> NetworkResult<Self> {
+        Ok(Self {
+            connection: Connection::new(TcpStream::connect(address)?)?,
+            parser: SpectatorMessageParser::default(),
+        })
+    }
+
+    /// Returns the frames received since the last call, the game closing the connection is an error
+    pub fn receive(&mut self) -> NetworkResult<Vec<FrameUpdate>> {
+        let mut updates = vec![];
+
+        for line in self.connection.receive_lines()? {
+            match self.parser.parse_line(&line)? {
+                Some(SpectatorMessage::Welcome(version)) if version != PROTOCOL_VERSION => {
+                    return Err(NetworkError::VersionMismatch(version, PROTOCOL_VERSION));
+                }
+                Some(SpectatorMessage::Frame(update)) => updates.push(update),
+                Some(SpectatorMessage::Welcome(_)) | None => {}
+            }
+        }
+
+        if updates.is_empty() && self.connection.is_closed() {
+            return Err(NetworkError::Disconnected);
+        }
+
+        Ok(updates)
+    }
+}
@@ -0,0 +1,185 @@
+use std::{io::ErrorKind, net::TcpListener};
+
+use crate::api::display::{FrameSink, Layout};
+
+use super::{
+    connection::Connection,
+    frame_protocol::{FrameUpdate, SpectatorMessage},
+    network_error::NetworkResult,
+    protocol::PROTOCOL_VERSION,
+};
+
+/// Sends every frame the game prints to the spectators that have connected, spectators can't affect the game. A spectator that stops
+/// reading is dropped rather than slowing the game down.
+pub struct SpectatorServer {
+    listener: TcpListener,
+    spectators: Vec<Connection>,
+    /// The last frame that was sent, new frames are sent as the elements that changed
+    previous: Option<Layout>,
+}
+
+impl SpectatorServer {
+    /// Listens for spectators on the address, e.g. `0.0.0.0:7879` to accept spectators from other machines
+    pub fn bind(address: &str) -> NetworkResult<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+
+        Ok(Self {
+            listener,
+            spectators: vec![],
+            previous: None,
+        })
+    }
+
+    #[cfg(test)]
+    pub fn get_address(&self) -> NetworkResult<std::net::SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Accepts the waiting spectators, each is sent the whole frame so they don't have to wait for the screen to change
+    fn accept_spectators(&mut self, layout: &Layout) {
+        loop {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(error) if error.kind() == ErrorKind::WouldBlock => return,
+                // A spectator failing to connect shouldn't stop the game
+                Err(_) => return,
+            };
+
+            let Ok(mut connection) = Connection::new(stream) else {
+                continue;
+            };
+
+            let mut lines = SpectatorMessage::Welcome(PROTOCOL_VERSION).encode();
+            lines.extend(SpectatorMessage::Frame(FrameUpdate::new(layout, None)).encode());
+
+            if connection.send_lines(&lines).is_ok() {
+                self.spectators.push(connection);
+            }
+        }
+    }
+}
+
+impl FrameSink for SpectatorServer {
+    fn present(&mut self, layout: &Layout) {
+        let update = FrameUpdate::new(layout, self.previous.as_ref());
+
+        if !update.changes.is_empty() {
+            let lines = SpectatorMessage::Frame(update).encode();
+
+            // Spectators don't send anything, reading only finds out whether they have left
+            self.spectators.retain_mut(|spectator| {
+                let _ = spectator.receive_lines();
+
+                !spectator.is_closed() && spectator.send_lines(&lines).is_ok()
+            });
+        }
+
+        self.accept_spectators(layout);
+
+        self.previous = Some(layout.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::TcpStream,
+        thread::sleep,
+        time::{Duration, Instant},
+    };
+
+    use crossterm::style::Color;
+
+    use crate::{
+        api::display::{Element, FrameSink, Layout, Point},
+        network::SpectatorClient,
+    };
+
+    use super::SpectatorServer;
+
+    #[test]
+    fn it_streams_frames_over_loopback() {
+        let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let mut client =
+            SpectatorClient::connect(&server.get_address().unwrap().to_string()).unwrap();
+
+        let mut layout = Layout::new(&Point::new(10, 4), None);
+
+        // Presenting a frame accepts the spectator and sends it the whole frame
+        for _ in 0..100 {
+            server.present(&layout);
+
+            if !server.spectators.is_empty() {
+                break;
+            }
+
+            sleep(Duration::from_millis(10));
+        }
+
+        layout
+            .draw_str("ship", &Point::new(2, 1), None, Some(Color::Green))
+            .unwrap();
+        server.present(&layout);
+
+        let mut spectator_layout = Layout::new(&Point::new(10, 4), None);
+        let mut dimensions = None;
+
+        for _ in 0..100 {
+            for update in client.receive().unwrap() {
+                update.apply(&mut spectator_layout, dimensions);
+                dimensions = Some(update.dimensions);
+            }
+
+            if spectator_layout == layout {
+                break;
+            }
+
+            sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(spectator_layout, layout);
+    }
+
+    #[test]
+    fn it_drops_spectators_that_stop_reading() {
+        let mut server = SpectatorServer::bind("127.0.0.1:0").unwrap();
+        let _spectator = TcpStream::connect(server.get_address().unwrap()).unwrap();
+
+        let dimensions = Point::new(200, 60);
+        let mut layout = Layout::new(&dimensions, None);
+
+        for _ in 0..100 {
+            server.present(&layout);
+
+            if !server.spectators.is_empty() {
+                break;
+            }
+
+            sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(server.spectators.len(), 1);
+
+        let start = Instant::now();
+
+        // Every element changes each frame, the spectator never reads any of them
+        for frame in 0..1000 {
+            let value = if frame % 2 == 0 { '#' } else { '@' };
+
+            layout = Layout::new(
+                &dimensions,
+                Some(Element::new(value, Color::Black, Color::Green)),
+            );
+
+            server.present(&layout);
+
+            if server.spectators.is_empty() {
+                break;
+            }
+        }
+
+        assert!(server.spectators.is_empty());
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
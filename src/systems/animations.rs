@@ -0,0 +1,9 @@
+use crate::world::World;
+
+/// Advances the animation of every sprite. Animations are advanced before collision detection so collisions use the shape of the
+/// frame that is drawn.
+pub fn run_animation_system(world: &mut World, game_loop_duration: u128) {
+    for (_, sprite) in world.sprites.iter_mut() {
+        sprite.animation.advance(game_loop_duration);
+    }
+}
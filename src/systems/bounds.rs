@@ -0,0 +1,63 @@
+use crate::{api::display::Point, world::World};
+
+/// Despawns the entities that have moved further than the margin outside of the play field, such as bullets that missed and
+/// asteroids that passed the player. Entities without a sprite are treated as a single cell.
+///
+/// # Arguments
+///
+/// * `dimensions` - The dimensions of the play field
+/// * `margin` - How far outside of the play field an entity can be before it is despawned, so entities can spawn just off screen
+pub fn run_bounds_system(world: &mut World, dimensions: &Point<i64>, margin: i64) {
+    let mut offscreen_entities = vec![];
+
+    for entity in world.get_entities() {
+        let Some(location) = world.positions.get(entity) else {
+            continue;
+        };
+
+        let size = world
+            .sprites
+            .get(entity)
+            .map(|sprite| sprite.get_layout().dimensions)
+            .unwrap_or(Point::new(1, 1));
+
+        let is_outside_bounds = location.width + size.width <= -margin
+            || location.height + size.height <= -margin
+            || location.width >= dimensions.width + margin
+            || location.height >= dimensions.height + margin;
+
+        if is_outside_bounds {
+            offscreen_entities.push(entity);
+        }
+    }
+
+    for entity in offscreen_entities {
+        world.despawn_offscreen(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::display::Point, world::World};
+
+    use super::run_bounds_system;
+
+    const DIMENSIONS: &Point<i64> = &Point::new(20, 10);
+
+    #[test]
+    fn it_despawns_entities_beyond_the_margin() {
+        let mut world = World::new();
+
+        let inside = world.spawn().with_position(Point::new(5, 5)).build();
+        let within_margin = world.spawn().with_position(Point::new(-3, 12)).build();
+        let above = world.spawn().with_position(Point::new(5, -4)).build();
+        let right = world.spawn().with_position(Point::new(23, 5)).build();
+
+        run_bounds_system(&mut world, DIMENSIONS, 3);
+
+        assert!(world.has_entity(inside));
+        assert!(world.has_entity(within_margin));
+        assert!(!world.has_entity(above));
+        assert!(!world.has_entity(right));
+    }
+}
@@ -1,27 +1,125 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use uuid::Uuid;
 
-use crate::{
-    api::display::{collapse_twoDVec, create_map, Point, TwoDVec},
-    components::{DrawableState, DrawableType},
-};
-
-// Enum stores the damage result of the player or ammunition and also the damage result of the asteroid
-pub struct CollisionOutcome {
-    pub affected_damage: u32,
-    pub enemy_damage: u32,
-    pub asteroid_uuid: Uuid,
-    pub points: u64,
+use crate::{api::display::Point, components::DrawableState};
+
+use super::{CollisionEvent, CollisionEventKind, CollisionParticipant, InteractionTable};
+
+/// A position on the grid and the drawable items that overlap on it
+type PositionWithOverlaps = (Point<i64>, Vec<CollisionParticipant>);
+
+/// The size of each bucket in the spatial hash, drawables are only compared with drawables that share a bucket
+const SPATIAL_HASH_BUCKET_SIZE: i64 = 8;
+
+/// The area covered by a drawable's layout, the end is exclusive
+#[derive(Clone, Debug, Copy, PartialEq)]
+struct BoundingBox {
+    start: Point<i64>,
+    end: Point<i64>,
 }
 
-#[derive(Clone, Debug, Copy)]
-struct MinimalDrawableDetails {
-    pub uuid: Uuid,
-    pub drawable_type: DrawableType,
+impl BoundingBox {
+    /// Creates the bounding box of the drawable, clipped to the game dimensions. Returns None if the drawable is entirely outside the dimensions.
+    fn from_drawable_state(
+        drawable_state: &DrawableState,
+        dimensions: &Point<i64>,
+    ) -> Option<Self> {
+        let start = drawable_state.location;
+        let end = start + drawable_state.layout.dimensions;
+
+        BoundingBox { start, end }.get_intersection(&BoundingBox {
+            start: Point::default(),
+            end: *dimensions,
+        })
+    }
+
+    fn get_intersection(&self, other: &BoundingBox) -> Option<Self> {
+        let start = Point::new(
+            self.start.width.max(other.start.width),
+            self.start.height.max(other.start.height),
+        );
+        let end = Point::new(
+            self.end.width.min(other.end.width),
+            self.end.height.min(other.end.height),
+        );
+
+        if start.width >= end.width || start.height >= end.height {
+            return None;
+        }
+
+        Some(BoundingBox { start, end })
+    }
+
+    fn contains(&self, position: &Point<i64>) -> bool {
+        position.width >= self.start.width
+            && position.width < self.end.width
+            && position.height >= self.start.height
+            && position.height < self.end.height
+    }
+}
+
+/// Broadphase of the collision detection. Each bounding box is added to the buckets of a spatial hash that it covers, then the bounding boxes in each bucket are compared.
+///
+/// # Returns
+///
+/// * For each drawable item, the areas where its bounding box overlaps with other bounding boxes
+fn get_bounding_box_overlaps(bounding_boxes: &[Option<BoundingBox>]) -> Vec<Vec<BoundingBox>> {
+    let mut spatial_hash: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+
+    for (index, bounding_box) in bounding_boxes.iter().enumerate() {
+        let Some(bounding_box) = bounding_box else {
+            continue;
+        };
+
+        // Bounding boxes are clipped to the dimensions, so the bucket of a position is never negative
+        let get_bucket = |position: i64| position / SPATIAL_HASH_BUCKET_SIZE;
+
+        let height_buckets =
+            get_bucket(bounding_box.start.height)..=get_bucket(bounding_box.end.height - 1);
+        let width_buckets =
+            get_bucket(bounding_box.start.width)..=get_bucket(bounding_box.end.width - 1);
+
+        for bucket_height in height_buckets {
+            for bucket_width in width_buckets.clone() {
+                spatial_hash
+                    .entry((bucket_height, bucket_width))
+                    .or_default()
+                    .push(index);
+            }
+        }
+    }
+
+    let mut checked_pairs: HashSet<(usize, usize)> = HashSet::new();
+    let mut overlaps: Vec<Vec<BoundingBox>> = vec![vec![]; bounding_boxes.len()];
+
+    for indexes in spatial_hash.values() {
+        for (position, first) in indexes.iter().enumerate() {
+            for second in &indexes[position + 1..] {
+                // Drawables that span multiple buckets are only compared once
+                if !checked_pairs.insert((*first, *second)) {
+                    continue;
+                }
+
+                if let (Some(first_box), Some(second_box)) =
+                    (&bounding_boxes[*first], &bounding_boxes[*second])
+                {
+                    if let Some(intersection) = first_box.get_intersection(second_box) {
+                        overlaps[*first].push(intersection);
+                        overlaps[*second].push(intersection);
+                    }
+                }
+            }
+        }
+    }
+
+    overlaps
 }
 
-/// This method calculates the positions where the provided drawable_items overlap. It returns an array where each item is a position on the grid with overlapping drawable_items, it returns the uuid and drawable_type for each item.
+/// This method calculates the positions where the provided drawable_items overlap. It returns an array where each item is a position on the grid with overlapping drawable_items, along with the uuid and drawable_type for each item.
+/// Positions are ordered by row then column, and the items on a position are in the order they were provided.
+///
+/// Only the cells of drawables whose bounding boxes overlap are checked, and only within the overlapping area.
 ///
 /// # Arguments
 ///
@@ -32,201 +130,150 @@ struct MinimalDrawableDetails {
 fn get_positions_with_overlaps(
     drawable_items: Vec<&DrawableState>,
     dimensions: &Point<i64>,
-) -> TwoDVec<MinimalDrawableDetails> {
-    let mut parsed_map: TwoDVec<Option<Vec<MinimalDrawableDetails>>> = create_map(dimensions, None);
+) -> Vec<PositionWithOverlaps> {
+    let bounding_boxes: Vec<Option<BoundingBox>> = drawable_items
+        .iter()
+        .map(|drawable_state| BoundingBox::from_drawable_state(drawable_state, dimensions))
+        .collect();
 
-    // Iterating over each drawable item to handle it
-    for drawable_state in drawable_items {
-        // Each row in the drawable layout
-        for (index, row) in drawable_state.layout.map.iter().enumerate() {
-            let height = drawable_state.location.height + index as i64;
+    let overlaps = get_bounding_box_overlaps(&bounding_boxes);
 
-            if height < 0 || height >= dimensions.height {
-                continue;
-            }
+    // Position (height, width) -> drawable items on the position
+    let mut occupied_positions: HashMap<(i64, i64), Vec<CollisionParticipant>> = HashMap::new();
+
+    for (index, drawable_state) in drawable_items.iter().enumerate() {
+        let (Some(bounding_box), item_overlaps) = (&bounding_boxes[index], &overlaps[index]) else {
+            continue;
+        };
+
+        if item_overlaps.is_empty() {
+            continue;
+        }
+
+        for height in bounding_box.start.height..bounding_box.end.height {
+            let row =
+                &drawable_state.layout.map[(height - drawable_state.location.height) as usize];
 
-            for (width, element) in row.iter().enumerate() {
-                if element.is_none() {
+            for width in bounding_box.start.width..bounding_box.end.width {
+                let column = (width - drawable_state.location.width) as usize;
+
+                if !row.get(column).is_some_and(|element| element.is_some()) {
                     continue;
                 }
 
-                let width = drawable_state.location.width + width as i64;
+                let position = Point::new(width, height);
 
-                if width < 0 || width >= dimensions.width {
+                if !item_overlaps
+                    .iter()
+                    .any(|overlap| overlap.contains(&position))
+                {
                     continue;
                 }
 
-                let parsed_item = MinimalDrawableDetails {
-                    uuid: drawable_state.uuid,
-                    drawable_type: drawable_state.drawable_type,
-                };
-
-                match &mut parsed_map[height as usize][width as usize] {
-                    Some(items) => {
-                        items.push(parsed_item);
-                    }
-                    None => {
-                        parsed_map[height as usize][width as usize] = Some(vec![parsed_item]);
-                    }
-                };
+                occupied_positions
+                    .entry((height, width))
+                    .or_default()
+                    .push(CollisionParticipant {
+                        uuid: drawable_state.uuid,
+                        drawable_type: drawable_state.drawable_type,
+                    });
             }
         }
     }
 
-    // Reduce the map into a single array of overlapping drawable_items
-    let mut drawable_details: TwoDVec<MinimalDrawableDetails> = vec![];
+    let mut positions_with_overlaps: Vec<((i64, i64), Vec<CollisionParticipant>)> =
+        occupied_positions
+            .into_iter()
+            .filter(|(_, items)| items.len() > 1)
+            .collect();
 
-    for item in collapse_twoDVec(parsed_map).iter_mut() {
-        if let Some(some_item) = item {
-            if some_item.len() > 1 {
-                drawable_details.push(some_item.to_vec());
-            }
-        }
-    }
+    positions_with_overlaps.sort_by_key(|(position, _)| *position);
 
-    drawable_details
+    positions_with_overlaps
+        .into_iter()
+        .map(|((height, width), items)| (Point::new(width, height), items))
+        .collect()
 }
 
-/// UUID of affected drawable item -> UUID of causing drawable item -> collision results
-/// This is stored in a complex manner to increase memory access speed when determining collision results
-type CollisionResults = HashMap<Uuid, HashMap<Uuid, CollisionOutcome>>;
-
-/// How much damage to apply to an enemy when it collides with a player, this should destroy the enemy if a collision occurs
-pub const PLAYER_ENEMY_COLLISION_DAMAGE: u32 = 100;
-
+/// Finds the collisions between the drawable items. A drawable is only affected by drawables on the layers in its collision mask, and what happens to it is determined by the interaction table.
+///
+/// # Arguments
+///
+/// * `drawable_items` - The drawable items to check for collisions
+/// * `dimensions` - The game dimensions, ignoring outside of dimensions
+/// * `interaction_table` - The rules for each pair of collision layers
+///
+/// # Returns
+///
+/// * An event for each drawable that was affected by another drawable, in the order the collisions were found
 pub fn run_collision_detection(
     drawable_items: Vec<&DrawableState>,
     dimensions: &Point<i64>,
-) -> CollisionResults {
-    let mut collision_outcomes: HashMap<Uuid, HashMap<Uuid, CollisionOutcome>> = HashMap::new();
-
-    let positions_with_overlaps = get_positions_with_overlaps(drawable_items, dimensions);
-
-    for elements_on_position in positions_with_overlaps {
-        // Single out all the enemies, so that if there is a player or ammunition on the position we can apply affects
-        let mut enemies: Vec<&MinimalDrawableDetails> = vec![];
-        for element in &elements_on_position {
-            match element.drawable_type {
-                DrawableType::Enemy(..) => {
-                    enemies.push(element);
+    interaction_table: &InteractionTable,
+) -> Vec<CollisionEvent> {
+    let mut collision_events: Vec<CollisionEvent> = vec![];
+    // Target uuid -> source uuid, drawables overlap on many positions but only a single event is emitted for each pair
+    let mut emitted_events: HashSet<(Uuid, Uuid)> = HashSet::new();
+
+    for (position, elements_on_position) in get_positions_with_overlaps(drawable_items, dimensions)
+    {
+        for target in &elements_on_position {
+            let target_layer = target.drawable_type.get_collision_layer();
+            let target_mask = target.drawable_type.get_collision_mask();
+
+            for source in &elements_on_position {
+                let source_layer = source.drawable_type.get_collision_layer();
+
+                if target.uuid == source.uuid || !target_mask.contains(source_layer) {
+                    continue;
                 }
-                _ => {}
-            }
-        }
 
-        for element in &elements_on_position {
-            match element.drawable_type {
-                DrawableType::Player | DrawableType::Ammunition(..) => {
-                    // Get the hashmap for element collisions for the player
-                    let element_collisions = match collision_outcomes.get_mut(&element.uuid) {
-                        // Return the hashmap if it exists
-                        Some(collisions) => collisions,
-                        // Otherwise create it then return a mutable reference to it
-                        None => {
-                            collision_outcomes.insert(element.uuid, HashMap::new());
-
-                            collision_outcomes.get_mut(&element.uuid).unwrap()
-                        }
-                    };
-
-                    for enemy in &enemies {
-                        // Only create a new collision if one doesn't exist already for the same element/enemy combination
-                        if !element_collisions.contains_key(&enemy.uuid) {
-                            if let DrawableType::Enemy(enemy_damage) = enemy.drawable_type {
-                                // If an enemy hit the player, apply the enemies damage to the player and destroy the enemy
-                                if element.drawable_type == DrawableType::Player {
-                                    element_collisions.insert(
-                                        enemy.uuid,
-                                        CollisionOutcome {
-                                            affected_damage: enemy_damage,
-                                            enemy_damage: PLAYER_ENEMY_COLLISION_DAMAGE,
-                                            asteroid_uuid: enemy.uuid,
-                                            points: 0,
-                                        },
-                                    );
-                                } else {
-                                    // If the asteroid collided with ammunition, extract the ammunition damage
-                                    if let DrawableType::Ammunition(
-                                        ammunition_damage,
-                                        collision_points,
-                                    ) = element.drawable_type
-                                    {
-                                        // Apply the enemies damage to the ammunition and the ammunition's damage to the enemy
-                                        element_collisions.insert(
-                                            enemy.uuid,
-                                            CollisionOutcome {
-                                                affected_damage: enemy_damage,
-                                                enemy_damage: ammunition_damage,
-                                                asteroid_uuid: enemy.uuid,
-                                                points: collision_points as u64,
-                                            },
-                                        );
-                                    }
-                                }
-                            } else {
-                                panic!("Error: Not an enemy in the enemies vector");
-                            }
-                        }
-                    }
+                let Some(rule) = interaction_table.get_rule(target_layer, source_layer) else {
+                    continue;
+                };
+
+                if !emitted_events.insert((target.uuid, source.uuid)) {
+                    continue;
                 }
-                _ => {}
+
+                collision_events.push(CollisionEvent {
+                    kind: CollisionEventKind::from_layers(source_layer, target_layer),
+                    source: *source,
+                    target: *target,
+                    position,
+                    damage: rule.get_damage(&source.drawable_type),
+                    points: rule.get_points(&source.drawable_type),
+                });
             }
         }
     }
 
-    collision_outcomes
+    collision_events
 }
 
+/// The total damage dealt to a target, points are awarded from the events instead as they depend on which event destroyed the target
 pub struct Summary {
-    pub uuid: Uuid,
     pub damage: u32,
-    pub points: u64,
 }
 
 pub type CollisionSummary = HashMap<Uuid, Summary>;
 
-pub fn apply_damage_to_uuid(
-    collision_summary: &mut CollisionSummary,
-    uuid: Uuid,
-    damage: u32,
-    points: u64,
-) {
+pub fn apply_damage_to_uuid(collision_summary: &mut CollisionSummary, uuid: Uuid, damage: u32) {
     match collision_summary.get_mut(&uuid) {
-        Some(item) => item.damage += damage,
+        // Saturate as destroying a drawable applies the maximum damage
+        Some(item) => item.damage = item.damage.saturating_add(damage),
         None => {
-            collision_summary.insert(
-                uuid,
-                Summary {
-                    uuid,
-                    damage,
-                    points,
-                },
-            );
+            collision_summary.insert(uuid, Summary { damage });
         }
     };
 }
 
-/// Method reduces the collision results hashmap into a summary of damages for each element
-pub fn get_collision_summary(collision_results: CollisionResults) -> CollisionSummary {
+/// Method reduces the collision events into a summary of damages for each target
+pub fn get_collision_summary(collision_events: &[CollisionEvent]) -> CollisionSummary {
     let mut collision_summary: CollisionSummary = HashMap::new();
 
-    for (uuid, element_collisions) in collision_results {
-        for (_, asteroid_collision) in element_collisions {
-            // Apply damage to the element
-            apply_damage_to_uuid(
-                &mut collision_summary,
-                uuid,
-                asteroid_collision.affected_damage,
-                0,
-            );
-            // Also apply damage to the asteroid
-            apply_damage_to_uuid(
-                &mut collision_summary,
-                asteroid_collision.asteroid_uuid,
-                asteroid_collision.enemy_damage,
-                asteroid_collision.points,
-            );
-        }
+    for event in collision_events {
+        apply_damage_to_uuid(&mut collision_summary, event.target.uuid, event.damage);
     }
 
     collision_summary
@@ -235,218 +282,325 @@ pub fn get_collision_summary(collision_results: CollisionResults) -> CollisionSu
 #[cfg(test)]
 mod tests {
 
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use uuid::Uuid;
+
     use crate::{
-        api::display::{Map, Point, TwoDVec},
-        components::Drawable,
-        entities::{player, Asteroid, Bullet, Player, ASTEROID_DAMAGE, BULLET_DAMAGE},
-        systems::PLAYER_ENEMY_COLLISION_DAMAGE,
+        api::display::{collapse_two_d_vec, create_map, Point, TwoDVec},
+        components::{CollisionLayer, DrawableState},
+        entities::{
+            spawn_asteroid, spawn_basic_bullet, spawn_spread_bullet, Player, ASTEROID_DAMAGE,
+            BULLET_DAMAGE,
+        },
+        systems::{
+            CollisionEvent, CollisionEventKind, CollisionParticipant, InteractionRule,
+            InteractionTable, DESTROY_DAMAGE,
+        },
+        user_display::Theme,
+        world::{Entity, World},
     };
 
-    use super::{get_collision_summary, get_positions_with_overlaps, run_collision_detection};
+    use super::{
+        get_collision_summary, get_positions_with_overlaps, run_collision_detection,
+        PositionWithOverlaps,
+    };
 
     const POSITION: Point<i64> = Point {
         width: 5,
         height: 5,
     };
 
-    fn get_asteroid_mock() -> Asteroid {
-        Asteroid::new(
+    const DIMENSIONS: Point<i64> = Point {
+        width: 30,
+        height: 30,
+    };
+
+    fn get_drawable_state(world: &World, entity: Entity) -> DrawableState {
+        world.get_drawable_state(entity).unwrap()
+    }
+
+    fn get_asteroid_mock(world: &mut World) -> DrawableState {
+        let asteroid = spawn_asteroid(
+            world,
             POSITION,
             Point {
                 width: 1,
                 height: 1,
             },
-        )
+            &Theme::default(),
+        );
+
+        get_drawable_state(world, asteroid)
+    }
+
+    fn get_player_mock(world: &mut World) -> DrawableState {
+        let player = Player::new(world, Some(POSITION), 5, &Theme::default());
+
+        get_drawable_state(world, player.entity)
+    }
+
+    /// A basic bullet with its top left on the position, rather than its anchor
+    fn get_bullet_mock(world: &mut World) -> DrawableState {
+        let bullet = spawn_basic_bullet(world, POSITION, &Theme::default());
+
+        world.positions.insert(bullet, POSITION);
+
+        get_drawable_state(world, bullet)
+    }
+
+    fn find_event(events: &[CollisionEvent], source: Uuid, target: Uuid) -> &CollisionEvent {
+        events
+            .iter()
+            .find(|event| event.source.uuid == source && event.target.uuid == target)
+            .unwrap()
     }
 
-    /**
-     *
-     */
     #[test]
     fn it_should_return_no_collisions_when_providing_multiple_asteroids() {
-        let asteroid = get_asteroid_mock();
-        let drawable_states = vec![asteroid.get_drawable_state(); 10];
-
-        let collisions = run_collision_detection(
-            drawable_states,
-            &Point {
-                width: 30,
-                height: 30,
-            },
-        );
+        let mut world = World::new();
+
+        let asteroid = get_asteroid_mock(&mut world);
+        let drawable_states = vec![&asteroid; 10];
 
-        assert_eq!(collisions.len(), 0);
+        let events =
+            run_collision_detection(drawable_states, &DIMENSIONS, &InteractionTable::default());
+
+        assert_eq!(events.len(), 0);
     }
 
-    /**
-     *
-     */
     #[test]
     fn it_should_return_no_collisions_when_providing_player_and_ammunition() {
-        let player = Player::new(Some(POSITION), 5);
-        let ammunition = Bullet::build_basic_bullet(POSITION);
+        let mut world = World::new();
 
-        let drawable_states = vec![player.get_drawable_state(), ammunition.get_drawable_state()];
+        let player = get_player_mock(&mut world);
+        let ammunition = get_bullet_mock(&mut world);
 
-        let collisions = run_collision_detection(
-            drawable_states,
-            &Point {
-                width: 30,
-                height: 30,
-            },
-        );
+        let drawable_states = vec![&player, &ammunition];
 
-        assert_eq!(collisions.len(), 0);
+        let events =
+            run_collision_detection(drawable_states, &DIMENSIONS, &InteractionTable::default());
+
+        assert_eq!(events.len(), 0);
     }
 
-    /**
-     *
-     */
     #[test]
     fn it_should_return_a_collision_for_a_player_and_asteroid() {
-        let player = Player::new(Some(POSITION), 5);
+        let mut world = World::new();
 
-        let asteroid = get_asteroid_mock();
+        let player = get_player_mock(&mut world);
 
-        let drawable_states = vec![asteroid.get_drawable_state(), player.get_drawable_state()];
+        let asteroid = get_asteroid_mock(&mut world);
 
-        let collisions = run_collision_detection(
-            drawable_states,
-            &Point {
-                width: 30,
-                height: 30,
-            },
-        );
-
-        assert_eq!(collisions.len(), 1);
+        let drawable_states = vec![&asteroid, &player];
 
-        let player_collisions = collisions.get(&player.drawable.uuid).unwrap();
+        let events =
+            run_collision_detection(drawable_states, &DIMENSIONS, &InteractionTable::default());
 
-        assert_eq!(player_collisions.len(), 1);
+        assert_eq!(events.len(), 2);
 
-        let player_asteroid_collision = player_collisions.get(&asteroid.drawable.uuid).unwrap();
+        let asteroid_hit_player = find_event(&events, asteroid.uuid, player.uuid);
 
         // Expect that the player took damage
-        assert_eq!(player_asteroid_collision.affected_damage, 1);
+        assert_eq!(asteroid_hit_player.kind, CollisionEventKind::EnemyHitPlayer);
+        assert_eq!(asteroid_hit_player.damage, 1);
 
-        // Expect that the asteroid took damage
-        assert_eq!(
-            player_asteroid_collision.enemy_damage,
-            PLAYER_ENEMY_COLLISION_DAMAGE
-        );
+        let player_hit_asteroid = find_event(&events, player.uuid, asteroid.uuid);
+
+        // Expect that the asteroid was destroyed
+        assert_eq!(player_hit_asteroid.kind, CollisionEventKind::PlayerHitEnemy);
+        assert_eq!(player_hit_asteroid.damage, DESTROY_DAMAGE);
+
+        // Both events are found on the same position, which is within both drawables
+        assert_eq!(asteroid_hit_player.position, player_hit_asteroid.position);
+        assert!(asteroid_hit_player.position.height >= POSITION.height);
     }
 
-    /**
-     *
-     */
     #[test]
     fn it_should_return_a_collision_for_a_asteroid_and_ammunition() {
-        let ammunition = Bullet::build_basic_bullet(POSITION);
-
-        let asteroid = get_asteroid_mock();
+        let mut world = World::new();
 
-        let drawable_states = vec![
-            asteroid.get_drawable_state(),
-            ammunition.get_drawable_state(),
-        ];
+        let ammunition = get_bullet_mock(&mut world);
 
-        let collisions = run_collision_detection(
-            drawable_states,
-            &Point {
-                width: 30,
-                height: 30,
-            },
-        );
+        let asteroid = get_asteroid_mock(&mut world);
 
-        assert_eq!(collisions.len(), 1);
+        let drawable_states = vec![&asteroid, &ammunition];
 
-        let ammunition_collisions = collisions.get(&ammunition.drawable.uuid).unwrap();
+        let events =
+            run_collision_detection(drawable_states, &DIMENSIONS, &InteractionTable::default());
 
-        assert_eq!(ammunition_collisions.len(), 1);
+        assert_eq!(events.len(), 2);
 
-        let ammunition_asteroid_collision =
-            ammunition_collisions.get(&asteroid.drawable.uuid).unwrap();
+        let asteroid_hit_bullet = find_event(&events, asteroid.uuid, ammunition.uuid);
 
         // Expect that the bullet took damange
+        assert_eq!(asteroid_hit_bullet.kind, CollisionEventKind::EnemyHitBullet);
+        assert_eq!(asteroid_hit_bullet.damage, ASTEROID_DAMAGE);
+
+        let bullet_hit_asteroid = find_event(&events, ammunition.uuid, asteroid.uuid);
+
+        // Expect that the asteroid took damage equal to the bullets damage and awards the bullets points
+        assert_eq!(bullet_hit_asteroid.kind, CollisionEventKind::BulletHitEnemy);
+        assert_eq!(bullet_hit_asteroid.damage, BULLET_DAMAGE);
         assert_eq!(
-            ammunition_asteroid_collision.affected_damage,
-            ASTEROID_DAMAGE
+            bullet_hit_asteroid.points,
+            ammunition.drawable_type.get_points()
         );
-
-        // Expect that the asteroid took damage equal to the bullets damage
-        assert_eq!(ammunition_asteroid_collision.enemy_damage, BULLET_DAMAGE);
     }
 
-    /**
-     *
-     */
     #[test]
     fn it_should_return_a_single_collision_for_player_and_multiple_occurrences_of_asteroid() {
-        let player = Player::new(Some(POSITION), 5);
+        let mut world = World::new();
+
+        let player = get_player_mock(&mut world);
 
-        let mut drawable_states = vec![player.get_drawable_state()];
+        let mut drawable_states = vec![&player];
 
-        let asteroid = get_asteroid_mock();
+        let asteroid = get_asteroid_mock(&mut world);
         // Create multiple asteroids and append them to the drawable states vector
-        let mut asteroids = vec![asteroid.get_drawable_state(); 10];
+        let mut asteroids = vec![&asteroid; 10];
         drawable_states.append(&mut asteroids);
 
-        let collisions = run_collision_detection(
-            drawable_states,
-            &Point {
-                width: 30,
-                height: 30,
-            },
-        );
-
-        assert_eq!(collisions.len(), 1);
+        let events =
+            run_collision_detection(drawable_states, &DIMENSIONS, &InteractionTable::default());
 
-        let player_collisions = collisions.get(&player.drawable.uuid).unwrap();
-
-        assert_eq!(player_collisions.len(), 1);
+        // A single event for each direction, as every occurrence shares the same uuid
+        assert_eq!(events.len(), 2);
     }
 
     #[test]
     fn it_should_return_collisions_and_summarize_for_a_player_ammunition_and_multiple_asteroids() {
-        let player = Player::new(Some(POSITION), 5);
-        let asteroid1 = get_asteroid_mock();
-        let asteroid2 = get_asteroid_mock();
-        let ammunition = Bullet::build_basic_bullet(POSITION);
-
-        let drawable_states = vec![
-            player.get_drawable_state(),
-            asteroid1.get_drawable_state(),
-            asteroid2.get_drawable_state(),
-            ammunition.get_drawable_state(),
-        ];
-
-        let collisions = run_collision_detection(
-            drawable_states,
-            &Point {
-                width: 30,
-                height: 30,
-            },
-        );
+        let mut world = World::new();
+
+        let player = get_player_mock(&mut world);
+        let asteroid1 = get_asteroid_mock(&mut world);
+        let asteroid2 = get_asteroid_mock(&mut world);
+        let ammunition = get_bullet_mock(&mut world);
+
+        let drawable_states = vec![&player, &asteroid1, &asteroid2, &ammunition];
+
+        let events =
+            run_collision_detection(drawable_states, &DIMENSIONS, &InteractionTable::default());
 
-        let collision_summary = get_collision_summary(collisions);
+        let collision_summary = get_collision_summary(&events);
 
         assert_eq!(collision_summary.len(), 4);
 
-        for (_, collision) in collision_summary {
-            if collision.uuid == player.drawable.uuid || collision.uuid == ammunition.drawable.uuid
-            {
+        for (uuid, collision) in collision_summary {
+            if uuid == player.uuid || uuid == ammunition.uuid {
                 assert_eq!(collision.damage, ASTEROID_DAMAGE * 2);
-            } else if collision.uuid == asteroid1.drawable.uuid
-                || collision.uuid == asteroid2.drawable.uuid
-            {
-                assert_eq!(
-                    collision.damage,
-                    BULLET_DAMAGE + PLAYER_ENEMY_COLLISION_DAMAGE
-                );
+            } else if uuid == asteroid1.uuid || uuid == asteroid2.uuid {
+                // The player destroys the asteroid
+                assert_eq!(collision.damage, DESTROY_DAMAGE);
             } else {
                 // Ensure all items are tested
                 panic!("No test case for element in collision summary")
             }
         }
     }
+
+    #[test]
+    fn it_should_use_the_rules_from_the_interaction_table() {
+        let mut world = World::new();
+
+        let player = get_player_mock(&mut world);
+        let asteroid = get_asteroid_mock(&mut world);
+
+        let mut interaction_table = InteractionTable::new();
+        interaction_table.set_rule(
+            CollisionLayer::Player,
+            CollisionLayer::Enemy,
+            InteractionRule {
+                damage: Some(3),
+                destroy: false,
+                points: Some(0),
+            },
+        );
+
+        let events =
+            run_collision_detection(vec![&player, &asteroid], &DIMENSIONS, &interaction_table);
+
+        // The asteroid has no rule for colliding with the player
+        assert_eq!(events.len(), 1);
+        assert_eq!(find_event(&events, asteroid.uuid, player.uuid).damage, 3);
+    }
+
+    /// The original implementation that fills a grid the size of the dimensions, used to check the broadphase gives identical results
+    fn get_positions_with_overlaps_full_grid(
+        drawable_items: Vec<&DrawableState>,
+        dimensions: &Point<i64>,
+    ) -> Vec<PositionWithOverlaps> {
+        let mut parsed_map: TwoDVec<Option<PositionWithOverlaps>> = create_map(dimensions, None);
+
+        for drawable_state in drawable_items {
+            for (index, row) in drawable_state.layout.map.iter().enumerate() {
+                let height = drawable_state.location.height + index as i64;
+
+                if height < 0 || height >= dimensions.height {
+                    continue;
+                }
+
+                for (width, element) in row.iter().enumerate() {
+                    let width = drawable_state.location.width + width as i64;
+
+                    if element.is_none() || width < 0 || width >= dimensions.width {
+                        continue;
+                    }
+
+                    parsed_map[height as usize][width as usize]
+                        .get_or_insert_with(|| (Point::new(width, height), vec![]))
+                        .1
+                        .push(CollisionParticipant {
+                            uuid: drawable_state.uuid,
+                            drawable_type: drawable_state.drawable_type,
+                        });
+                }
+            }
+        }
+
+        collapse_two_d_vec(parsed_map)
+            .into_iter()
+            .flatten()
+            .filter(|(_, items)| items.len() > 1)
+            .collect()
+    }
+
+    #[test]
+    fn it_should_find_the_same_overlaps_as_a_full_grid() {
+        let dimensions = Point::new(120, 40);
+        let theme = Theme::default();
+        let mut rng = StdRng::seed_from_u64(31);
+
+        // Drawables are spread slightly past the dimensions so clipping is covered
+        let mut get_location = || {
+            Point::new(
+                rng.gen_range(-5..dimensions.width + 5),
+                rng.gen_range(-5..dimensions.height + 5),
+            )
+        };
+
+        let mut world = World::new();
+
+        Player::new(&mut world, Some(get_location()), 5, &theme);
+
+        for index in 0..150 {
+            spawn_asteroid(&mut world, get_location(), Point::new(1, 1), &theme);
+
+            if index % 2 == 0 {
+                spawn_basic_bullet(&mut world, get_location(), &theme);
+            } else {
+                spawn_spread_bullet(&mut world, get_location(), &theme);
+            }
+        }
+
+        let collidable_drawable_states = world.get_collidable_drawable_states();
+        let drawable_states: Vec<&DrawableState> = collidable_drawable_states.iter().collect();
+
+        let expected = get_positions_with_overlaps_full_grid(drawable_states.clone(), &dimensions);
+
+        assert!(!expected.is_empty());
+        assert_eq!(
+            get_positions_with_overlaps(drawable_states, &dimensions),
+            expected
+        );
+    }
 }
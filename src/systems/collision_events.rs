@@ -0,0 +1,57 @@
+use uuid::Uuid;
+
+use crate::{
+    api::display::Point,
+    components::{CollisionLayer, DrawableType},
+};
+
+/// What collided with what, named from the point of view of the drawable that dealt the damage
+///
+/// The kind comes from the collision layers, so an enemy is anything on the enemy layer: asteroids, flying saucers and boss shots
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CollisionEventKind {
+    BulletHitEnemy,
+    EnemyHitBullet,
+    EnemyHitPlayer,
+    PlayerHitEnemy,
+    EnemyHitEnemy,
+    PlayerCollectedPickup,
+    BulletHitBoss,
+    /// Collisions between layers without a named event, the layers of the source and the target
+    Other(CollisionLayer, CollisionLayer),
+}
+
+impl CollisionEventKind {
+    pub fn from_layers(source: CollisionLayer, target: CollisionLayer) -> Self {
+        match (source, target) {
+            (CollisionLayer::Ammunition, CollisionLayer::Enemy) => Self::BulletHitEnemy,
+            (CollisionLayer::Enemy, CollisionLayer::Ammunition) => Self::EnemyHitBullet,
+            (CollisionLayer::Enemy, CollisionLayer::Player) => Self::EnemyHitPlayer,
+            (CollisionLayer::Player, CollisionLayer::Enemy) => Self::PlayerHitEnemy,
+            (CollisionLayer::Enemy, CollisionLayer::Enemy) => Self::EnemyHitEnemy,
+            (CollisionLayer::Player, CollisionLayer::Pickup) => Self::PlayerCollectedPickup,
+            (CollisionLayer::Ammunition, CollisionLayer::Boss) => Self::BulletHitBoss,
+            (source, target) => Self::Other(source, target),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionParticipant {
+    pub uuid: Uuid,
+    pub drawable_type: DrawableType,
+}
+
+/// A collision where the source dealt damage to the target. A collision between two drawables emits an event for each drawable that was affected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionEvent {
+    pub kind: CollisionEventKind,
+    pub source: CollisionParticipant,
+    pub target: CollisionParticipant,
+    /// The first position where the drawables overlapped
+    pub position: Point<i64>,
+    /// The damage dealt to the target
+    pub damage: u32,
+    /// Points awarded if the target is destroyed
+    pub points: u64,
+}
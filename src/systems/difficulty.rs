@@ -0,0 +1,77 @@
+/// Scales how often and how fast the spawner spawns, on top of the spawn table of the game mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    /// The name used to select the difficulty in the settings file and on the command line
+    pub name: &'static str,
+    /// The percentage of the spawn table's interval that is waited between spawns, lower spawns more often
+    pub interval_percent: u128,
+    /// The percentage of the spawn table's velocities that entities are spawned with
+    pub speed_percent: i64,
+}
+
+pub const EASY_DIFFICULTY: Difficulty = Difficulty {
+    name: "easy",
+    interval_percent: 150,
+    speed_percent: 75,
+};
+
+pub const NORMAL_DIFFICULTY: Difficulty = Difficulty {
+    name: "normal",
+    interval_percent: 100,
+    speed_percent: 100,
+};
+
+pub const HARD_DIFFICULTY: Difficulty = Difficulty {
+    name: "hard",
+    interval_percent: 70,
+    speed_percent: 125,
+};
+
+pub const DIFFICULTIES: [Difficulty; 3] = [EASY_DIFFICULTY, NORMAL_DIFFICULTY, HARD_DIFFICULTY];
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        NORMAL_DIFFICULTY
+    }
+}
+
+impl Difficulty {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+
+        DIFFICULTIES
+            .iter()
+            .find(|difficulty| difficulty.name == name)
+            .copied()
+    }
+
+    pub fn get_interval(&self, interval: u128) -> u128 {
+        interval * self.interval_percent / 100
+    }
+
+    /// Scales the velocity, a moving entity is never slowed to a stop
+    pub fn get_velocity(&self, velocity: i64) -> i64 {
+        match velocity * self.speed_percent / 100 {
+            0 => velocity.signum(),
+            scaled_velocity => scaled_velocity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Difficulty, EASY_DIFFICULTY, HARD_DIFFICULTY};
+
+    #[test]
+    fn it_scales_the_interval_and_velocity() {
+        assert_eq!(HARD_DIFFICULTY.get_interval(1000), 700);
+        assert_eq!(HARD_DIFFICULTY.get_velocity(-8), -10);
+
+        // Slow entities keep moving
+        assert_eq!(EASY_DIFFICULTY.get_velocity(1), 1);
+        assert_eq!(EASY_DIFFICULTY.get_velocity(0), 0);
+
+        assert_eq!(Difficulty::from_name(" Hard "), Some(HARD_DIFFICULTY));
+        assert_eq!(Difficulty::from_name("nightmare"), None);
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use crate::components::{CollisionLayer, DrawableType};
+
+/// The damage used to destroy a drawable regardless of its health
+pub const DESTROY_DAMAGE: u32 = u32::MAX;
+
+/// What happens to a drawable when it collides with a drawable on another layer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InteractionRule {
+    /// The damage taken, None takes the damage of the other drawable, e.g. the damage of the bullet that hit an asteroid
+    pub damage: Option<u32>,
+    /// Destroys the drawable regardless of its health
+    pub destroy: bool,
+    /// The points awarded if the drawable is destroyed, None awards the points of the other drawable
+    pub points: Option<u64>,
+}
+
+impl InteractionRule {
+    /// Takes the damage of the other drawable without awarding any points
+    pub const fn take_damage() -> Self {
+        Self {
+            damage: None,
+            destroy: false,
+            points: Some(0),
+        }
+    }
+
+    /// Takes the damage of the other drawable, and awards its points when destroyed
+    pub const fn take_damage_for_points() -> Self {
+        Self {
+            damage: None,
+            destroy: false,
+            points: None,
+        }
+    }
+
+    /// Collides without taking any damage, used for drawables that bounce off each other
+    pub const fn no_damage() -> Self {
+        Self {
+            damage: Some(0),
+            destroy: false,
+            points: Some(0),
+        }
+    }
+
+    pub const fn destroy() -> Self {
+        Self {
+            damage: None,
+            destroy: true,
+            points: Some(0),
+        }
+    }
+
+    /// Returns the damage the drawable takes from the other drawable
+    pub fn get_damage(&self, other: &DrawableType) -> u32 {
+        if self.destroy {
+            return DESTROY_DAMAGE;
+        }
+
+        self.damage.unwrap_or_else(|| other.get_damage())
+    }
+
+    /// Returns the points awarded if the drawable is destroyed by the other drawable
+    pub fn get_points(&self, other: &DrawableType) -> u64 {
+        self.points.unwrap_or_else(|| other.get_points())
+    }
+}
+
+/// Collision layer of the affected drawable -> collision layer of the other drawable -> what happens to the affected drawable
+pub struct InteractionTable {
+    rules: HashMap<(CollisionLayer, CollisionLayer), InteractionRule>,
+}
+
+impl InteractionTable {
+    pub fn new() -> Self {
+        Self {
+            rules: HashMap::new(),
+        }
+    }
+
+    /// Sets what happens to drawables on the affected layer when they collide with drawables on the other layer
+    pub fn set_rule(
+        &mut self,
+        affected: CollisionLayer,
+        other: CollisionLayer,
+        rule: InteractionRule,
+    ) -> &mut Self {
+        self.rules.insert((affected, other), rule);
+
+        self
+    }
+
+    pub fn get_rule(
+        &self,
+        affected: CollisionLayer,
+        other: CollisionLayer,
+    ) -> Option<&InteractionRule> {
+        self.rules.get(&(affected, other))
+    }
+}
+
+impl Default for InteractionTable {
+    /// The rules of the game, the player and ammunition take the damage of enemies, enemies take the damage of ammunition and are destroyed when they hit the player.
+    /// Pickups are destroyed when the player collects them. Bosses take the damage of ammunition, but the player is destroyed by ramming them.
+    fn default() -> Self {
+        let mut table = InteractionTable::new();
+
+        table
+            .set_rule(
+                CollisionLayer::Player,
+                CollisionLayer::Enemy,
+                InteractionRule::take_damage(),
+            )
+            .set_rule(
+                CollisionLayer::Enemy,
+                CollisionLayer::Player,
+                InteractionRule::destroy(),
+            )
+            .set_rule(
+                CollisionLayer::Ammunition,
+                CollisionLayer::Enemy,
+                InteractionRule::take_damage(),
+            )
+            .set_rule(
+                CollisionLayer::Enemy,
+                CollisionLayer::Ammunition,
+                InteractionRule::take_damage_for_points(),
+            )
+            .set_rule(
+                CollisionLayer::Pickup,
+                CollisionLayer::Player,
+                InteractionRule::destroy(),
+            )
+            .set_rule(
+                CollisionLayer::Ammunition,
+                CollisionLayer::Boss,
+                InteractionRule::take_damage(),
+            )
+            .set_rule(
+                CollisionLayer::Boss,
+                CollisionLayer::Ammunition,
+                InteractionRule::take_damage_for_points(),
+            )
+            .set_rule(
+                CollisionLayer::Player,
+                CollisionLayer::Boss,
+                InteractionRule::destroy(),
+            );
+
+        table
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{
+    api::display::Point,
+    components::CollisionLayer,
+    entities::BossKind,
+    levels::{Edge, Level, LevelEvent, WinCondition},
+    user_display::Theme,
+    world::World,
+};
+
+use super::SpawnKind;
+
+/// A single entity waiting to be spawned, the entities at the back of a formation wait until the front has moved out of the way
+struct PendingSpawn {
+    time: u128,
+    kind: SpawnKind,
+    edge: Edge,
+    /// The center of the entity along the edge, in cells
+    along: i64,
+    velocity: Point<i64>,
+}
+
+/// Plays the timeline of a level, spawning each event into the world once its time is reached
+pub struct LevelDirector {
+    level: Level,
+    dimensions: Point<i64>,
+    theme: Theme,
+    time_elapsed: u128,
+    next_event: usize,
+    next_boss: usize,
+    pending_spawns: Vec<PendingSpawn>,
+}
+
+impl LevelDirector {
+    /// Creates a new level director
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The level to play
+    /// * `dimensions` - The dimensions of the play field
+    /// * `theme` - The theme used to color the spawned entities
+    pub fn new(level: Level, dimensions: Point<i64>, theme: &Theme) -> Self {
+        Self {
+            level,
+            dimensions,
+            theme: *theme,
+            time_elapsed: 0,
+            next_event: 0,
+            next_boss: 0,
+            pending_spawns: vec![],
+        }
+    }
+
+    pub fn handle_game_loop(&mut self, world: &mut World, game_loop_duration: u128) -> &mut Self {
+        self.time_elapsed += game_loop_duration;
+
+        while let Some(event) = self.level.events.get(self.next_event) {
+            if event.time > self.time_elapsed {
+                break;
+            }
+
+            let pending_spawns = self.get_pending_spawns(event);
+            self.pending_spawns.extend(pending_spawns);
+
+            self.next_event += 1;
+        }
+
+        let time_elapsed = self.time_elapsed;
+        let (due_spawns, pending_spawns) = std::mem::take(&mut self.pending_spawns)
+            .into_iter()
+            .partition(|spawn| spawn.time <= time_elapsed);
+
+        self.pending_spawns = pending_spawns;
+
+        for spawn in due_spawns {
+            self.spawn(world, &spawn);
+        }
+
+        self
+    }
+
+    /// Returns the next boss once its time has been reached, the boss is only taken once so it can wait for the previous boss
+    pub fn take_boss_appearance(&mut self) -> Option<BossKind> {
+        let boss = self
+            .level
+            .bosses
+            .get(self.next_boss)
+            .filter(|boss| boss.time <= self.time_elapsed)?;
+
+        self.next_boss += 1;
+
+        Some(boss.kind)
+    }
+
+    /// True once every event on the timeline has been spawned
+    pub fn is_timeline_finished(&self) -> bool {
+        self.next_event >= self.level.events.len()
+            && self.next_boss >= self.level.bosses.len()
+            && self.pending_spawns.is_empty()
+    }
+
+    /// Whether the player has met the level's win condition
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - The player's score
+    /// * `enemies_destroyed` - The number of enemies the player has destroyed
+    pub fn is_complete(&self, world: &World, score: u64, enemies_destroyed: u32) -> bool {
+        match self.level.win_condition {
+            WinCondition::Survive(duration) => self.time_elapsed >= duration,
+            WinCondition::Score(points) => score >= points,
+            WinCondition::Destroy(count) => enemies_destroyed >= count,
+            WinCondition::Clear => {
+                self.is_timeline_finished()
+                    && world.count_on_layer(CollisionLayer::Enemy) == 0
+                    && world.count_on_layer(CollisionLayer::Boss) == 0
+            }
+        }
+    }
+
+    /// Splits an event into the entities of its formation. The entities behind the front of the formation are delayed by the
+    /// time it takes to cover the distance at the event's speed, since spawning them further out would cull them straight away.
+    fn get_pending_spawns(&self, event: &LevelEvent) -> Vec<PendingSpawn> {
+        let edge_length = match event.edge {
+            Edge::Top | Edge::Bottom => self.dimensions.width,
+            Edge::Left | Edge::Right => self.dimensions.height,
+        };
+
+        let speed = match event.edge {
+            Edge::Top | Edge::Bottom => event.velocity.height.abs(),
+            Edge::Left | Edge::Right => event.velocity.width.abs(),
+        };
+
+        let offsets = event
+            .formation
+            .map(|formation| formation.get_offsets())
+            .unwrap_or(vec![(0, 0)]);
+
+        offsets
+            .into_iter()
+            .map(|(along, behind)| PendingSpawn {
+                time: match speed {
+                    0 => event.time,
+                    speed => event.time + behind.max(0) as u128 * 1000 / speed as u128,
+                },
+                kind: event.kind,
+                edge: event.edge,
+                along: edge_length * event.position / 100 + along,
+                velocity: event.velocity,
+            })
+            .collect()
+    }
+
+    fn spawn(&self, world: &mut World, spawn: &PendingSpawn) {
+        // The location depends on the size of the sprite, so the entity is moved once it has been spawned
+        let entity = spawn
+            .kind
+            .spawn(world, Point::default(), spawn.velocity, &self.theme);
+
+        let size = world
+            .sprites
+            .get(entity)
+            .map(|sprite| sprite.get_layout().dimensions)
+            .unwrap_or_default();
+
+        let dimensions = self.dimensions;
+
+        let location = match spawn.edge {
+            Edge::Top => Point::new(spawn.along - size.width / 2, -size.height),
+            Edge::Bottom => Point::new(spawn.along - size.width / 2, dimensions.height),
+            Edge::Left => Point::new(-size.width, spawn.along - size.height / 2),
+            Edge::Right => Point::new(dimensions.width, spawn.along - size.height / 2),
+        };
+
+        world.positions.insert(entity, location);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        components::CollisionLayer,
+        levels::{Edge, Formation, Level, LevelEvent, WinCondition},
+        systems::SpawnKind,
+        user_display::Theme,
+        world::World,
+    };
+
+    use super::LevelDirector;
+
+    fn get_level_mock(win_condition: WinCondition) -> Level {
+        Level {
+            name: "Test".to_string(),
+            win_condition,
+            events: vec![LevelEvent {
+                time: 1000,
+                kind: SpawnKind::Asteroid,
+                edge: Edge::Top,
+                position: 50,
+                velocity: Point::new(0, 10),
+                formation: Some(Formation::Column {
+                    count: 2,
+                    spacing: 5,
+                }),
+            }],
+            bosses: vec![],
+        }
+    }
+
+    #[test]
+    fn it_spawns_events_when_their_time_is_reached() {
+        let mut world = World::new();
+        let mut director = LevelDirector::new(
+            get_level_mock(WinCondition::Clear),
+            Point::new(40, 20),
+            &Theme::default(),
+        );
+
+        director.handle_game_loop(&mut world, 999);
+
+        assert_eq!(world.count_on_layer(CollisionLayer::Enemy), 0);
+
+        director.handle_game_loop(&mut world, 1);
+
+        assert_eq!(world.count_on_layer(CollisionLayer::Enemy), 1);
+        assert!(!director.is_timeline_finished());
+
+        // The second asteroid is 5 cells behind at 10 cells a second
+        director.handle_game_loop(&mut world, 500);
+
+        assert_eq!(world.count_on_layer(CollisionLayer::Enemy), 2);
+        assert!(director.is_timeline_finished());
+
+        // Asteroids are centered on the position and spawn just above the play field
+        for entity in world.get_entities() {
+            let location = world.positions.get(entity).unwrap();
+
+            assert!(location.height < 0);
+            assert!((location.width - 20).abs() < 10);
+        }
+    }
+
+    #[test]
+    fn it_checks_the_win_condition() {
+        let mut world = World::new();
+        let mut director = LevelDirector::new(
+            get_level_mock(WinCondition::Clear),
+            Point::new(40, 20),
+            &Theme::default(),
+        );
+
+        director.handle_game_loop(&mut world, 2000);
+
+        assert!(!director.is_complete(&world, 0, 0));
+
+        for entity in world.get_entities() {
+            world.despawn(entity);
+        }
+
+        assert!(director.is_complete(&world, 0, 0));
+
+        let director = LevelDirector::new(
+            get_level_mock(WinCondition::Score(10)),
+            Point::new(40, 20),
+            &Theme::default(),
+        );
+
+        assert!(!director.is_complete(&world, 9, 0));
+        assert!(director.is_complete(&world, 10, 0));
+    }
+}
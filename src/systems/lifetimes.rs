@@ -0,0 +1,40 @@
+use crate::world::World;
+
+/// Counts down the lifetimes and despawns the entities that have run out of time, such as explosions and classic bullets
+pub fn run_lifetime_system(world: &mut World, game_loop_duration: u128) {
+    let mut expired_entities = vec![];
+
+    for (entity, lifetime) in world.lifetimes.iter_mut() {
+        *lifetime = lifetime.saturating_sub(game_loop_duration);
+
+        if *lifetime == 0 {
+            expired_entities.push(*entity);
+        }
+    }
+
+    for entity in expired_entities {
+        world.despawn(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::world::World;
+
+    use super::run_lifetime_system;
+
+    #[test]
+    fn it_despawns_entities_once_their_lifetime_runs_out() {
+        let mut world = World::new();
+
+        let entity = world.spawn().with_lifetime(100).build();
+        let permanent = world.spawn().build();
+
+        run_lifetime_system(&mut world, 60);
+        assert!(world.has_entity(entity));
+
+        run_lifetime_system(&mut world, 60);
+        assert!(!world.has_entity(entity));
+        assert!(world.has_entity(permanent));
+    }
+}
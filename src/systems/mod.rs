@@ -1,7 +1,27 @@
-mod asteroid_controller;
+mod animations;
+mod bounds;
 mod collision_detection;
-mod entity_controller;
+mod collision_events;
+mod difficulty;
+mod interaction_table;
+mod level_director;
+mod lifetimes;
+mod movement;
+mod physics;
+mod spawn_commands;
+mod spawn_tables;
+mod spawner;
 
-pub use asteroid_controller::*;
+pub use animations::*;
+pub use bounds::*;
 pub use collision_detection::*;
-pub use entity_controller::*;
+pub use collision_events::*;
+pub use difficulty::*;
+pub use interaction_table::*;
+pub use level_director::*;
+pub use lifetimes::*;
+pub use movement::*;
+pub use physics::*;
+pub use spawn_commands::*;
+pub use spawn_tables::*;
+pub use spawner::*;
@@ -0,0 +1,81 @@
+use crate::{
+    api::display::Point,
+    components::{get_clamped_location, get_rated_velocity, get_wrapped_location, CollisionLayer},
+    world::World,
+};
+
+/// Moves every entity that has a position and a velocity. The player is blocked from leaving the screen and everything else is free
+/// to move off it, where the bounds system culls it once it is past the cull margin. When the screen wraps everything wraps around the
+/// edges instead.
+///
+/// # Arguments
+///
+/// * `dimensions` - The dimensions of the play field
+/// * `screen_wrap` - Wraps every entity around the edges of the play field
+pub fn run_movement_system(
+    world: &mut World,
+    dimensions: &Point<i64>,
+    game_loop_duration: u128,
+    screen_wrap: bool,
+) {
+    for entity in world.get_entities() {
+        let (Some(location), Some(velocity)) = (
+            world.positions.get(entity).copied(),
+            world.velocities.get(entity).copied(),
+        ) else {
+            continue;
+        };
+
+        let size = world
+            .sprites
+            .get(entity)
+            .map(|sprite| sprite.get_layout().dimensions)
+            .unwrap_or_default();
+
+        let mut updated_location = location + get_rated_velocity(velocity, game_loop_duration);
+
+        if screen_wrap {
+            updated_location = get_wrapped_location(updated_location, size, dimensions);
+        } else if world.colliders.get(entity) == Some(&CollisionLayer::Player) {
+            updated_location = get_clamped_location(updated_location, size, dimensions);
+        }
+
+        world.positions.insert(entity, updated_location);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::display::Point, components::CollisionLayer, world::World};
+
+    use super::run_movement_system;
+
+    const DIMENSIONS: &Point<i64> = &Point::new(20, 10);
+
+    #[test]
+    fn it_blocks_the_player_and_wraps_everything_when_the_screen_wraps() {
+        let mut world = World::new();
+
+        let player = world
+            .spawn()
+            .with_position(Point::new(18, 5))
+            .with_velocity(Point::new(20, 0))
+            .with_collider(CollisionLayer::Player)
+            .build();
+        let asteroid = world
+            .spawn()
+            .with_position(Point::new(18, 5))
+            .with_velocity(Point::new(20, 0))
+            .build();
+
+        run_movement_system(&mut world, DIMENSIONS, 1000, false);
+
+        assert_eq!(world.positions.get(player), Some(&Point::new(19, 5)));
+        assert_eq!(world.positions.get(asteroid), Some(&Point::new(38, 5)));
+
+        run_movement_system(&mut world, DIMENSIONS, 1000, true);
+
+        assert_eq!(world.positions.get(player), Some(&Point::new(19, 5)));
+        assert_eq!(world.positions.get(asteroid), Some(&Point::new(18, 5)));
+    }
+}
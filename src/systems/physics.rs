@@ -0,0 +1,185 @@
+use crate::{api::display::Point, components::DrawableState};
+
+/// The new velocities and locations of two drawables after they bounce off each other
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BounceOutcome {
+    pub first_velocity: Point<i64>,
+    pub first_location: Point<i64>,
+    pub second_velocity: Point<i64>,
+    pub second_location: Point<i64>,
+}
+
+/// The mass of a drawable is the number of cells its layout fills, so larger sprites push smaller sprites around
+pub fn get_mass(drawable_state: &DrawableState) -> f64 {
+    let mass = drawable_state.layout.map.iter().flatten().flatten().count();
+
+    // An empty layout would divide by zero when bouncing
+    mass.max(1) as f64
+}
+
+fn get_center(drawable_state: &DrawableState) -> (f64, f64) {
+    let dimensions = drawable_state.layout.dimensions;
+
+    (
+        drawable_state.location.width as f64 + dimensions.width as f64 / 2.0,
+        drawable_state.location.height as f64 + dimensions.height as f64 / 2.0,
+    )
+}
+
+/// How far the bounding boxes overlap along one axis, 0 if they don't overlap
+fn get_overlap(first_start: i64, first_size: i64, second_start: i64, second_size: i64) -> i64 {
+    let start = first_start.max(second_start);
+    let end = (first_start + first_size).min(second_start + second_size);
+
+    (end - start).max(0)
+}
+
+/// Calculates an elastic collision between two overlapping drawables. The velocities are exchanged along the contact normal, which
+/// runs between the centers of the drawables, and the drawables are pushed apart along the axis they overlap the least so they
+/// don't stick together.
+pub fn get_bounce(first: &DrawableState, second: &DrawableState) -> BounceOutcome {
+    let first_center = get_center(first);
+    let second_center = get_center(second);
+
+    let (mut normal_width, mut normal_height) = (
+        second_center.0 - first_center.0,
+        second_center.1 - first_center.1,
+    );
+
+    let length = (normal_width * normal_width + normal_height * normal_height).sqrt();
+
+    // Drawables with the same center are pushed apart horizontally
+    if length == 0.0 {
+        (normal_width, normal_height) = (1.0, 0.0);
+    } else {
+        (normal_width, normal_height) = (normal_width / length, normal_height / length);
+    }
+
+    let first_mass = get_mass(first);
+    let second_mass = get_mass(second);
+
+    let mut first_velocity = first.velocity;
+    let mut second_velocity = second.velocity;
+
+    let relative_velocity = (first.velocity.width - second.velocity.width) as f64 * normal_width
+        + (first.velocity.height - second.velocity.height) as f64 * normal_height;
+
+    // Drawables that are already moving apart keep their velocities, otherwise they would be pulled back together
+    if relative_velocity > 0.0 {
+        let total_mass = first_mass + second_mass;
+        let first_change = 2.0 * second_mass / total_mass * relative_velocity;
+        let second_change = 2.0 * first_mass / total_mass * relative_velocity;
+
+        first_velocity = Point::new(
+            (first.velocity.width as f64 - first_change * normal_width).round() as i64,
+            (first.velocity.height as f64 - first_change * normal_height).round() as i64,
+        );
+        second_velocity = Point::new(
+            (second.velocity.width as f64 + second_change * normal_width).round() as i64,
+            (second.velocity.height as f64 + second_change * normal_height).round() as i64,
+        );
+    }
+
+    let overlap_width = get_overlap(
+        first.location.width,
+        first.layout.dimensions.width,
+        second.location.width,
+        second.layout.dimensions.width,
+    );
+    let overlap_height = get_overlap(
+        first.location.height,
+        first.layout.dimensions.height,
+        second.location.height,
+        second.layout.dimensions.height,
+    );
+
+    let mut first_location = first.location;
+    let mut second_location = second.location;
+
+    // Each drawable moves half of the overlap, rounding up so they always end up apart
+    if overlap_width <= overlap_height {
+        let separation = (overlap_width + 1) / 2;
+        let direction = if normal_width < 0.0 { -1 } else { 1 };
+
+        first_location.width -= separation * direction;
+        second_location.width += separation * direction;
+    } else {
+        let separation = (overlap_height + 1) / 2;
+        let direction = if normal_height < 0.0 { -1 } else { 1 };
+
+        first_location.height -= separation * direction;
+        second_location.height += separation * direction;
+    }
+
+    BounceOutcome {
+        first_velocity,
+        first_location,
+        second_velocity,
+        second_location,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::{
+        api::display::{Layout, Point},
+        components::{DrawableState, DrawableType},
+    };
+
+    use super::{get_bounce, get_mass};
+
+    fn get_drawable_mock(ascii: &str, location: Point<i64>, velocity: Point<i64>) -> DrawableState {
+        DrawableState::new(
+            Layout::from_ascii(ascii, Color::White),
+            location,
+            DrawableType::Enemy(1),
+            Some(velocity),
+        )
+    }
+
+    #[test]
+    fn it_exchanges_velocities_of_equal_masses_head_on() {
+        let first = get_drawable_mock("###", Point::new(0, 0), Point::new(10, 0));
+        let second = get_drawable_mock("###", Point::new(2, 0), Point::new(-4, 0));
+
+        let outcome = get_bounce(&first, &second);
+
+        assert_eq!(outcome.first_velocity, Point::new(-4, 0));
+        assert_eq!(outcome.second_velocity, Point::new(10, 0));
+
+        // The single overlapping cell is resolved by pushing both apart
+        assert_eq!(outcome.first_location, Point::new(-1, 0));
+        assert_eq!(outcome.second_location, Point::new(3, 0));
+    }
+
+    #[test]
+    fn it_uses_the_sprite_size_as_mass() {
+        let small = get_drawable_mock("#", Point::new(0, 0), Point::new(10, 0));
+        let large = get_drawable_mock("####\n####", Point::new(0, 0), Point::new(0, 0));
+
+        assert_eq!(get_mass(&small), 1.0);
+        assert_eq!(get_mass(&large), 8.0);
+
+        let small = get_drawable_mock("#", Point::new(1, 0), Point::new(10, 0));
+        let large = get_drawable_mock("####", Point::new(1, 0), Point::new(0, 0));
+
+        let outcome = get_bounce(&small, &large);
+
+        // The small drawable bounces back while the large drawable only gains a little speed
+        assert!(outcome.first_velocity.width < 0);
+        assert!(outcome.second_velocity.width > 0 && outcome.second_velocity.width < 10);
+    }
+
+    #[test]
+    fn it_keeps_the_velocities_of_drawables_moving_apart() {
+        let first = get_drawable_mock("##", Point::new(0, 0), Point::new(-5, 0));
+        let second = get_drawable_mock("##", Point::new(1, 0), Point::new(5, 0));
+
+        let outcome = get_bounce(&first, &second);
+
+        assert_eq!(outcome.first_velocity, first.velocity);
+        assert_eq!(outcome.second_velocity, second.velocity);
+    }
+}
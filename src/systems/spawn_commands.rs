@@ -0,0 +1,14 @@
+use crate::{
+    components::SpawnCommand, entities::spawn_explosion, user_display::Theme, world::World,
+};
+
+/// Spawns the follow-up entities returned by lifecycle hooks, e.g. the explosion of a destroyed asteroid
+pub fn run_spawn_command_system(world: &mut World, theme: &Theme) {
+    for spawn_command in world.take_spawn_commands() {
+        match spawn_command {
+            SpawnCommand::Explosion(position) => {
+                spawn_explosion(world, position, theme);
+            }
+        }
+    }
+}
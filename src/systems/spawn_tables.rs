@@ -0,0 +1,131 @@
+use crate::{api::display::Point, entities::BossKind};
+
+use super::{
+    BossCondition, BossTrigger, IntervalCurve, SpawnArea, SpawnEntry, SpawnKind, SpawnTable,
+};
+
+const UFO_FROM_LEFT: SpawnEntry = SpawnEntry {
+    kind: SpawnKind::Ufo,
+    weight: 1,
+    area: SpawnArea::Left,
+    velocity_width: 10..20,
+    velocity_height: 0..0,
+    min_concurrent: 0,
+    max_concurrent: Some(1),
+};
+
+const UFO_FROM_RIGHT: SpawnEntry = SpawnEntry {
+    kind: SpawnKind::Ufo,
+    weight: 1,
+    area: SpawnArea::Right,
+    velocity_width: -20..-10,
+    velocity_height: 0..0,
+    min_concurrent: 0,
+    max_concurrent: Some(1),
+};
+
+const FALLING_PICKUP: SpawnEntry = SpawnEntry {
+    kind: SpawnKind::Pickup,
+    weight: 1,
+    area: SpawnArea::Top,
+    velocity_width: 0..0,
+    velocity_height: 5..10,
+    min_concurrent: 0,
+    max_concurrent: Some(1),
+};
+
+/// Asteroids rain down from the top of the screen, spawning faster as the game goes on
+pub static STANDARD_SPAWN_TABLE: SpawnTable = SpawnTable {
+    entries: &[
+        SpawnEntry {
+            kind: SpawnKind::Asteroid,
+            weight: 300,
+            area: SpawnArea::Top,
+            velocity_width: -20..20,
+            velocity_height: 1..40,
+            min_concurrent: 0,
+            max_concurrent: None,
+        },
+        UFO_FROM_LEFT,
+        UFO_FROM_RIGHT,
+        FALLING_PICKUP,
+    ],
+    interval: IntervalCurve {
+        start: 100,
+        end: 70,
+        ramp_duration: 180_000,
+    },
+    wave_size: 400,
+    bosses: &[
+        BossTrigger {
+            kind: BossKind::Mothership,
+            condition: BossCondition::Wave(3),
+        },
+        BossTrigger {
+            kind: BossKind::Mothership,
+            condition: BossCondition::Score(2000),
+        },
+    ],
+};
+
+/// Asteroids drift in from every edge, the screen wraps so asteroids never leave and the number of asteroids is capped
+pub static CLASSIC_SPAWN_TABLE: SpawnTable = SpawnTable {
+    entries: &[
+        SpawnEntry {
+            kind: SpawnKind::Asteroid,
+            weight: 100,
+            area: SpawnArea::Top,
+            velocity_width: -15..15,
+            velocity_height: 1..15,
+            min_concurrent: 1,
+            max_concurrent: Some(3),
+        },
+        SpawnEntry {
+            kind: SpawnKind::Asteroid,
+            weight: 100,
+            area: SpawnArea::Bottom,
+            velocity_width: -15..15,
+            velocity_height: -15..-1,
+            min_concurrent: 1,
+            max_concurrent: Some(3),
+        },
+        SpawnEntry {
+            kind: SpawnKind::Asteroid,
+            weight: 100,
+            area: SpawnArea::Left,
+            velocity_width: 1..25,
+            velocity_height: -10..10,
+            min_concurrent: 0,
+            max_concurrent: Some(3),
+        },
+        SpawnEntry {
+            kind: SpawnKind::Asteroid,
+            weight: 100,
+            area: SpawnArea::Right,
+            velocity_width: -25..-1,
+            velocity_height: -10..10,
+            min_concurrent: 0,
+            max_concurrent: Some(3),
+        },
+        UFO_FROM_LEFT,
+        SpawnEntry {
+            kind: SpawnKind::Pickup,
+            weight: 1,
+            area: SpawnArea::Region {
+                start: Point::new(10, 10),
+                end: Point::new(90, 90),
+            },
+            velocity_width: 0..0,
+            velocity_height: 0..0,
+            min_concurrent: 0,
+            max_concurrent: Some(1),
+        },
+    ],
+    interval: IntervalCurve::constant(100),
+    // Asteroids are capped, so a wave only moves on as asteroids are destroyed
+    wave_size: 40,
+    bosses: &[BossTrigger {
+        kind: BossKind::Mothership,
+        condition: BossCondition::Wave(2),
+    }],
+};
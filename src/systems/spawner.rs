@@ -0,0 +1,452 @@
+use std::ops::Range;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    api::display::Point,
+    entities::{spawn_asteroid, spawn_pickup, spawn_ufo, BossKind},
+    systems::Difficulty,
+    user_display::Theme,
+    world::{Entity, World},
+};
+
+/// The kinds of entity a spawner can spawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpawnKind {
+    Asteroid,
+    Ufo,
+    Pickup,
+}
+
+impl SpawnKind {
+    /// Spawns an entity of this kind into the world
+    pub fn spawn(
+        &self,
+        world: &mut World,
+        location: Point<i64>,
+        velocity: Point<i64>,
+        theme: &Theme,
+    ) -> Entity {
+        let spawn = match self {
+            SpawnKind::Asteroid => spawn_asteroid,
+            SpawnKind::Ufo => spawn_ufo,
+            SpawnKind::Pickup => spawn_pickup,
+        };
+
+        spawn(world, location, velocity, theme)
+    }
+}
+
+/// Where entities are spawned, entities spawned on an edge start just outside of the play field at a random point along the edge
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpawnArea {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    /// A region of the play field, the start and end are percentages of the play field's width and height
+    Region {
+        start: Point<i64>,
+        end: Point<i64>,
+    },
+}
+
+/// A row of a spawn table
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnEntry {
+    pub kind: SpawnKind,
+    /// How likely this entry is picked compared to the other entries in the table
+    pub weight: u32,
+    pub area: SpawnArea,
+    /// The velocities are picked at random from the ranges, in cells per second
+    pub velocity_width: Range<i64>,
+    pub velocity_height: Range<i64>,
+    /// The entry is spawned straight away, without waiting for the interval, until this many of its entities are alive
+    pub min_concurrent: usize,
+    /// The entry isn't picked while this many of its entities are alive, None has no limit
+    pub max_concurrent: Option<usize>,
+}
+
+/// The time between spawns, which moves linearly from the start to the end interval as the game goes on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntervalCurve {
+    /// In milliseconds
+    pub start: u128,
+    /// In milliseconds
+    pub end: u128,
+    /// How long it takes to reach the end interval, in milliseconds
+    pub ramp_duration: u128,
+}
+
+impl IntervalCurve {
+    pub const fn constant(interval: u128) -> Self {
+        Self {
+            start: interval,
+            end: interval,
+            ramp_duration: 0,
+        }
+    }
+
+    /// The interval once the game has been running for the time elapsed
+    pub fn get_interval(&self, time_elapsed: u128) -> u128 {
+        if time_elapsed >= self.ramp_duration {
+            return self.end;
+        }
+
+        let progress = time_elapsed as f64 / self.ramp_duration as f64;
+
+        (self.start as f64 + (self.end as f64 - self.start as f64) * progress).round() as u128
+    }
+}
+
+/// When a boss appears, each trigger only fires once per game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossCondition {
+    /// Once the wave has been reached
+    Wave(u32),
+    /// Once the player's score has reached the points
+    Score(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BossTrigger {
+    pub kind: BossKind,
+    pub condition: BossCondition,
+}
+
+/// What a spawner spawns, and how often
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnTable {
+    pub entries: &'static [SpawnEntry],
+    pub interval: IntervalCurve,
+    /// The number of entities spawned in each wave, the game starts on the first wave
+    pub wave_size: u32,
+    pub bosses: &'static [BossTrigger],
+}
+
+/// Spawns entities into the world from a spawn table. Every interval one entry is picked at random by weight, skipping the entries
+/// that are at their max concurrent entities.
+pub struct Spawner {
+    table: &'static SpawnTable,
+    dimensions: Point<i64>,
+    theme: Theme,
+    rng: StdRng,
+    difficulty: Difficulty,
+    // Same approach as the animations, the game_loop_duration is accumulated each loop rather than fetching the system time
+    time_elapsed: u128,
+    time_elapsed_since_spawn: u128,
+    /// The entities spawned by each entry of the table that are still alive
+    spawned_entities: Vec<Vec<Entity>>,
+    spawn_count: u32,
+    /// Whether each boss trigger of the table has fired
+    triggered_bosses: Vec<bool>,
+}
+
+impl Spawner {
+    /// Creates a new spawner
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - What to spawn, essentially sets the difficulty
+    /// * `dimensions` - The dimensions of the play field
+    /// * `theme` - The theme used to color the spawned entities
+    pub fn new(table: &'static SpawnTable, dimensions: Point<i64>, theme: &Theme) -> Self {
+        Self {
+            table,
+            dimensions,
+            theme: *theme,
+            rng: StdRng::from_entropy(),
+            difficulty: Difficulty::default(),
+            time_elapsed: 0,
+            time_elapsed_since_spawn: 0,
+            spawned_entities: vec![vec![]; table.entries.len()],
+            spawn_count: 0,
+            triggered_bosses: vec![false; table.bosses.len()],
+        }
+    }
+
+    /// Spawns the same entities at the same times and places in every game with the seed, given the same frame durations
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+
+        self
+    }
+
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+
+        self
+    }
+
+    /// The current wave, starting from 1
+    pub fn get_wave(&self) -> u32 {
+        self.spawn_count / self.table.wave_size.max(1) + 1
+    }
+
+    /// Returns the first boss whose trigger has been met and hasn't fired yet, the trigger won't fire again
+    ///
+    /// # Arguments
+    ///
+    /// * `score` - The player's score
+    pub fn take_triggered_boss(&mut self, score: u64) -> Option<BossKind> {
+        let wave = self.get_wave();
+
+        let index = self
+            .table
+            .bosses
+            .iter()
+            .enumerate()
+            .position(|(index, trigger)| {
+                !self.triggered_bosses[index]
+                    && match trigger.condition {
+                        BossCondition::Wave(trigger_wave) => wave >= trigger_wave,
+                        BossCondition::Score(points) => score >= points,
+                    }
+            })?;
+
+        self.triggered_bosses[index] = true;
+
+        Some(self.table.bosses[index].kind)
+    }
+
+    pub fn handle_game_loop(&mut self, world: &mut World, game_loop_duration: u128) -> &mut Self {
+        for entities in self.spawned_entities.iter_mut() {
+            entities.retain(|entity| world.has_entity(*entity));
+        }
+
+        for (index, entry) in self.table.entries.iter().enumerate() {
+            while self.spawned_entities[index].len() < entry.min_concurrent {
+                self.spawn_entry(world, index);
+            }
+        }
+
+        self.time_elapsed += game_loop_duration;
+        self.time_elapsed_since_spawn += game_loop_duration;
+
+        let interval = self
+            .difficulty
+            .get_interval(self.table.interval.get_interval(self.time_elapsed));
+
+        if self.time_elapsed_since_spawn > interval {
+            self.time_elapsed_since_spawn = 0;
+
+            if let Some(index) = self.pick_entry() {
+                self.spawn_entry(world, index);
+            }
+        }
+
+        self
+    }
+
+    /// Picks an entry at random by weight, from the entries that aren't at their max concurrent entities
+    fn pick_entry(&mut self) -> Option<usize> {
+        let available_entries: Vec<(usize, u32)> = self
+            .table
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(index, entry)| {
+                entry
+                    .max_concurrent
+                    .is_none_or(|max| self.spawned_entities[*index].len() < max)
+            })
+            .map(|(index, entry)| (index, entry.weight))
+            .collect();
+
+        let total_weight: u32 = available_entries.iter().map(|(_, weight)| weight).sum();
+
+        if total_weight == 0 {
+            return None;
+        }
+
+        let mut pick = self.rng.gen_range(0..total_weight);
+
+        for (index, weight) in available_entries {
+            if pick < weight {
+                return Some(index);
+            }
+
+            pick -= weight;
+        }
+
+        None
+    }
+
+    fn get_random_in_range(&mut self, range: &Range<i64>) -> i64 {
+        if range.is_empty() {
+            return range.start;
+        }
+
+        self.rng.gen_range(range.clone())
+    }
+
+    /// The top left of an entity of the size, spawned in the area
+    fn get_spawn_location(&mut self, area: &SpawnArea, size: Point<i64>) -> Point<i64> {
+        let dimensions = self.dimensions;
+
+        match area {
+            SpawnArea::Top => Point::new(
+                self.get_random_in_range(&(0..dimensions.width)),
+                -size.height,
+            ),
+            SpawnArea::Bottom => Point::new(
+                self.get_random_in_range(&(0..dimensions.width)),
+                dimensions.height,
+            ),
+            SpawnArea::Left => Point::new(
+                -size.width,
+                self.get_random_in_range(&(0..dimensions.height)),
+            ),
+            SpawnArea::Right => Point::new(
+                dimensions.width,
+                self.get_random_in_range(&(0..dimensions.height)),
+            ),
+            SpawnArea::Region { start, end } => Point::new(
+                self.get_random_in_range(
+                    &(dimensions.width * start.width / 100..dimensions.width * end.width / 100),
+                ),
+                self.get_random_in_range(
+                    &(dimensions.height * start.height / 100..dimensions.height * end.height / 100),
+                ),
+            ),
+        }
+    }
+
+    fn spawn_entry(&mut self, world: &mut World, index: usize) -> Entity {
+        let entry = &self.table.entries[index];
+
+        let velocity_width = self.get_random_in_range(&entry.velocity_width);
+        let velocity_height = self.get_random_in_range(&entry.velocity_height);
+
+        let velocity = Point::new(
+            self.difficulty.get_velocity(velocity_width),
+            self.difficulty.get_velocity(velocity_height),
+        );
+
+        // The location depends on the size of the sprite, so the entity is moved once it has been spawned
+        let entity = entry
+            .kind
+            .spawn(world, Point::default(), velocity, &self.theme);
+
+        let size = world
+            .sprites
+            .get(entity)
+            .map(|sprite| sprite.get_layout().dimensions)
+            .unwrap_or_default();
+
+        let location = self.get_spawn_location(&entry.area, size);
+        world.positions.insert(entity, location);
+
+        self.spawned_entities[index].push(entity);
+        self.spawn_count += 1;
+
+        entity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point, components::CollisionLayer, user_display::Theme, world::World,
+    };
+
+    use crate::entities::BossKind;
+
+    use super::{
+        BossCondition, BossTrigger, IntervalCurve, SpawnArea, SpawnEntry, SpawnKind, SpawnTable,
+        Spawner,
+    };
+
+    static TABLE: SpawnTable = SpawnTable {
+        entries: &[
+            SpawnEntry {
+                kind: SpawnKind::Asteroid,
+                weight: 1,
+                area: SpawnArea::Top,
+                velocity_width: 0..0,
+                velocity_height: 1..10,
+                min_concurrent: 1,
+                max_concurrent: Some(3),
+            },
+            SpawnEntry {
+                kind: SpawnKind::Pickup,
+                weight: 0,
+                area: SpawnArea::Region {
+                    start: Point::new(0, 0),
+                    end: Point::new(100, 100),
+                },
+                velocity_width: 0..0,
+                velocity_height: 0..0,
+                min_concurrent: 0,
+                max_concurrent: None,
+            },
+        ],
+        interval: IntervalCurve::constant(100),
+        wave_size: 2,
+        bosses: &[
+            BossTrigger {
+                kind: BossKind::Mothership,
+                condition: BossCondition::Wave(2),
+            },
+            BossTrigger {
+                kind: BossKind::Mothership,
+                condition: BossCondition::Score(100),
+            },
+        ],
+    };
+
+    #[test]
+    fn it_keeps_the_concurrent_entities_between_the_min_and_max() {
+        let mut world = World::new();
+        let mut spawner = Spawner::new(&TABLE, Point::new(40, 20), &Theme::default());
+
+        spawner.handle_game_loop(&mut world, 0);
+
+        assert_eq!(world.count_on_layer(CollisionLayer::Enemy), 1);
+
+        for _ in 0..20 {
+            spawner.handle_game_loop(&mut world, 150);
+        }
+
+        // Entries with no weight are never picked
+        assert_eq!(world.count_on_layer(CollisionLayer::Enemy), 3);
+        assert_eq!(world.count_on_layer(CollisionLayer::Pickup), 0);
+
+        // Asteroids spawn just above the play field
+        for entity in world.get_entities() {
+            assert!(world.positions.get(entity).unwrap().height < 0);
+        }
+    }
+
+    #[test]
+    fn it_moves_the_interval_along_the_curve() {
+        let curve = IntervalCurve {
+            start: 200,
+            end: 100,
+            ramp_duration: 1000,
+        };
+
+        assert_eq!(curve.get_interval(0), 200);
+        assert_eq!(curve.get_interval(500), 150);
+        assert_eq!(curve.get_interval(5000), 100);
+    }
+
+    #[test]
+    fn it_triggers_each_boss_once() {
+        let mut world = World::new();
+        let mut spawner = Spawner::new(&TABLE, Point::new(40, 20), &Theme::default());
+
+        assert_eq!(spawner.take_triggered_boss(0), None);
+
+        // The first spawn is straight away, the second after the interval
+        spawner.handle_game_loop(&mut world, 0);
+        spawner.handle_game_loop(&mut world, 150);
+
+        assert_eq!(spawner.get_wave(), 2);
+        assert_eq!(spawner.take_triggered_boss(0), Some(BossKind::Mothership));
+        assert_eq!(spawner.take_triggered_boss(0), None);
+
+        assert_eq!(spawner.take_triggered_boss(100), Some(BossKind::Mothership));
+        assert_eq!(spawner.take_triggered_boss(100), None);
+    }
+}
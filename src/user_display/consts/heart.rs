@@ -1,3 +1,5 @@
+pub const HEART_SPRITE: &str = "heart";
+
 pub const HEART: &str = ",d88b.d88b,
 88888888888
 `Y8888888Y'
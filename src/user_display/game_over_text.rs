@@ -1,3 +1,5 @@
+pub const GAME_OVER_SPRITE: &str = "game_over";
+
 pub const GAME_OVER_TEXT: &str = " $$$$$$\\                                           $$$$$$\\                                 
 $$  __$$\\                                         $$  __$$\\                                
 $$ /  \\__| $$$$$$\\  $$$$$$\\$$$$\\   $$$$$$\\        $$ /  $$ |$$\\    $$\\  $$$$$$\\   $$$$$$\\  
@@ -1,7 +1,7 @@
 mod consts;
-mod difficulty_display;
 mod game_over_text;
+mod theme;
 
 pub use consts::*;
-pub use difficulty_display::*;
 pub use game_over_text::*;
+pub use theme::*;
@@ -0,0 +1,163 @@
+use crossterm::style::Color;
+
+/// The colors used to draw the game. The HUD colors are always used, the sprite colors replace the colors from the sprite
+/// files when they are set, so None keeps the colors the sprite was drawn with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// The name used to select the theme in the settings file
+    pub name: &'static str,
+    pub border: Color,
+    pub lives: Color,
+    pub score: Color,
+    pub text: Color,
+    pub warning: Color,
+    pub player: Option<Color>,
+    /// The second ship in co-op, always set so the ships can be told apart
+    pub second_player: Option<Color>,
+    pub asteroid: Option<Color>,
+    pub bullet: Option<Color>,
+    pub explosion: Option<Color>,
+    pub heart: Option<Color>,
+    pub game_over: Option<Color>,
+}
+
+pub const CLASSIC_THEME: Theme = Theme {
+    name: "classic",
+    border: Color::Cyan,
+    lives: Color::Red,
+    score: Color::Cyan,
+    text: Color::Green,
+    warning: Color::Red,
+    player: None,
+    second_player: Some(Color::Cyan),
+    asteroid: None,
+    bullet: None,
+    explosion: None,
+    heart: None,
+    game_over: None,
+};
+
+pub const HIGH_CONTRAST_THEME: Theme = Theme {
+    name: "high_contrast",
+    border: Color::White,
+    lives: Color::Yellow,
+    score: Color::White,
+    text: Color::White,
+    warning: Color::Yellow,
+    player: Some(Color::Cyan),
+    second_player: Some(Color::Magenta),
+    asteroid: Some(Color::Yellow),
+    bullet: Some(Color::White),
+    explosion: Some(Color::Yellow),
+    heart: Some(Color::Yellow),
+    game_over: Some(Color::White),
+};
+
+// Uses the blue and orange colors from the Okabe-Ito palette, which stay distinct with deuteranopia and protanopia
+const SKY_BLUE: Color = Color::Rgb {
+    r: 86,
+    g: 180,
+    b: 233,
+};
+const BLUE: Color = Color::Rgb {
+    r: 0,
+    g: 114,
+    b: 178,
+};
+const ORANGE: Color = Color::Rgb {
+    r: 230,
+    g: 159,
+    b: 0,
+};
+const VERMILLION: Color = Color::Rgb {
+    r: 213,
+    g: 94,
+    b: 0,
+};
+const LIGHT_YELLOW: Color = Color::Rgb {
+    r: 240,
+    g: 228,
+    b: 66,
+};
+
+pub const COLORBLIND_THEME: Theme = Theme {
+    name: "colorblind",
+    border: BLUE,
+    lives: ORANGE,
+    score: SKY_BLUE,
+    text: SKY_BLUE,
+    warning: VERMILLION,
+    player: Some(SKY_BLUE),
+    second_player: Some(Color::White),
+    asteroid: Some(ORANGE),
+    bullet: Some(LIGHT_YELLOW),
+    explosion: Some(VERMILLION),
+    heart: Some(ORANGE),
+    game_over: Some(SKY_BLUE),
+};
+
+pub const MONOCHROME_THEME: Theme = Theme {
+    name: "monochrome",
+    border: Color::Grey,
+    lives: Color::White,
+    score: Color::White,
+    text: Color::White,
+    warning: Color::White,
+    player: Some(Color::White),
+    second_player: Some(Color::Grey),
+    asteroid: Some(Color::Grey),
+    bullet: Some(Color::White),
+    explosion: Some(Color::White),
+    heart: Some(Color::White),
+    game_over: Some(Color::White),
+};
+
+pub const THEMES: [Theme; 4] = [
+    CLASSIC_THEME,
+    HIGH_CONTRAST_THEME,
+    COLORBLIND_THEME,
+    MONOCHROME_THEME,
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        CLASSIC_THEME
+    }
+}
+
+impl Theme {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase().replace(['-', ' '], "_");
+
+        THEMES.iter().find(|theme| theme.name == name).copied()
+    }
+
+    /// Returns the theme after this one, looping back to the first theme
+    pub fn next(&self) -> Self {
+        let index = THEMES
+            .iter()
+            .position(|theme| theme == self)
+            .unwrap_or_default();
+
+        THEMES[(index + 1) % THEMES.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Theme, CLASSIC_THEME, COLORBLIND_THEME, HIGH_CONTRAST_THEME, THEMES};
+
+    #[test]
+    fn it_finds_themes_by_name() {
+        assert_eq!(Theme::from_name("classic"), Some(CLASSIC_THEME));
+        assert_eq!(Theme::from_name("High-Contrast"), Some(HIGH_CONTRAST_THEME));
+        assert_eq!(Theme::from_name("neon"), None);
+    }
+
+    #[test]
+    fn it_cycles_through_the_themes() {
+        assert_eq!(CLASSIC_THEME.next(), HIGH_CONTRAST_THEME);
+        assert_eq!(HIGH_CONTRAST_THEME.next(), COLORBLIND_THEME);
+        assert_eq!(THEMES[THEMES.len() - 1].next(), CLASSIC_THEME);
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use super::Entity;
+
+/// Stores one type of component for the entities that have it
+#[derive(Debug)]
+pub struct ComponentStorage<T> {
+    components: HashMap<Entity, T>,
+}
+
+impl<T> ComponentStorage<T> {
+    pub fn new() -> Self {
+        Self {
+            components: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, entity: Entity, component: T) -> &mut Self {
+        self.components.insert(entity, component);
+
+        self
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        self.components.remove(&entity)
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.components.contains_key(&entity)
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.components.get(&entity)
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.components.get_mut(&entity)
+    }
+
+    /// Iterates over the components in no particular order, use the entities of the world when the order matters
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Entity, &mut T)> {
+        self.components.iter_mut()
+    }
+}
+
+impl<T> Default for ComponentStorage<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
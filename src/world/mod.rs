@@ -0,0 +1,6 @@
+mod component_storage;
+#[allow(clippy::module_inception)]
+mod world;
+
+pub use component_storage::*;
+pub use world::*;
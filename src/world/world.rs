@@ -0,0 +1,410 @@
+use uuid::Uuid;
+
+use crate::{
+    api::display::Point,
+    components::{
+        get_updated_health, CollisionLayer, DrawableState, DrawableType, Lifecycle,
+        LifecycleContext, SpawnCommand, Sprite,
+    },
+};
+
+use super::ComponentStorage;
+
+/// An entity is only an id, everything about it is stored in the component storages of the world
+pub type Entity = Uuid;
+
+/// Every entity in the game and their components. Systems iterate over the entities that have the components they need, so a new
+/// kind of entity is a new combination of components rather than a new controller.
+pub struct World {
+    /// Kept in spawn order so systems always visit the entities in the same order
+    entities: Vec<Entity>,
+    pub positions: ComponentStorage<Point<i64>>,
+    /// In cells per second
+    pub velocities: ComponentStorage<Point<i64>>,
+    pub sprites: ComponentStorage<Sprite>,
+    pub healths: ComponentStorage<u32>,
+    /// The collision layer of the entity, entities without a collider never collide
+    pub colliders: ComponentStorage<CollisionLayer>,
+    /// The damage the entity deals to the entities it collides with
+    pub damages: ComponentStorage<u32>,
+    /// The points awarded when the entity destroys another entity
+    pub scores: ComponentStorage<u64>,
+    /// How long the entity has left in milliseconds before it is despawned
+    pub lifetimes: ComponentStorage<u128>,
+    /// The entity that spawned this entity, e.g. the ship that fired a bullet
+    pub owners: ComponentStorage<Entity>,
+    /// The hooks invoked as the entity is spawned, damaged, destroyed or leaves the play field
+    pub lifecycles: ComponentStorage<Box<dyn Lifecycle>>,
+    /// The follow-up spawns returned by lifecycle hooks, waiting for the spawn command system
+    spawn_commands: Vec<SpawnCommand>,
+}
+
+/// Which lifecycle hook to invoke
+enum LifecycleHook {
+    Spawn,
+    Damage(u32),
+    Destroy,
+    Offscreen,
+}
+
+/// Adds components to a newly spawned entity
+pub struct EntityBuilder<'a> {
+    world: &'a mut World,
+    entity: Entity,
+}
+
+impl<'a> EntityBuilder<'a> {
+    pub fn with_position(self, position: Point<i64>) -> Self {
+        self.world.positions.insert(self.entity, position);
+
+        self
+    }
+
+    pub fn with_velocity(self, velocity: Point<i64>) -> Self {
+        self.world.velocities.insert(self.entity, velocity);
+
+        self
+    }
+
+    pub fn with_sprite(self, sprite: Sprite) -> Self {
+        self.world.sprites.insert(self.entity, sprite);
+
+        self
+    }
+
+    pub fn with_health(self, health: u32) -> Self {
+        self.world.healths.insert(self.entity, health);
+
+        self
+    }
+
+    pub fn with_collider(self, layer: CollisionLayer) -> Self {
+        self.world.colliders.insert(self.entity, layer);
+
+        self
+    }
+
+    pub fn with_damage(self, damage: u32) -> Self {
+        self.world.damages.insert(self.entity, damage);
+
+        self
+    }
+
+    pub fn with_score(self, points: u64) -> Self {
+        self.world.scores.insert(self.entity, points);
+
+        self
+    }
+
+    pub fn with_lifetime(self, lifetime: u128) -> Self {
+        self.world.lifetimes.insert(self.entity, lifetime);
+
+        self
+    }
+
+    pub fn with_lifecycle(self, lifecycle: impl Lifecycle + 'static) -> Self {
+        self.world
+            .lifecycles
+            .insert(self.entity, Box::new(lifecycle));
+
+        self
+    }
+
+    /// Finishes the entity, invoking its spawn hook now that all of its components are in place
+    pub fn build(self) -> Entity {
+        self.world
+            .run_lifecycle_hook(self.entity, LifecycleHook::Spawn);
+
+        self.entity
+    }
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self {
+            entities: vec![],
+            positions: ComponentStorage::new(),
+            velocities: ComponentStorage::new(),
+            sprites: ComponentStorage::new(),
+            healths: ComponentStorage::new(),
+            colliders: ComponentStorage::new(),
+            damages: ComponentStorage::new(),
+            scores: ComponentStorage::new(),
+            lifetimes: ComponentStorage::new(),
+            owners: ComponentStorage::new(),
+            lifecycles: ComponentStorage::new(),
+            spawn_commands: vec![],
+        }
+    }
+
+    /// Creates a new entity without any components, the components are added with the returned builder
+    pub fn spawn(&mut self) -> EntityBuilder<'_> {
+        let entity = Uuid::new_v4();
+
+        self.entities.push(entity);
+
+        EntityBuilder {
+            world: self,
+            entity,
+        }
+    }
+
+    /// Removes the entity and all of its components, despawning an entity that doesn't exist does nothing
+    pub fn despawn(&mut self, entity: Entity) -> &mut Self {
+        self.entities.retain(|existing| *existing != entity);
+
+        self.positions.remove(entity);
+        self.velocities.remove(entity);
+        self.sprites.remove(entity);
+        self.healths.remove(entity);
+        self.colliders.remove(entity);
+        self.damages.remove(entity);
+        self.scores.remove(entity);
+        self.lifetimes.remove(entity);
+        self.owners.remove(entity);
+        self.lifecycles.remove(entity);
+
+        self
+    }
+
+    /// Despawns an entity that has left the play field, invoking its offscreen hook first
+    pub fn despawn_offscreen(&mut self, entity: Entity) -> &mut Self {
+        self.run_lifecycle_hook(entity, LifecycleHook::Offscreen);
+
+        self.despawn(entity)
+    }
+
+    /// Takes the follow-up spawns queued by lifecycle hooks
+    pub fn take_spawn_commands(&mut self) -> Vec<SpawnCommand> {
+        std::mem::take(&mut self.spawn_commands)
+    }
+
+    /// Invokes a lifecycle hook of the entity, if it has any, and queues the spawn commands it returns
+    fn run_lifecycle_hook(&mut self, entity: Entity, hook: LifecycleHook) {
+        let Some(lifecycle) = self.lifecycles.get(entity) else {
+            return;
+        };
+
+        let context = LifecycleContext {
+            entity,
+            location: self.positions.get(entity).copied().unwrap_or_default(),
+            velocity: self.velocities.get(entity).copied().unwrap_or_default(),
+            size: self
+                .sprites
+                .get(entity)
+                .map(|sprite| sprite.get_layout().dimensions)
+                .unwrap_or_default(),
+        };
+
+        let mut spawn_commands = match hook {
+            LifecycleHook::Spawn => lifecycle.on_spawn(&context),
+            LifecycleHook::Damage(damage) => lifecycle.on_damage(&context, damage),
+            LifecycleHook::Destroy => lifecycle.on_destroy(&context),
+            LifecycleHook::Offscreen => lifecycle.on_offscreen(&context),
+        };
+
+        self.spawn_commands.append(&mut spawn_commands);
+    }
+
+    pub fn has_entity(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    /// The entities in the order they were spawned
+    pub fn get_entities(&self) -> Vec<Entity> {
+        self.entities.clone()
+    }
+
+    /// The number of entities that collide on the layer, e.g. the number of asteroids
+    pub fn count_on_layer(&self, layer: CollisionLayer) -> usize {
+        self.entities
+            .iter()
+            .filter(|entity| self.colliders.get(**entity) == Some(&layer))
+            .count()
+    }
+
+    /// Applies damage to an entity, entities without health can't be damaged
+    ///
+    /// # Returns
+    ///
+    /// * True if the entity was destroyed, destroyed entities are despawned
+    pub fn apply_damage(&mut self, entity: Entity, damage: u32) -> bool {
+        let Some(health) = self.healths.get_mut(entity) else {
+            return false;
+        };
+
+        *health = get_updated_health(*health, damage);
+
+        let destroyed = *health == 0;
+
+        if damage > 0 {
+            self.run_lifecycle_hook(entity, LifecycleHook::Damage(damage));
+        }
+
+        if destroyed {
+            self.run_lifecycle_hook(entity, LifecycleHook::Destroy);
+            self.despawn(entity);
+        }
+
+        destroyed
+    }
+
+    /// Restores health to an entity, entities without health can't be healed
+    ///
+    /// # Arguments
+    ///
+    /// * `max_health` - The health can't be restored past this
+    pub fn apply_healing(&mut self, entity: Entity, healing: u32, max_health: u32) -> &mut Self {
+        if let Some(health) = self.healths.get_mut(entity) {
+            *health = health.saturating_add(healing).min(max_health.max(*health));
+        }
+
+        self
+    }
+
+    /// The collision system describes what an entity collides as with a drawable type, it is built from the collider, damage and score
+    pub fn get_drawable_type(&self, entity: Entity) -> Option<DrawableType> {
+        let damage = self.damages.get(entity).copied().unwrap_or_default();
+
+        let drawable_type = match self.colliders.get(entity)? {
+            CollisionLayer::Player => DrawableType::Player,
+            CollisionLayer::Enemy => DrawableType::Enemy(damage),
+            CollisionLayer::Ammunition => DrawableType::Ammunition(
+                damage,
+                self.scores.get(entity).copied().unwrap_or_default() as u32,
+            ),
+            CollisionLayer::Border => DrawableType::Border,
+            CollisionLayer::Effect => DrawableType::Effect,
+            CollisionLayer::Pickup => DrawableType::Pickup,
+            CollisionLayer::Boss => DrawableType::Boss(damage),
+        };
+
+        Some(drawable_type)
+    }
+
+    /// The current shape and location of an entity, used by the systems that work on drawables such as collision detection
+    pub fn get_drawable_state(&self, entity: Entity) -> Option<DrawableState> {
+        let sprite = self.sprites.get(entity)?;
+
+        let mut drawable_state = DrawableState::new(
+            sprite.get_layout().clone(),
+            *self.positions.get(entity)?,
+            self.get_drawable_type(entity)
+                .unwrap_or(DrawableType::Effect),
+            self.velocities.get(entity).copied(),
+        );
+
+        drawable_state.anchor = sprite.anchor;
+        drawable_state.uuid = entity;
+
+        Some(drawable_state)
+    }
+
+    /// The drawable states of every entity that can collide
+    pub fn get_collidable_drawable_states(&self) -> Vec<DrawableState> {
+        self.entities
+            .iter()
+            .filter(|entity| self.colliders.contains(**entity))
+            .filter_map(|entity| self.get_drawable_state(*entity))
+            .collect()
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        components::{CollisionLayer, DrawableType, Lifecycle, LifecycleContext, SpawnCommand},
+    };
+
+    use super::World;
+
+    /// Explodes where it was destroyed
+    struct LifecycleMock;
+
+    impl Lifecycle for LifecycleMock {
+        fn on_destroy(&self, context: &LifecycleContext) -> Vec<SpawnCommand> {
+            vec![SpawnCommand::Explosion(context.location)]
+        }
+    }
+
+    #[test]
+    fn it_despawns_entities_with_all_their_components() {
+        let mut world = World::new();
+
+        let entity = world
+            .spawn()
+            .with_position(Point::new(1, 2))
+            .with_health(1)
+            .with_collider(CollisionLayer::Enemy)
+            .build();
+
+        assert_eq!(world.count_on_layer(CollisionLayer::Enemy), 1);
+
+        world.despawn(entity);
+
+        assert!(!world.has_entity(entity));
+        assert!(world.positions.get(entity).is_none());
+        assert_eq!(world.count_on_layer(CollisionLayer::Enemy), 0);
+    }
+
+    #[test]
+    fn it_destroys_entities_when_their_health_runs_out() {
+        let mut world = World::new();
+
+        let entity = world.spawn().with_health(2).build();
+        let indestructible = world.spawn().build();
+
+        assert!(!world.apply_damage(entity, 1));
+        assert!(world.apply_damage(entity, 1));
+        assert!(!world.has_entity(entity));
+
+        assert!(!world.apply_damage(indestructible, 10));
+        assert!(world.has_entity(indestructible));
+    }
+
+    #[test]
+    fn it_builds_the_drawable_type_from_the_components() {
+        let mut world = World::new();
+
+        let bullet = world
+            .spawn()
+            .with_collider(CollisionLayer::Ammunition)
+            .with_damage(1)
+            .with_score(3)
+            .build();
+
+        assert_eq!(
+            world.get_drawable_type(bullet),
+            Some(DrawableType::Ammunition(1, 3))
+        );
+    }
+
+    #[test]
+    fn it_queues_the_spawn_commands_of_lifecycle_hooks() {
+        let mut world = World::new();
+
+        let entity = world
+            .spawn()
+            .with_position(Point::new(3, 4))
+            .with_health(2)
+            .with_lifecycle(LifecycleMock)
+            .build();
+
+        world.apply_damage(entity, 1);
+        assert!(world.take_spawn_commands().is_empty());
+
+        world.apply_damage(entity, 1);
+        assert_eq!(
+            world.take_spawn_commands(),
+            vec![SpawnCommand::Explosion(Point::new(3, 4))]
+        );
+        assert!(world.take_spawn_commands().is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+//! Uses the game through the library, the way another tool would
+
+use crossterm::style::Color;
+use games::{
+    api::display::{Layout, Point},
+    app::{Environment, GameOptions, HeadlessInput, HeadlessRunner, Settings},
+    components::{DrawableState, DrawableType},
+    entities::{Action, Autopilot, AutopilotKind, AutopilotView},
+    systems::{run_collision_detection, CollisionEventKind, InteractionTable},
+};
+
+/// Fires whenever there is something to shoot at
+struct Turret;
+
+impl Autopilot for Turret {
+    fn get_actions(&mut self, view: &AutopilotView) -> Vec<Action> {
+        match view.get_enemies().is_empty() {
+            true => vec![],
+            false => vec![Action::Fire],
+        }
+    }
+}
+
+const TURRET: AutopilotKind = AutopilotKind {
+    name: "turret",
+    create: |_seed| Box::new(Turret),
+};
+
+#[test]
+fn it_detects_collisions_between_drawables() {
+    let bullet = DrawableState::new(
+        Layout::from_ascii("|", Color::White),
+        Point::new(5, 5),
+        DrawableType::Ammunition(1, 3),
+        None,
+    );
+    let asteroid = DrawableState::new(
+        Layout::from_ascii("###\n###", Color::Grey),
+        Point::new(4, 4),
+        DrawableType::Enemy(1),
+        None,
+    );
+
+    let events = run_collision_detection(
+        vec![&bullet, &asteroid],
+        &Point::new(20, 20),
+        &InteractionTable::default(),
+    );
+
+    assert!(events
+        .iter()
+        .any(|event| event.kind == CollisionEventKind::BulletHitEnemy
+            && event.target.uuid == asteroid.uuid));
+}
+
+#[test]
+fn it_draws_onto_layouts() {
+    let mut layout = Layout::new(&Point::new(10, 3), None);
+
+    layout
+        .draw_str("ship", &Point::new(3, 1), None, Some(Color::Green))
+        .unwrap();
+
+    let changed = layout.get_changed_elements(&Layout::new(&Point::new(10, 3), None));
+
+    assert_eq!(changed.len(), 4);
+    assert_eq!(changed[0].0, Point::new(3, 1));
+}
+
+#[test]
+fn it_flies_ships_with_custom_autopilots() {
+    let results = HeadlessRunner::new(Point::new(120, 40), Settings::default())
+        .with_options(GameOptions {
+            seed: Some(3),
+            ..Default::default()
+        })
+        .unwrap()
+        .with_input(HeadlessInput::Autopilot(TURRET))
+        .with_max_frames(500)
+        .run(2)
+        .unwrap();
+
+    assert!(results.iter().all(|result| result.stats.bullet_hits > 0));
+}
+
+#[test]
+fn it_steps_games_for_agents() {
+    let mut environment = Environment::new(Point::new(120, 40), Settings::default());
+
+    let observation = environment.reset(3).unwrap();
+    let result = environment.step(&[Action::Left, Action::Fire]).unwrap();
+
+    assert_eq!(observation.players.len(), 1);
+    assert!(
+        result.observation.players[0].location.unwrap().width
+            < observation.players[0].location.unwrap().width
+    );
+    assert!(!result.done);
+}